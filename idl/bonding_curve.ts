@@ -25,6 +25,14 @@ export type BondingCurve = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the protocol fee of every trade paid in `new_accepted_base_mint`."
+          ]
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -50,6 +58,31 @@ export type BondingCurve = {
         }
       ]
     },
+    {
+      "name": "setCreationFeeInstruction",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "creationFee",
+          "type": "u64"
+        },
+        {
+          "name": "inBaseMint",
+          "type": "bool"
+        }
+      ]
+    },
     {
       "name": "mintFullSupplyInstruction",
       "accounts": [
@@ -86,8 +119,42 @@ export type BondingCurve = {
         {
           "name": "paymentMint",
           "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The core's accepted base mint; its decimals scale the graduation threshold."
+          ]
+        },
+        {
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the creator's slice of every trade until the creator claims it."
+          ]
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "creatorTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Creator's base token account; only needed when the creation fee is in the base mint"
+          ]
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Protocol fee vault of the base mint; only needed when the creation fee is in it"
+          ]
+        },
         {
           "name": "metadataAccount",
           "isMut": true,
@@ -189,6 +256,54 @@ export type BondingCurve = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the protocol's trading fees in the base mint."
+          ]
+        },
+        {
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the creator's slice of this token's trades."
+          ]
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Records the buyer's referrer; the first one sticks."
+          ]
+        },
+        {
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "referralVault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The referrer's share of the trading fees in the base mint, claimable by the referrer.",
+            "Only used together with `referrer`."
+          ]
+        },
+        {
+          "name": "traderStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The buyer's rolling volume for the fee tiers."
+          ]
+        },
         {
           "name": "associatedTokenProgram",
           "isMut": false,
@@ -217,7 +332,7 @@ export type BondingCurve = {
       ]
     },
     {
-      "name": "sellExactInputInstruction",
+      "name": "buyExactOutputInstruction",
       "accounts": [
         {
           "name": "tokenSeed",
@@ -225,7 +340,7 @@ export type BondingCurve = {
           "isSigner": false
         },
         {
-          "name": "user",
+          "name": "buyer",
           "isMut": true,
           "isSigner": true
         },
@@ -242,49 +357,79 @@ export type BondingCurve = {
         {
           "name": "escrowTokenAccount",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "The escrow SPL token account that holds the *payment* tokens (e.g. USDC)."
-          ]
+          "isSigner": false
         },
         {
           "name": "paymentMint",
           "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
           "isSigner": false,
           "docs": [
-            "The SPL mint of the payment token (e.g., USDC)."
+            "Collects the protocol's trading fees in the base mint."
           ]
         },
         {
-          "name": "mint",
+          "name": "creatorFeeVault",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Token mint (fully minted at init)."
+            "Collects the creator's slice of this token's trades."
           ]
         },
         {
-          "name": "vaultTokenAccount",
+          "name": "referral",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "The vault that holds project’s tokens."
+            "Records the buyer's referrer; the first one sticks."
           ]
         },
         {
-          "name": "userTokenAccount",
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "referralVault",
           "isMut": true,
           "isSigner": false,
+          "isOptional": true,
           "docs": [
-            "The user’s token account holding tokens."
+            "The referrer's share of the trading fees in the base mint, claimable by the referrer.",
+            "Only used together with `referrer`."
           ]
         },
         {
-          "name": "userPaymentAccount",
+          "name": "traderStats",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "The user’s associated token account for the *payment* token."
+            "The buyer's rolling volume for the fee tiers."
           ]
         },
         {
@@ -305,88 +450,141 @@ export type BondingCurve = {
       ],
       "args": [
         {
-          "name": "normalizedTokenAmount",
+          "name": "tokensOut",
           "type": "u64"
         },
         {
-          "name": "minBaseAmountOut",
+          "name": "maxBaseIn",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "withdrawLiquidity",
+      "name": "sellExactInputInstruction",
       "accounts": [
         {
-          "name": "xyberCore",
-          "isMut": true,
+          "name": "tokenSeed",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "user",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "xyberToken",
+          "name": "xyberCore",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "mint",
+          "name": "xyberToken",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vaultTokenAccount",
+          "name": "escrowTokenAccount",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The escrow SPL token account that holds the *payment* tokens (e.g. USDC)."
+          ]
         },
         {
-          "name": "tokenSeed",
+          "name": "paymentMint",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The SPL mint of the payment token (e.g., USDC)."
+          ]
         },
         {
-          "name": "creator",
-          "isMut": false,
-          "isSigner": false
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Token mint (fully minted at init)."
+          ]
         },
         {
-          "name": "escrowTokenAccount",
+          "name": "vaultTokenAccount",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Escrow token account holding the payment tokens (e.g. USDC)"
+            "The vault that holds project’s tokens."
           ]
         },
         {
-          "name": "baseTokenMint",
-          "isMut": false,
-          "isSigner": false
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user’s token account holding tokens."
+          ]
         },
         {
-          "name": "adminTokenAccount",
+          "name": "userPaymentAccount",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The user’s associated token account for the *payment* token."
+          ]
         },
         {
-          "name": "adminVaultAccount",
+          "name": "feeVault",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "Collects the protocol's trading fees in the base mint."
+          ]
         },
         {
-          "name": "rent",
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the creator's slice of this token's trades."
+          ]
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Records the user's referrer; the first one sticks."
+          ]
+        },
+        {
+          "name": "referrer",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "tokenProgram",
+          "name": "referralVault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The referrer's share of the trading fees in the base mint, claimable by the referrer.",
+            "Only used together with `referrer`."
+          ]
+        },
+        {
+          "name": "traderStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user's rolling volume for the fee tiers."
+          ]
+        },
+        {
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
@@ -396,378 +594,138 @@ export type BondingCurve = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "tokenAmount",
+          "type": "u64"
+        },
+        {
+          "name": "minBaseAmountOut",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "closeXyberCoreInstruction",
+      "name": "sellExactOutputInstruction",
       "accounts": [
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "xyberCore",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "xyberToken",
           "isMut": true,
-          "isSigner": true
-        }
-      ],
-      "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "xyberCore",
-      "docs": [
-        "The sixbte, global state for all tokens."
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": "publicKey"
-          },
-          {
-            "name": "gradThreshold",
-            "type": "u64"
-          },
-          {
-            "name": "totalSupply",
-            "type": "u64"
-          },
-          {
-            "name": "bondingCurve",
-            "type": {
-              "defined": "SmoothBondingCurve"
-            }
-          },
-          {
-            "name": "acceptedBaseMint",
-            "type": "publicKey"
-          }
-        ]
-      }
-    },
-    {
-      "name": "xyberToken",
-      "docs": [
-        "One account per unique token. It holds only “token-specific” info."
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "isGraduated",
-            "type": "bool"
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "vault",
-            "type": "publicKey"
-          },
-          {
-            "name": "creator",
-            "type": "publicKey"
-          },
-          {
-            "name": "totalChains",
-            "type": "u8"
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "SmoothBondingCurve",
-      "docs": [
-        "A smooth bonding curve referencing the base asset (e.g., SOL, XBT) deposited.",
-        "",
-        "Formula: y(x) = A - (K / (C + x))",
-        "- A = asymptotic max token supply (in integer \"token units\")",
-        "- K = (token * lamport), controlling how quickly we approach A",
-        "- C = virtual pool offset (in base_tokens)",
-        "",
-        "NOTE: We no longer store `x_total_base_deposit` inside the struct.",
-        "Instead, the caller passes the current x each time."
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "aTotalTokens",
-            "docs": [
-              "Asymptotic total token supply (in \"raw\" tokens)"
-            ],
-            "type": "u64"
-          },
-          {
-            "name": "kVirtualPoolOffset",
-            "docs": [
-              "Controls how quickly we approach A (token * lamport)"
-            ],
-            "type": "u128"
-          },
-          {
-            "name": "cBondingScaleFactor",
-            "docs": [
-              "Virtual pool offset (in base_tokens)"
-            ],
-            "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "InitCoreParams",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": {
-              "option": "publicKey"
-            }
-          },
-          {
-            "name": "gradThreshold",
-            "type": {
-              "option": "u64"
-            }
-          },
-          {
-            "name": "bondingCurve",
-            "type": {
-              "option": {
-                "defined": "SmoothBondingCurve"
-              }
-            }
-          },
-          {
-            "name": "acceptedBaseMint",
-            "type": {
-              "option": "publicKey"
-            }
-          },
-          {
-            "name": "totalSupply",
-            "type": {
-              "option": "u64"
-            }
-          }
-        ]
-      }
-    },
-    {
-      "name": "TokenParams",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "name",
-            "type": "string"
-          },
-          {
-            "name": "symbol",
-            "type": "string"
-          },
-          {
-            "name": "uri",
-            "type": "string"
-          },
-          {
-            "name": "tokenSeed",
-            "type": "publicKey"
-          },
-          {
-            "name": "totalChains",
-            "type": "u8"
-          }
-        ]
-      }
-    },
-    {
-      "name": "XyberInstructionType",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "BuyExactIn"
-          },
-          {
-            "name": "BuyExactOut"
-          },
-          {
-            "name": "SellExactIn"
-          },
-          {
-            "name": "SellExactOut"
-          }
-        ]
-      }
-    }
-  ],
-  "events": [
-    {
-      "name": "GraduationTriggered",
-      "fields": [
-        {
-          "name": "buyer",
-          "type": "publicKey",
-          "index": false
+          "isSigner": false
         },
         {
-          "name": "escrowBalance",
-          "type": "u64",
-          "index": false
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The escrow SPL token account that holds the *payment* tokens (e.g. USDC)."
+          ]
         },
         {
-          "name": "vault",
-          "type": "publicKey",
-          "index": false
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The SPL mint of the payment token (e.g., USDC)."
+          ]
         },
         {
-          "name": "creator",
-          "type": "publicKey",
-          "index": false
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Token mint (fully minted at init)."
+          ]
         },
         {
-          "name": "escrow",
-          "type": "publicKey",
-          "index": false
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The vault that holds project’s tokens."
+          ]
         },
         {
-          "name": "tokenSeed",
-          "type": "publicKey",
-          "index": false
-        }
-      ]
-    },
-    {
-      "name": "XyberSwapEvent",
-      "fields": [
-        {
-          "name": "ixType",
-          "type": {
-            "defined": "XyberInstructionType"
-          },
-          "index": false
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user’s token account holding tokens."
+          ]
         },
         {
-          "name": "tokenSeed",
-          "type": "publicKey",
-          "index": false
-        },
-        {
-          "name": "user",
-          "type": "publicKey",
-          "index": false
+          "name": "userPaymentAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user’s associated token account for the *payment* token."
+          ]
         },
         {
-          "name": "baseAmount",
-          "type": "u64",
-          "index": false
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the protocol's trading fees in the base mint."
+          ]
         },
         {
-          "name": "tokenAmount",
-          "type": "u64",
-          "index": false
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the creator's slice of this token's trades."
+          ]
         },
         {
-          "name": "vaultTokenAmount",
-          "type": "u64",
-          "index": false
-        }
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "InsufficientTokenSupply",
-      "msg": "Custom error: Token supply is not enough to fulfill buy request"
-    },
-    {
-      "code": 6001,
-      "name": "MathOverflow",
-      "msg": "Provide a smaller amount. Use normalized tokens (e.g., raw value / 10 ** decimals)."
-    },
-    {
-      "code": 6002,
-      "name": "Unauthorized",
-      "msg": "Unauthorized: Caller is not authorized to perform this action."
-    },
-    {
-      "code": 6003,
-      "name": "BondingCurveNotGraduated",
-      "msg": "Bonding Curve not graduated: pool has not reached the required threshold."
-    },
-    {
-      "code": 6004,
-      "name": "InsufficientTokenVaultBalance",
-      "msg": "Insufficient token balance in the vault to fulfill the request."
-    },
-    {
-      "code": 6005,
-      "name": "InsufficientEscrowBalance",
-      "msg": "Insufficient escrow balance: Not enough tokens in escrow to complete the operation."
-    },
-    {
-      "code": 6006,
-      "name": "TokenIsGraduated",
-      "msg": "Token has graduated: The bonding curve is no longer active as the token is now listed on a DEX."
-    },
-    {
-      "code": 6007,
-      "name": "InvalidSeed",
-      "msg": "Invalid seed: the provided seed must be exactly 32 bytes in length."
-    },
-    {
-      "code": 6008,
-      "name": "SlippageExceeded",
-      "msg": "Slippage exceeded user-defined limit."
-    },
-    {
-      "code": 6009,
-      "name": "WrongPaymentMint",
-      "msg": "Wrong payment mint provided."
-    }
-  ]
-};
-
-export const IDL: BondingCurve = {
-  "version": "0.1.0",
-  "name": "bonding_curve",
-  "instructions": [
-    {
-      "name": "updateXyberCoreInstruction",
-      "accounts": [
-        {
-          "name": "admin",
+          "name": "referral",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "docs": [
+            "Records the user's referrer; the first one sticks."
+          ]
         },
         {
-          "name": "xyberCore",
-          "isMut": true,
-          "isSigner": false
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "newAcceptedBaseMint",
+          "name": "referralVault",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The referrer's share of the trading fees in the base mint, claimable by the referrer.",
+            "Only used together with `referrer`."
+          ]
         },
         {
-          "name": "escrowTokenAccount",
+          "name": "traderStats",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The user's rolling volume for the fee tiers."
+          ]
         },
         {
-          "name": "systemProgram",
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
         },
@@ -777,161 +735,260 @@ export const IDL: BondingCurve = {
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "params",
-          "type": {
-            "defined": "InitCoreParams"
-          }
+          "name": "baseAmountOut",
+          "type": "u64"
+        },
+        {
+          "name": "maxTokenAmount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "mintFullSupplyInstruction",
+      "name": "quoteBuyInstruction",
       "accounts": [
         {
-          "name": "creator",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "xyberToken",
-          "isMut": true,
+          "name": "tokenSeed",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "xyberCore",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "mint",
-          "isMut": true,
+          "name": "xyberToken",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "vaultTokenAccount",
-          "isMut": true,
-          "isSigner": false
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The vault that holds the project's tokens; buys are quoted against its balance."
+          ]
         },
         {
-          "name": "escrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The base mint; its decimals scale the graduation threshold."
+          ]
         },
         {
-          "name": "paymentMint",
+          "name": "trader",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "metadataAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "traderStats",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The trader's volume stats; quotes at the undiscounted fee without them."
+          ]
         },
         {
-          "name": "rent",
+          "name": "referral",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The trader's recorded referrer, if they have one."
+          ]
         },
         {
-          "name": "tokenMetadataProgram",
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Validated against the recorded one like the trade instructions do."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "exactInput",
+          "type": "bool"
+        }
+      ],
+      "returns": {
+        "defined": "TradeQuote"
+      }
+    },
+    {
+      "name": "quoteSellInstruction",
+      "accounts": [
+        {
+          "name": "tokenSeed",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "xyberCore",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "xyberToken",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "vaultTokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The vault that holds the project's tokens; buys are quoted against its balance."
+          ]
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The base mint; its decimals scale the graduation threshold."
+          ]
+        },
+        {
+          "name": "trader",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenFactoryProgram",
+          "name": "traderStats",
           "isMut": false,
           "isSigner": false,
+          "isOptional": true,
           "docs": [
-            "External factory program"
+            "The trader's volume stats; quotes at the undiscounted fee without them."
+          ]
+        },
+        {
+          "name": "referral",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The trader's recorded referrer, if they have one."
+          ]
+        },
+        {
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Validated against the recorded one like the trade instructions do."
           ]
         }
       ],
       "args": [
         {
-          "name": "params",
-          "type": {
-            "defined": "TokenParams"
-          }
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "exactInput",
+          "type": "bool"
         }
-      ]
+      ],
+      "returns": {
+        "defined": "TradeQuote"
+      }
     },
     {
-      "name": "buyExactInputInstruction",
+      "name": "withdrawLiquidity",
       "accounts": [
         {
-          "name": "tokenSeed",
-          "isMut": false,
+          "name": "xyberCore",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyer",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "xyberCore",
+          "name": "xyberToken",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "xyberToken",
+          "name": "mint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "escrowTokenAccount",
+          "name": "vaultTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "paymentMint",
+          "name": "tokenSeed",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "mint",
-          "isMut": true,
+          "name": "creator",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vaultTokenAccount",
+          "name": "escrowTokenAccount",
           "isMut": true,
-          "isSigner": false
-        },
+          "isSigner": false,
+          "docs": [
+            "Escrow token account holding the payment tokens (e.g. USDC)"
+          ]
+        },
         {
-          "name": "buyerTokenAccount",
+          "name": "baseTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyerPaymentAccount",
+          "name": "adminVaultAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "creatorTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the protocol's graduation fee"
+          ]
+        },
+        {
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
@@ -941,42 +998,53 @@ export const IDL: BondingCurve = {
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "baseIn",
-          "type": "u64"
         },
         {
-          "name": "minAmountOut",
-          "type": "u64"
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "sellExactInputInstruction",
+      "name": "migrate",
       "accounts": [
         {
-          "name": "tokenSeed",
+          "name": "xyberCore",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "user",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "xyberCore",
+          "name": "xyberToken",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "xyberToken",
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "creator",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -985,82 +1053,81 @@ export const IDL: BondingCurve = {
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "The escrow SPL token account that holds the *payment* tokens (e.g. USDC)."
+            "Escrow token account holding the payment tokens (e.g. USDC)"
           ]
         },
         {
-          "name": "paymentMint",
+          "name": "baseTokenMint",
           "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "The SPL mint of the payment token (e.g., USDC)."
-          ]
+          "isSigner": false
         },
         {
-          "name": "mint",
+          "name": "adminTokenAccount",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Token mint (fully minted at init)."
-          ]
+          "isSigner": false
         },
         {
-          "name": "vaultTokenAccount",
+          "name": "creatorTokenAccount",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "The vault that holds project’s tokens."
-          ]
+          "isSigner": false
         },
         {
-          "name": "userTokenAccount",
+          "name": "feeVault",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "The user’s token account holding tokens."
+            "Receives the protocol's graduation fee"
           ]
         },
         {
-          "name": "userPaymentAccount",
+          "name": "pool",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "The user’s associated token account for the *payment* token."
-          ]
+          "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "poolBaseVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ammProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "normalizedTokenAmount",
-          "type": "u64"
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "minBaseAmountOut",
-          "type": "u64"
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "withdrawLiquidity",
+      "name": "sweepDonationsInstruction",
       "accounts": [
         {
           "name": "xyberCore",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1070,57 +1137,135 @@ export const IDL: BondingCurve = {
         },
         {
           "name": "xyberToken",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "mint",
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
           "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Escrow token account holding the payment tokens (e.g. USDC)"
+          ]
+        },
+        {
+          "name": "baseTokenMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vaultTokenAccount",
+          "name": "adminTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenSeed",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "creator",
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "escrowTokenAccount",
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimProtocolFees",
+      "accounts": [
+        {
+          "name": "xyberCore",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Escrow token account holding the payment tokens (e.g. USDC)"
-          ]
+          "isSigner": true
         },
         {
           "name": "baseTokenMint",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Protocol fees collected in `base_token_mint`"
+          ]
+        },
         {
           "name": "adminTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "adminVaultAccount",
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimCreatorFees",
+      "accounts": [
+        {
+          "name": "creator",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberToken",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "tokenSeed",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "baseTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Creator fees accrued by this token's trades"
+          ]
+        },
+        {
+          "name": "creatorTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -1140,224 +1285,4065 @@ export const IDL: BondingCurve = {
       "args": []
     },
     {
-      "name": "closeXyberCoreInstruction",
+      "name": "claimReferralFees",
       "accounts": [
         {
           "name": "xyberCore",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "referrer",
           "isMut": true,
           "isSigner": true
-        }
-      ],
-      "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "xyberCore",
-      "docs": [
-        "The sixbte, global state for all tokens."
-      ],
+        },
+        {
+          "name": "baseTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "referralVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Referral fees earned by `referrer` in `base_token_mint`"
+          ]
+        },
+        {
+          "name": "referrerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeXyberCoreInstruction",
+      "accounts": [
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "xyberCore",
+      "docs": [
+        "The sixbte, global state for all tokens."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "gradThreshold",
+            "type": "u64"
+          },
+          {
+            "name": "totalSupply",
+            "type": "u64"
+          },
+          {
+            "name": "bondingCurve",
+            "type": {
+              "defined": "BondingCurveType"
+            }
+          },
+          {
+            "name": "acceptedBaseMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "maxPriceImpactBps",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "creatorFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "referralShareBps",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeDecaySlots",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeToCreator",
+            "type": "bool"
+          },
+          {
+            "name": "feeTiers",
+            "type": {
+              "array": [
+                {
+                  "defined": "FeeTier"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "volumeWindowSlots",
+            "type": "u64"
+          },
+          {
+            "name": "creationFee",
+            "type": "u64"
+          },
+          {
+            "name": "creationFeeInBaseMint",
+            "type": "bool"
+          },
+          {
+            "name": "graduationProtocolFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "graduationCreatorFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "totalProtocolFees",
+            "type": "u64"
+          },
+          {
+            "name": "totalCreatorFees",
+            "type": "u64"
+          },
+          {
+            "name": "totalReferralFees",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "xyberToken",
+      "docs": [
+        "One account per unique token. It holds only “token-specific” info."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "isGraduated",
+            "type": "bool"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "totalChains",
+            "type": "u8"
+          },
+          {
+            "name": "realBaseReserve",
+            "type": "u64"
+          },
+          {
+            "name": "tokensSold",
+            "type": "u64"
+          },
+          {
+            "name": "bondingCurve",
+            "type": {
+              "defined": "BondingCurveType"
+            }
+          },
+          {
+            "name": "gradThreshold",
+            "type": "u64"
+          },
+          {
+            "name": "totalSupply",
+            "type": "u64"
+          },
+          {
+            "name": "totalProtocolFees",
+            "type": "u64"
+          },
+          {
+            "name": "totalCreatorFees",
+            "type": "u64"
+          },
+          {
+            "name": "totalReferralFees",
+            "type": "u64"
+          },
+          {
+            "name": "creatorFeesClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "launchSlot",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeDecaySlots",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeToCreator",
+            "type": "bool"
+          },
+          {
+            "name": "migrated",
+            "type": "bool"
+          },
+          {
+            "name": "withdrawn",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referral",
+      "docs": [
+        "One account per trader, recording who referred them."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "referrer",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "traderStats",
+      "docs": [
+        "One account per trader, tracking their base volume (raw units, before fees) across all",
+        "tokens for the fee tiers."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "currentVolume",
+            "type": "u64"
+          },
+          {
+            "name": "previousVolume",
+            "type": "u64"
+          },
+          {
+            "name": "windowStartSlot",
+            "type": "u64"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "ConstantProductBondingCurve",
+      "docs": [
+        "A constant-product (x * y = k) curve over virtual reserves, as used by pump.fun.",
+        "",
+        "Invariant: (V_base + x) * (V_token - y) = V_base * V_token",
+        "Inverse:   y(x) = V_token - V_base * V_token / (V_base + x)",
+        "- V_base  = virtual base reserve (in base_tokens)",
+        "- V_token = virtual token reserve (in raw token base units, i.e. scaled by 10^decimals)",
+        "- R       = real token reserve, the tokens actually sold on the curve (R < V_token)",
+        "",
+        "Token amounts are rounded down and base amounts up."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "virtualBaseReserve",
+            "docs": [
+              "Virtual base reserve (in base_tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "virtualTokenReserve",
+            "docs": [
+              "Virtual token reserve (in raw tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "realTokenReserve",
+            "docs": [
+              "Tokens that can actually be sold on the curve"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ExponentialBondingCurve",
+      "docs": [
+        "A bonding curve whose marginal price grows exponentially with the tokens sold.",
+        "",
+        "Price:   p(y) = (V / L) * e^(y / L)",
+        "Deposit: x(y) = V * (e^(y / L) - 1)",
+        "Inverse: y(x) = L * ln((V + x) / V)",
+        "- A = total tokens sold on the curve (in raw token base units, i.e. scaled by 10^decimals)",
+        "- V = virtual base reserve (in base_tokens), sets the starting price V / L",
+        "- L = growth length (in raw tokens): the price grows by a factor of e every L tokens",
+        "",
+        "`exp` and `ln` are evaluated in WAD fixed-point (see `curves::math`); `exp` is",
+        "rounded up and `ln` down, so every trade still rounds in the pool's favor."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "aTotalTokens",
+            "docs": [
+              "Total tokens that can be sold on the curve"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "vVirtualBaseReserve",
+            "docs": [
+              "Virtual base reserve that sets the starting price (in base_tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lGrowthTokens",
+            "docs": [
+              "Tokens sold per e-fold of the price"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LinearBondingCurve",
+      "docs": [
+        "A bonding curve whose marginal price grows linearly with the tokens sold.",
+        "",
+        "Price:   p(y) = (B + y) / M",
+        "Deposit: x(y) = y * (2B + y) / (2M)",
+        "Inverse: y(x) = sqrt(B^2 + 2M * x) - B",
+        "- A = total tokens sold on the curve (in raw token base units, i.e. scaled by 10^decimals)",
+        "- B = virtual token offset, sets the starting price B / M",
+        "- M = price divisor (raw token * raw token / base_token), sets the slope 1 / M",
+        "",
+        "Token amounts are rounded down and base amounts up, so the pool never",
+        "hands out more than the exact curve would."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "aTotalTokens",
+            "docs": [
+              "Total tokens that can be sold on the curve"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bVirtualTokenOffset",
+            "docs": [
+              "Virtual token offset that sets the starting price (in raw tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "mPriceDivisor",
+            "docs": [
+              "Slope divisor of the price line (raw token * raw token / base_token)"
+            ],
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LogisticBondingCurve",
+      "docs": [
+        "A bonding curve whose price follows a logistic (sigmoid) function of the tokens",
+        "sold: nearly flat early, steepest at the midpoint and flat again near the end.",
+        "",
+        "Price:   p(y) = P_min + (P_max - P_min) * sigma((y - M) / S)",
+        "Deposit: x(y) = integral of p from 0 to y",
+        "- A = total tokens sold on the curve (in raw token base units, i.e. scaled by 10^decimals)",
+        "- P_min, P_max = price floor and ceiling (base_tokens per raw token, scaled by WAD)",
+        "- M = tokens sold at the midpoint, where the price grows the fastest",
+        "- S = tokens per unit of z, i.e. how stretched the S-shape is",
+        "",
+        "On-chain, sigma is read from `SIGMOID_WAD` and interpolated linearly, so p(y) is",
+        "piecewise linear and x(y) piecewise quadratic. Both are evaluated exactly as a",
+        "256-bit numerator over 2 * (S / 4) * WAD, and y(x) is found by a binary search",
+        "inside the one piece that x falls into. Token amounts are rounded down and base",
+        "amounts up."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "aTotalTokens",
+            "docs": [
+              "Total tokens that can be sold on the curve"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "pMinPrice",
+            "docs": [
+              "Price floor (base_tokens per raw token, scaled by WAD)"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "pMaxPrice",
+            "docs": [
+              "Price ceiling (base_tokens per raw token, scaled by WAD)"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "mMidpointTokens",
+            "docs": [
+              "Tokens sold at the midpoint of the S-shape (in raw tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sScaleTokens",
+            "docs": [
+              "Tokens per unit of the sigmoid's argument, a multiple of 4 (in raw tokens)"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FlatSegment",
+      "docs": [
+        "A fixed-price segment, e.g. a presale before the curve starts moving.",
+        "",
+        "y(x) = x / P",
+        "- P = price (base_tokens per raw token, scaled by `math::WAD`)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "docs": [
+              "Price in base_tokens per raw token, scaled by WAD"
+            ],
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PiecewiseSegment",
+      "docs": [
+        "One segment of a piecewise curve, active from `x_start` base_tokens deposited",
+        "up to the next segment's `x_start`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "xStart",
+            "docs": [
+              "Breakpoint in base_tokens deposited where this segment starts"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "curve",
+            "docs": [
+              "The curve of this segment, evaluated at x - x_start"
+            ],
+            "type": {
+              "defined": "SegmentCurve"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PiecewiseBondingCurve",
+      "docs": [
+        "A bonding curve made of several segments, e.g. a flat presale price, then a",
+        "smooth hyperbolic curve, then a steep tail.",
+        "",
+        "Y(x) = T_i + y_i(x - X_i) for X_i <= x < X_(i+1)",
+        "- X_i = breakpoint of segment i in base_tokens deposited (X_0 = 0)",
+        "- y_i = the curve of segment i, each starting at y_i(0) = 0",
+        "- T_i = tokens sold by all earlier segments, each counted as ceil(y_j(X_(j+1) - X_j))",
+        "",
+        "Rounding each finished segment up keeps Y non-decreasing across breakpoints, and",
+        "every trade still brackets Y with floor/ceil exactly like a single curve does, so",
+        "a trade spanning several segments is priced in the pool's favor as a whole."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "segments",
+            "docs": [
+              "Segments ordered by breakpoint, the first one starting at 0"
+            ],
+            "type": {
+              "vec": {
+                "defined": "PiecewiseSegment"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "SmoothBondingCurve",
+      "docs": [
+        "A smooth bonding curve referencing the base asset (e.g., SOL, XBT) deposited.",
+        "",
+        "Formula: y(x) = A - (K / (C + x))",
+        "- A = asymptotic max token supply (in raw token base units, i.e. scaled by 10^decimals)",
+        "- K = (raw token * base unit), controlling how quickly we approach A",
+        "- C = virtual pool offset (in base_tokens)",
+        "",
+        "NOTE: We no longer store `x_total_base_deposit` inside the struct.",
+        "Instead, the caller passes the current x each time."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "aTotalTokens",
+            "docs": [
+              "Asymptotic total token supply (in \"raw\" tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "kVirtualPoolOffset",
+            "docs": [
+              "Controls how quickly we approach A (raw token * base unit)"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "cBondingScaleFactor",
+            "docs": [
+              "Virtual pool offset (in base_tokens)"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeTier",
+      "docs": [
+        "A volume tier: traders with at least `min_volume` rolling base volume (raw units) get",
+        "the regular trading fee reduced by `discount_bps` of it. Unused entries are all zero."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minVolume",
+            "type": "u64"
+          },
+          {
+            "name": "discountBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TradeQuote",
+      "docs": [
+        "The outcome of a trade against the current token state.",
+        "",
+        "Returned through `set_return_data`, so simulating a quote instruction yields the",
+        "same numbers the trade instruction would produce in the same slot."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "baseAmount",
+            "docs": [
+              "Base tokens moving on the curve: paid in (buys) or paid out (sells), before the fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "name": "creatorFee",
+            "type": "u64"
+          },
+          {
+            "name": "referralFee",
+            "docs": [
+              "The referrer's share, taken out of the protocol fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sniperFee",
+            "docs": [
+              "Buys only: anti-sniper surcharge right after launch, on top of the fees above"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tokenAmount",
+            "docs": [
+              "Project tokens (raw units) received (buys) or paid in (sells)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "newReserve",
+            "docs": [
+              "`real_base_reserve` after the trade"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "spotPriceBefore",
+            "type": "u128"
+          },
+          {
+            "name": "spotPriceAfter",
+            "type": "u128"
+          },
+          {
+            "name": "priceImpactBps",
+            "docs": [
+              "How far the trade moves the spot price, in bps of `spot_price_before` (rounded up)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tokensRemaining",
+            "docs": [
+              "Tokens (raw units) still for sale on the curve after the trade"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "partialFill",
+            "docs": [
+              "Buys only: the buy is capped at the tokens left for sale or at the graduation",
+              "threshold, and only `token_amount` for `base_amount` is filled"
+            ],
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "InitCoreParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "gradThreshold",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "bondingCurve",
+            "type": {
+              "option": {
+                "defined": "BondingCurveType"
+              }
+            }
+          },
+          {
+            "name": "acceptedBaseMint",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "totalSupply",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxPriceImpactBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "protocolFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "creatorFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "referralShareBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "sniperFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "sniperFeeDecaySlots",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "sniperFeeToCreator",
+            "type": {
+              "option": "bool"
+            }
+          },
+          {
+            "name": "feeTiers",
+            "type": {
+              "option": {
+                "array": [
+                  {
+                    "defined": "FeeTier"
+                  },
+                  4
+                ]
+              }
+            }
+          },
+          {
+            "name": "volumeWindowSlots",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "graduationProtocolFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "graduationCreatorFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "TokenParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "symbol",
+            "type": "string"
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          },
+          {
+            "name": "tokenSeed",
+            "type": "publicKey"
+          },
+          {
+            "name": "totalChains",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SegmentCurve",
+      "docs": [
+        "The curve used within one segment, driven by the base deposited since the",
+        "segment's breakpoint."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Flat",
+            "fields": [
+              {
+                "defined": "FlatSegment"
+              }
+            ]
+          },
+          {
+            "name": "Smooth",
+            "fields": [
+              {
+                "defined": "SmoothBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Linear",
+            "fields": [
+              {
+                "defined": "LinearBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Exponential",
+            "fields": [
+              {
+                "defined": "ExponentialBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "ConstantProduct",
+            "fields": [
+              {
+                "defined": "ConstantProductBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Logistic",
+            "fields": [
+              {
+                "defined": "LogisticBondingCurve"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "BondingCurveType",
+      "docs": [
+        "The curve family used to price a token, stored on-chain in `XyberCore`.",
+        "",
+        "Every variant wraps a concrete curve and all pricing goes through",
+        "`BondingCurveTrait`, so instruction handlers never depend on a specific family."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Smooth",
+            "fields": [
+              {
+                "defined": "SmoothBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Linear",
+            "fields": [
+              {
+                "defined": "LinearBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Exponential",
+            "fields": [
+              {
+                "defined": "ExponentialBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "ConstantProduct",
+            "fields": [
+              {
+                "defined": "ConstantProductBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Logistic",
+            "fields": [
+              {
+                "defined": "LogisticBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Piecewise",
+            "fields": [
+              {
+                "defined": "PiecewiseBondingCurve"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "XyberInstructionType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "BuyExactIn"
+          },
+          {
+            "name": "BuyExactOut"
+          },
+          {
+            "name": "SellExactIn"
+          },
+          {
+            "name": "SellExactOut"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "GraduationTriggered",
+      "fields": [
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "escrowBalance",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketCap",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "fullyDilutedValuation",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "escrow",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DonationsSwept",
+      "fields": [
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "treasury",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "realBaseReserve",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ProtocolFeesClaimed",
+      "fields": [
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "baseMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CreatorFeesClaimed",
+      "fields": [
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalAccrued",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalClaimed",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ReferralRecorded",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ReferralFeesClaimed",
+      "fields": [
+        {
+          "name": "referrer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "baseMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CreationFeePaid",
+      "fields": [
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseMint",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LiquidityWithdrawn",
+      "fields": [
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "escrowBalance",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "protocolFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseLiquidity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tokenLiquidity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PoolMigrated",
+      "fields": [
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "pool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "protocolFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseLiquidity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tokenLiquidity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseLeftover",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tokensBurned",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeeCollected",
+      "fields": [
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "baseMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "protocolFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sniperFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sniperFeeToCreator",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "totalProtocolFees",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalCreatorFees",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalReferralFees",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "XyberSwapEvent",
+      "fields": [
+        {
+          "name": "ixType",
+          "type": {
+            "defined": "XyberInstructionType"
+          },
+          "index": false
+        },
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "baseAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "vaultTokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "spotPrice",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "marketCap",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "tokensRemaining",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "partialFill",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "baseAmountUnfilled",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "protocolFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sniperFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feeBps",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "InsufficientTokenSupply",
+      "msg": "Custom error: Token supply is not enough to fulfill buy request"
+    },
+    {
+      "code": 6001,
+      "name": "MathOverflow",
+      "msg": "Math overflow: the amount is out of range for the bonding curve."
+    },
+    {
+      "code": 6002,
+      "name": "Unauthorized",
+      "msg": "Unauthorized: Caller is not authorized to perform this action."
+    },
+    {
+      "code": 6003,
+      "name": "BondingCurveNotGraduated",
+      "msg": "Bonding Curve not graduated: pool has not reached the required threshold."
+    },
+    {
+      "code": 6004,
+      "name": "InsufficientTokenVaultBalance",
+      "msg": "Insufficient token balance in the vault to fulfill the request."
+    },
+    {
+      "code": 6005,
+      "name": "InsufficientEscrowBalance",
+      "msg": "Insufficient escrow balance: Not enough tokens in escrow to complete the operation."
+    },
+    {
+      "code": 6006,
+      "name": "TokenIsGraduated",
+      "msg": "Token has graduated: The bonding curve is no longer active as the token is now listed on a DEX."
+    },
+    {
+      "code": 6007,
+      "name": "InvalidSeed",
+      "msg": "Invalid seed: the provided seed must be exactly 32 bytes in length."
+    },
+    {
+      "code": 6008,
+      "name": "SlippageExceeded",
+      "msg": "Slippage exceeded user-defined limit."
+    },
+    {
+      "code": 6009,
+      "name": "WrongPaymentMint",
+      "msg": "Wrong payment mint provided."
+    },
+    {
+      "code": 6010,
+      "name": "NothingToSweep",
+      "msg": "Nothing to sweep: the escrow holds no base tokens beyond the tracked reserve."
+    },
+    {
+      "code": 6011,
+      "name": "InvalidCurveParameters",
+      "msg": "Invalid curve parameters: every parameter must be non-zero and keep the curve in range."
+    },
+    {
+      "code": 6012,
+      "name": "CurveStartsAboveZero",
+      "msg": "Invalid curve: y(0) must be 0, but K / C is smaller than A."
+    },
+    {
+      "code": 6013,
+      "name": "CurveOffsetExceedsTotalTokens",
+      "msg": "Invalid curve: K / C is larger than A."
+    },
+    {
+      "code": 6014,
+      "name": "CurveExceedsTotalSupply",
+      "msg": "Invalid curve: the curve can sell more tokens than the total supply."
+    },
+    {
+      "code": 6015,
+      "name": "InvalidGradThreshold",
+      "msg": "Invalid graduation threshold: it must be non-zero and reachable within the total supply."
+    },
+    {
+      "code": 6016,
+      "name": "InvalidCurveSegments",
+      "msg": "Invalid piecewise curve: segments must start at 0, have increasing breakpoints and end on a bounded curve."
+    },
+    {
+      "code": 6017,
+      "name": "PriceImpactExceeded",
+      "msg": "Price impact exceeded: the trade moves the spot price more than the core allows."
+    },
+    {
+      "code": 6018,
+      "name": "InvalidFeeBps",
+      "msg": "Invalid fee: a fee rate must be below 10000 bps."
+    },
+    {
+      "code": 6019,
+      "name": "NothingToClaim",
+      "msg": "Nothing to claim: the fee vault is empty."
+    },
+    {
+      "code": 6020,
+      "name": "InvalidReferrer",
+      "msg": "Invalid referrer: traders cannot refer themselves, or replace or leave out their first referrer."
+    },
+    {
+      "code": 6021,
+      "name": "MissingCreationFeeAccounts",
+      "msg": "Missing creation fee accounts: a base mint creation fee needs the creator's token account and the fee vault."
+    },
+    {
+      "code": 6022,
+      "name": "InvalidTotalChains",
+      "msg": "Invalid total chains: a token must live on 1 to MAX_TOTAL_CHAINS chains."
+    },
+    {
+      "code": 6023,
+      "name": "UnreachableChainThreshold",
+      "msg": "Unreachable graduation threshold: the chain-adjusted threshold exceeds what the curve can take in."
+    },
+    {
+      "code": 6024,
+      "name": "AlreadyMigrated",
+      "msg": "Already migrated: the token's liquidity has moved into its AMM pool."
+    },
+    {
+      "code": 6025,
+      "name": "AlreadyWithdrawn",
+      "msg": "Already withdrawn: the token's liquidity has gone to the admin."
+    }
+  ]
+};
+
+export const IDL: BondingCurve = {
+  "version": "0.1.0",
+  "name": "bonding_curve",
+  "instructions": [
+    {
+      "name": "updateXyberCoreInstruction",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newAcceptedBaseMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the protocol fee of every trade paid in `new_accepted_base_mint`."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "params",
+          "type": {
+            "defined": "InitCoreParams"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setCreationFeeInstruction",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "creationFee",
+          "type": "u64"
+        },
+        {
+          "name": "inBaseMint",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "mintFullSupplyInstruction",
+      "accounts": [
+        {
+          "name": "creator",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The core's accepted base mint; its decimals scale the graduation threshold."
+          ]
+        },
+        {
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the creator's slice of every trade until the creator claims it."
+          ]
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "creatorTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Creator's base token account; only needed when the creation fee is in the base mint"
+          ]
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Protocol fee vault of the base mint; only needed when the creation fee is in it"
+          ]
+        },
+        {
+          "name": "metadataAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenFactoryProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "External factory program"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "params",
+          "type": {
+            "defined": "TokenParams"
+          }
+        }
+      ]
+    },
+    {
+      "name": "buyExactInputInstruction",
+      "accounts": [
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "xyberToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the protocol's trading fees in the base mint."
+          ]
+        },
+        {
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the creator's slice of this token's trades."
+          ]
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Records the buyer's referrer; the first one sticks."
+          ]
+        },
+        {
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "referralVault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The referrer's share of the trading fees in the base mint, claimable by the referrer.",
+            "Only used together with `referrer`."
+          ]
+        },
+        {
+          "name": "traderStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The buyer's rolling volume for the fee tiers."
+          ]
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "baseIn",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountOut",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "buyExactOutputInstruction",
+      "accounts": [
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "xyberToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the protocol's trading fees in the base mint."
+          ]
+        },
+        {
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the creator's slice of this token's trades."
+          ]
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Records the buyer's referrer; the first one sticks."
+          ]
+        },
+        {
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "referralVault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The referrer's share of the trading fees in the base mint, claimable by the referrer.",
+            "Only used together with `referrer`."
+          ]
+        },
+        {
+          "name": "traderStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The buyer's rolling volume for the fee tiers."
+          ]
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "tokensOut",
+          "type": "u64"
+        },
+        {
+          "name": "maxBaseIn",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "sellExactInputInstruction",
+      "accounts": [
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "xyberToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The escrow SPL token account that holds the *payment* tokens (e.g. USDC)."
+          ]
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The SPL mint of the payment token (e.g., USDC)."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Token mint (fully minted at init)."
+          ]
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The vault that holds project’s tokens."
+          ]
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user’s token account holding tokens."
+          ]
+        },
+        {
+          "name": "userPaymentAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user’s associated token account for the *payment* token."
+          ]
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the protocol's trading fees in the base mint."
+          ]
+        },
+        {
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the creator's slice of this token's trades."
+          ]
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Records the user's referrer; the first one sticks."
+          ]
+        },
+        {
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "referralVault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The referrer's share of the trading fees in the base mint, claimable by the referrer.",
+            "Only used together with `referrer`."
+          ]
+        },
+        {
+          "name": "traderStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user's rolling volume for the fee tiers."
+          ]
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "tokenAmount",
+          "type": "u64"
+        },
+        {
+          "name": "minBaseAmountOut",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "sellExactOutputInstruction",
+      "accounts": [
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "user",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "xyberToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The escrow SPL token account that holds the *payment* tokens (e.g. USDC)."
+          ]
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The SPL mint of the payment token (e.g., USDC)."
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Token mint (fully minted at init)."
+          ]
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The vault that holds project’s tokens."
+          ]
+        },
+        {
+          "name": "userTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user’s token account holding tokens."
+          ]
+        },
+        {
+          "name": "userPaymentAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user’s associated token account for the *payment* token."
+          ]
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the protocol's trading fees in the base mint."
+          ]
+        },
+        {
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Collects the creator's slice of this token's trades."
+          ]
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Records the user's referrer; the first one sticks."
+          ]
+        },
+        {
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "referralVault",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The referrer's share of the trading fees in the base mint, claimable by the referrer.",
+            "Only used together with `referrer`."
+          ]
+        },
+        {
+          "name": "traderStats",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The user's rolling volume for the fee tiers."
+          ]
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "baseAmountOut",
+          "type": "u64"
+        },
+        {
+          "name": "maxTokenAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "quoteBuyInstruction",
+      "accounts": [
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "xyberCore",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "xyberToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The vault that holds the project's tokens; buys are quoted against its balance."
+          ]
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The base mint; its decimals scale the graduation threshold."
+          ]
+        },
+        {
+          "name": "trader",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traderStats",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The trader's volume stats; quotes at the undiscounted fee without them."
+          ]
+        },
+        {
+          "name": "referral",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The trader's recorded referrer, if they have one."
+          ]
+        },
+        {
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Validated against the recorded one like the trade instructions do."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "exactInput",
+          "type": "bool"
+        }
+      ],
+      "returns": {
+        "defined": "TradeQuote"
+      }
+    },
+    {
+      "name": "quoteSellInstruction",
+      "accounts": [
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "xyberCore",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "xyberToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The vault that holds the project's tokens; buys are quoted against its balance."
+          ]
+        },
+        {
+          "name": "paymentMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The base mint; its decimals scale the graduation threshold."
+          ]
+        },
+        {
+          "name": "trader",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "traderStats",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The trader's volume stats; quotes at the undiscounted fee without them."
+          ]
+        },
+        {
+          "name": "referral",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "The trader's recorded referrer, if they have one."
+          ]
+        },
+        {
+          "name": "referrer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Validated against the recorded one like the trade instructions do."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "exactInput",
+          "type": "bool"
+        }
+      ],
+      "returns": {
+        "defined": "TradeQuote"
+      }
+    },
+    {
+      "name": "withdrawLiquidity",
+      "accounts": [
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "creator",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Escrow token account holding the payment tokens (e.g. USDC)"
+          ]
+        },
+        {
+          "name": "baseTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "adminVaultAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "creatorTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the protocol's graduation fee"
+          ]
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrate",
+      "accounts": [
+        {
+          "name": "xyberCore",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "creator",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Escrow token account holding the payment tokens (e.g. USDC)"
+          ]
+        },
+        {
+          "name": "baseTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "creatorTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the protocol's graduation fee"
+          ]
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolBaseVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ammProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sweepDonationsInstruction",
+      "accounts": [
+        {
+          "name": "xyberCore",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberToken",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Escrow token account holding the payment tokens (e.g. USDC)"
+          ]
+        },
+        {
+          "name": "baseTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "adminTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimProtocolFees",
+      "accounts": [
+        {
+          "name": "xyberCore",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "baseTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Protocol fees collected in `base_token_mint`"
+          ]
+        },
+        {
+          "name": "adminTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimCreatorFees",
+      "accounts": [
+        {
+          "name": "creator",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "xyberToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenSeed",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "baseTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "creatorFeeVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Creator fees accrued by this token's trades"
+          ]
+        },
+        {
+          "name": "creatorTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimReferralFees",
+      "accounts": [
+        {
+          "name": "xyberCore",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "referrer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "baseTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "referralVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Referral fees earned by `referrer` in `base_token_mint`"
+          ]
+        },
+        {
+          "name": "referrerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeXyberCoreInstruction",
+      "accounts": [
+        {
+          "name": "xyberCore",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "xyberCore",
+      "docs": [
+        "The sixbte, global state for all tokens."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "gradThreshold",
+            "type": "u64"
+          },
+          {
+            "name": "totalSupply",
+            "type": "u64"
+          },
+          {
+            "name": "bondingCurve",
+            "type": {
+              "defined": "BondingCurveType"
+            }
+          },
+          {
+            "name": "acceptedBaseMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "maxPriceImpactBps",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "creatorFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "referralShareBps",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeDecaySlots",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeToCreator",
+            "type": "bool"
+          },
+          {
+            "name": "feeTiers",
+            "type": {
+              "array": [
+                {
+                  "defined": "FeeTier"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "volumeWindowSlots",
+            "type": "u64"
+          },
+          {
+            "name": "creationFee",
+            "type": "u64"
+          },
+          {
+            "name": "creationFeeInBaseMint",
+            "type": "bool"
+          },
+          {
+            "name": "graduationProtocolFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "graduationCreatorFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "totalProtocolFees",
+            "type": "u64"
+          },
+          {
+            "name": "totalCreatorFees",
+            "type": "u64"
+          },
+          {
+            "name": "totalReferralFees",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "xyberToken",
+      "docs": [
+        "One account per unique token. It holds only “token-specific” info."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "isGraduated",
+            "type": "bool"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "totalChains",
+            "type": "u8"
+          },
+          {
+            "name": "realBaseReserve",
+            "type": "u64"
+          },
+          {
+            "name": "tokensSold",
+            "type": "u64"
+          },
+          {
+            "name": "bondingCurve",
+            "type": {
+              "defined": "BondingCurveType"
+            }
+          },
+          {
+            "name": "gradThreshold",
+            "type": "u64"
+          },
+          {
+            "name": "totalSupply",
+            "type": "u64"
+          },
+          {
+            "name": "totalProtocolFees",
+            "type": "u64"
+          },
+          {
+            "name": "totalCreatorFees",
+            "type": "u64"
+          },
+          {
+            "name": "totalReferralFees",
+            "type": "u64"
+          },
+          {
+            "name": "creatorFeesClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "launchSlot",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeDecaySlots",
+            "type": "u64"
+          },
+          {
+            "name": "sniperFeeToCreator",
+            "type": "bool"
+          },
+          {
+            "name": "migrated",
+            "type": "bool"
+          },
+          {
+            "name": "withdrawn",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referral",
+      "docs": [
+        "One account per trader, recording who referred them."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "referrer",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "traderStats",
+      "docs": [
+        "One account per trader, tracking their base volume (raw units, before fees) across all",
+        "tokens for the fee tiers."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "currentVolume",
+            "type": "u64"
+          },
+          {
+            "name": "previousVolume",
+            "type": "u64"
+          },
+          {
+            "name": "windowStartSlot",
+            "type": "u64"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "ConstantProductBondingCurve",
+      "docs": [
+        "A constant-product (x * y = k) curve over virtual reserves, as used by pump.fun.",
+        "",
+        "Invariant: (V_base + x) * (V_token - y) = V_base * V_token",
+        "Inverse:   y(x) = V_token - V_base * V_token / (V_base + x)",
+        "- V_base  = virtual base reserve (in base_tokens)",
+        "- V_token = virtual token reserve (in raw token base units, i.e. scaled by 10^decimals)",
+        "- R       = real token reserve, the tokens actually sold on the curve (R < V_token)",
+        "",
+        "Token amounts are rounded down and base amounts up."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "virtualBaseReserve",
+            "docs": [
+              "Virtual base reserve (in base_tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "virtualTokenReserve",
+            "docs": [
+              "Virtual token reserve (in raw tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "realTokenReserve",
+            "docs": [
+              "Tokens that can actually be sold on the curve"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ExponentialBondingCurve",
+      "docs": [
+        "A bonding curve whose marginal price grows exponentially with the tokens sold.",
+        "",
+        "Price:   p(y) = (V / L) * e^(y / L)",
+        "Deposit: x(y) = V * (e^(y / L) - 1)",
+        "Inverse: y(x) = L * ln((V + x) / V)",
+        "- A = total tokens sold on the curve (in raw token base units, i.e. scaled by 10^decimals)",
+        "- V = virtual base reserve (in base_tokens), sets the starting price V / L",
+        "- L = growth length (in raw tokens): the price grows by a factor of e every L tokens",
+        "",
+        "`exp` and `ln` are evaluated in WAD fixed-point (see `curves::math`); `exp` is",
+        "rounded up and `ln` down, so every trade still rounds in the pool's favor."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "aTotalTokens",
+            "docs": [
+              "Total tokens that can be sold on the curve"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "vVirtualBaseReserve",
+            "docs": [
+              "Virtual base reserve that sets the starting price (in base_tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lGrowthTokens",
+            "docs": [
+              "Tokens sold per e-fold of the price"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LinearBondingCurve",
+      "docs": [
+        "A bonding curve whose marginal price grows linearly with the tokens sold.",
+        "",
+        "Price:   p(y) = (B + y) / M",
+        "Deposit: x(y) = y * (2B + y) / (2M)",
+        "Inverse: y(x) = sqrt(B^2 + 2M * x) - B",
+        "- A = total tokens sold on the curve (in raw token base units, i.e. scaled by 10^decimals)",
+        "- B = virtual token offset, sets the starting price B / M",
+        "- M = price divisor (raw token * raw token / base_token), sets the slope 1 / M",
+        "",
+        "Token amounts are rounded down and base amounts up, so the pool never",
+        "hands out more than the exact curve would."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "aTotalTokens",
+            "docs": [
+              "Total tokens that can be sold on the curve"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bVirtualTokenOffset",
+            "docs": [
+              "Virtual token offset that sets the starting price (in raw tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "mPriceDivisor",
+            "docs": [
+              "Slope divisor of the price line (raw token * raw token / base_token)"
+            ],
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LogisticBondingCurve",
+      "docs": [
+        "A bonding curve whose price follows a logistic (sigmoid) function of the tokens",
+        "sold: nearly flat early, steepest at the midpoint and flat again near the end.",
+        "",
+        "Price:   p(y) = P_min + (P_max - P_min) * sigma((y - M) / S)",
+        "Deposit: x(y) = integral of p from 0 to y",
+        "- A = total tokens sold on the curve (in raw token base units, i.e. scaled by 10^decimals)",
+        "- P_min, P_max = price floor and ceiling (base_tokens per raw token, scaled by WAD)",
+        "- M = tokens sold at the midpoint, where the price grows the fastest",
+        "- S = tokens per unit of z, i.e. how stretched the S-shape is",
+        "",
+        "On-chain, sigma is read from `SIGMOID_WAD` and interpolated linearly, so p(y) is",
+        "piecewise linear and x(y) piecewise quadratic. Both are evaluated exactly as a",
+        "256-bit numerator over 2 * (S / 4) * WAD, and y(x) is found by a binary search",
+        "inside the one piece that x falls into. Token amounts are rounded down and base",
+        "amounts up."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "aTotalTokens",
+            "docs": [
+              "Total tokens that can be sold on the curve"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "pMinPrice",
+            "docs": [
+              "Price floor (base_tokens per raw token, scaled by WAD)"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "pMaxPrice",
+            "docs": [
+              "Price ceiling (base_tokens per raw token, scaled by WAD)"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "mMidpointTokens",
+            "docs": [
+              "Tokens sold at the midpoint of the S-shape (in raw tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sScaleTokens",
+            "docs": [
+              "Tokens per unit of the sigmoid's argument, a multiple of 4 (in raw tokens)"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FlatSegment",
+      "docs": [
+        "A fixed-price segment, e.g. a presale before the curve starts moving.",
+        "",
+        "y(x) = x / P",
+        "- P = price (base_tokens per raw token, scaled by `math::WAD`)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "docs": [
+              "Price in base_tokens per raw token, scaled by WAD"
+            ],
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PiecewiseSegment",
+      "docs": [
+        "One segment of a piecewise curve, active from `x_start` base_tokens deposited",
+        "up to the next segment's `x_start`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "xStart",
+            "docs": [
+              "Breakpoint in base_tokens deposited where this segment starts"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "curve",
+            "docs": [
+              "The curve of this segment, evaluated at x - x_start"
+            ],
+            "type": {
+              "defined": "SegmentCurve"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PiecewiseBondingCurve",
+      "docs": [
+        "A bonding curve made of several segments, e.g. a flat presale price, then a",
+        "smooth hyperbolic curve, then a steep tail.",
+        "",
+        "Y(x) = T_i + y_i(x - X_i) for X_i <= x < X_(i+1)",
+        "- X_i = breakpoint of segment i in base_tokens deposited (X_0 = 0)",
+        "- y_i = the curve of segment i, each starting at y_i(0) = 0",
+        "- T_i = tokens sold by all earlier segments, each counted as ceil(y_j(X_(j+1) - X_j))",
+        "",
+        "Rounding each finished segment up keeps Y non-decreasing across breakpoints, and",
+        "every trade still brackets Y with floor/ceil exactly like a single curve does, so",
+        "a trade spanning several segments is priced in the pool's favor as a whole."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "segments",
+            "docs": [
+              "Segments ordered by breakpoint, the first one starting at 0"
+            ],
+            "type": {
+              "vec": {
+                "defined": "PiecewiseSegment"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "SmoothBondingCurve",
+      "docs": [
+        "A smooth bonding curve referencing the base asset (e.g., SOL, XBT) deposited.",
+        "",
+        "Formula: y(x) = A - (K / (C + x))",
+        "- A = asymptotic max token supply (in raw token base units, i.e. scaled by 10^decimals)",
+        "- K = (raw token * base unit), controlling how quickly we approach A",
+        "- C = virtual pool offset (in base_tokens)",
+        "",
+        "NOTE: We no longer store `x_total_base_deposit` inside the struct.",
+        "Instead, the caller passes the current x each time."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "aTotalTokens",
+            "docs": [
+              "Asymptotic total token supply (in \"raw\" tokens)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "kVirtualPoolOffset",
+            "docs": [
+              "Controls how quickly we approach A (raw token * base unit)"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "cBondingScaleFactor",
+            "docs": [
+              "Virtual pool offset (in base_tokens)"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeTier",
+      "docs": [
+        "A volume tier: traders with at least `min_volume` rolling base volume (raw units) get",
+        "the regular trading fee reduced by `discount_bps` of it. Unused entries are all zero."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minVolume",
+            "type": "u64"
+          },
+          {
+            "name": "discountBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TradeQuote",
+      "docs": [
+        "The outcome of a trade against the current token state.",
+        "",
+        "Returned through `set_return_data`, so simulating a quote instruction yields the",
+        "same numbers the trade instruction would produce in the same slot."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "baseAmount",
+            "docs": [
+              "Base tokens moving on the curve: paid in (buys) or paid out (sells), before the fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "name": "creatorFee",
+            "type": "u64"
+          },
+          {
+            "name": "referralFee",
+            "docs": [
+              "The referrer's share, taken out of the protocol fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sniperFee",
+            "docs": [
+              "Buys only: anti-sniper surcharge right after launch, on top of the fees above"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tokenAmount",
+            "docs": [
+              "Project tokens (raw units) received (buys) or paid in (sells)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "newReserve",
+            "docs": [
+              "`real_base_reserve` after the trade"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "spotPriceBefore",
+            "type": "u128"
+          },
+          {
+            "name": "spotPriceAfter",
+            "type": "u128"
+          },
+          {
+            "name": "priceImpactBps",
+            "docs": [
+              "How far the trade moves the spot price, in bps of `spot_price_before` (rounded up)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tokensRemaining",
+            "docs": [
+              "Tokens (raw units) still for sale on the curve after the trade"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "partialFill",
+            "docs": [
+              "Buys only: the buy is capped at the tokens left for sale or at the graduation",
+              "threshold, and only `token_amount` for `base_amount` is filled"
+            ],
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "InitCoreParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "gradThreshold",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "bondingCurve",
+            "type": {
+              "option": {
+                "defined": "BondingCurveType"
+              }
+            }
+          },
+          {
+            "name": "acceptedBaseMint",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "totalSupply",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxPriceImpactBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "protocolFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "creatorFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "referralShareBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "sniperFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "sniperFeeDecaySlots",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "sniperFeeToCreator",
+            "type": {
+              "option": "bool"
+            }
+          },
+          {
+            "name": "feeTiers",
+            "type": {
+              "option": {
+                "array": [
+                  {
+                    "defined": "FeeTier"
+                  },
+                  4
+                ]
+              }
+            }
+          },
+          {
+            "name": "volumeWindowSlots",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "graduationProtocolFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "graduationCreatorFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "TokenParams",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "admin",
-            "type": "publicKey"
+            "name": "name",
+            "type": "string"
           },
           {
-            "name": "gradThreshold",
-            "type": "u64"
+            "name": "symbol",
+            "type": "string"
           },
           {
-            "name": "totalSupply",
-            "type": "u64"
+            "name": "uri",
+            "type": "string"
           },
           {
-            "name": "bondingCurve",
-            "type": {
-              "defined": "SmoothBondingCurve"
-            }
+            "name": "tokenSeed",
+            "type": "publicKey"
           },
           {
-            "name": "acceptedBaseMint",
-            "type": "publicKey"
+            "name": "totalChains",
+            "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "xyberToken",
+      "name": "SegmentCurve",
       "docs": [
-        "One account per unique token. It holds only “token-specific” info."
+        "The curve used within one segment, driven by the base deposited since the",
+        "segment's breakpoint."
       ],
       "type": {
-        "kind": "struct",
-        "fields": [
+        "kind": "enum",
+        "variants": [
           {
-            "name": "isGraduated",
-            "type": "bool"
+            "name": "Flat",
+            "fields": [
+              {
+                "defined": "FlatSegment"
+              }
+            ]
           },
           {
-            "name": "mint",
-            "type": "publicKey"
+            "name": "Smooth",
+            "fields": [
+              {
+                "defined": "SmoothBondingCurve"
+              }
+            ]
           },
           {
-            "name": "vault",
-            "type": "publicKey"
+            "name": "Linear",
+            "fields": [
+              {
+                "defined": "LinearBondingCurve"
+              }
+            ]
           },
           {
-            "name": "creator",
-            "type": "publicKey"
+            "name": "Exponential",
+            "fields": [
+              {
+                "defined": "ExponentialBondingCurve"
+              }
+            ]
           },
           {
-            "name": "totalChains",
-            "type": "u8"
+            "name": "ConstantProduct",
+            "fields": [
+              {
+                "defined": "ConstantProductBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Logistic",
+            "fields": [
+              {
+                "defined": "LogisticBondingCurve"
+              }
+            ]
           }
         ]
       }
-    }
-  ],
-  "types": [
+    },
     {
-      "name": "SmoothBondingCurve",
+      "name": "BondingCurveType",
       "docs": [
-        "A smooth bonding curve referencing the base asset (e.g., SOL, XBT) deposited.",
-        "",
-        "Formula: y(x) = A - (K / (C + x))",
-        "- A = asymptotic max token supply (in integer \"token units\")",
-        "- K = (token * lamport), controlling how quickly we approach A",
-        "- C = virtual pool offset (in base_tokens)",
+        "The curve family used to price a token, stored on-chain in `XyberCore`.",
         "",
-        "NOTE: We no longer store `x_total_base_deposit` inside the struct.",
-        "Instead, the caller passes the current x each time."
+        "Every variant wraps a concrete curve and all pricing goes through",
+        "`BondingCurveTrait`, so instruction handlers never depend on a specific family."
       ],
       "type": {
-        "kind": "struct",
-        "fields": [
+        "kind": "enum",
+        "variants": [
           {
-            "name": "aTotalTokens",
-            "docs": [
-              "Asymptotic total token supply (in \"raw\" tokens)"
-            ],
-            "type": "u64"
+            "name": "Smooth",
+            "fields": [
+              {
+                "defined": "SmoothBondingCurve"
+              }
+            ]
           },
           {
-            "name": "kVirtualPoolOffset",
-            "docs": [
-              "Controls how quickly we approach A (token * lamport)"
-            ],
-            "type": "u128"
+            "name": "Linear",
+            "fields": [
+              {
+                "defined": "LinearBondingCurve"
+              }
+            ]
           },
           {
-            "name": "cBondingScaleFactor",
-            "docs": [
-              "Virtual pool offset (in base_tokens)"
-            ],
-            "type": "u64"
+            "name": "Exponential",
+            "fields": [
+              {
+                "defined": "ExponentialBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "ConstantProduct",
+            "fields": [
+              {
+                "defined": "ConstantProductBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Logistic",
+            "fields": [
+              {
+                "defined": "LogisticBondingCurve"
+              }
+            ]
+          },
+          {
+            "name": "Piecewise",
+            "fields": [
+              {
+                "defined": "PiecewiseBondingCurve"
+              }
+            ]
           }
         ]
       }
     },
     {
-      "name": "InitCoreParams",
+      "name": "XyberInstructionType",
       "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": {
-              "option": "publicKey"
-            }
-          },
+        "kind": "enum",
+        "variants": [
           {
-            "name": "gradThreshold",
-            "type": {
-              "option": "u64"
-            }
+            "name": "BuyExactIn"
           },
           {
-            "name": "bondingCurve",
-            "type": {
-              "option": {
-                "defined": "SmoothBondingCurve"
-              }
-            }
+            "name": "BuyExactOut"
           },
           {
-            "name": "acceptedBaseMint",
-            "type": {
-              "option": "publicKey"
-            }
+            "name": "SellExactIn"
           },
           {
-            "name": "totalSupply",
-            "type": {
-              "option": "u64"
-            }
+            "name": "SellExactOut"
           }
         ]
       }
+    }
+  ],
+  "events": [
+    {
+      "name": "GraduationTriggered",
+      "fields": [
+        {
+          "name": "buyer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "escrowBalance",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketCap",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "fullyDilutedValuation",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "vault",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "escrow",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DonationsSwept",
+      "fields": [
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "treasury",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "realBaseReserve",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ProtocolFeesClaimed",
+      "fields": [
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "baseMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CreatorFeesClaimed",
+      "fields": [
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalAccrued",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalClaimed",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ReferralRecorded",
+      "fields": [
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ReferralFeesClaimed",
+      "fields": [
+        {
+          "name": "referrer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "baseMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "TokenParams",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "name",
-            "type": "string"
-          },
-          {
-            "name": "symbol",
-            "type": "string"
-          },
-          {
-            "name": "uri",
-            "type": "string"
-          },
-          {
-            "name": "tokenSeed",
-            "type": "publicKey"
+      "name": "CreationFeePaid",
+      "fields": [
+        {
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseMint",
+          "type": {
+            "option": "publicKey"
           },
-          {
-            "name": "totalChains",
-            "type": "u8"
-          }
-        ]
-      }
+          "index": false
+        }
+      ]
     },
     {
-      "name": "XyberInstructionType",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "BuyExactIn"
-          },
-          {
-            "name": "BuyExactOut"
-          },
-          {
-            "name": "SellExactIn"
-          },
-          {
-            "name": "SellExactOut"
-          }
-        ]
-      }
-    }
-  ],
-  "events": [
-    {
-      "name": "GraduationTriggered",
+      "name": "LiquidityWithdrawn",
       "fields": [
         {
-          "name": "buyer",
+          "name": "tokenSeed",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creator",
           "type": "publicKey",
           "index": false
         },
@@ -1367,24 +5353,141 @@ export const IDL: BondingCurve = {
           "index": false
         },
         {
-          "name": "vault",
-          "type": "publicKey",
+          "name": "protocolFee",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "creator",
+          "name": "creatorFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseLiquidity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tokenLiquidity",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PoolMigrated",
+      "fields": [
+        {
+          "name": "tokenSeed",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "escrow",
+          "name": "pool",
           "type": "publicKey",
           "index": false
         },
+        {
+          "name": "price",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "protocolFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseLiquidity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tokenLiquidity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseLeftover",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tokensBurned",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeeCollected",
+      "fields": [
         {
           "name": "tokenSeed",
           "type": "publicKey",
           "index": false
+        },
+        {
+          "name": "user",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "baseMint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "protocolFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sniperFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sniperFeeToCreator",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "totalProtocolFees",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalCreatorFees",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalReferralFees",
+          "type": "u64",
+          "index": false
         }
       ]
     },
@@ -1422,6 +5525,63 @@ export const IDL: BondingCurve = {
           "name": "vaultTokenAmount",
           "type": "u64",
           "index": false
+        },
+        {
+          "name": "spotPrice",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "marketCap",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "tokensRemaining",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "partialFill",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "baseAmountUnfilled",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "protocolFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "referralFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sniperFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feeBps",
+          "type": "u64",
+          "index": false
         }
       ]
     }
@@ -1435,7 +5595,7 @@ export const IDL: BondingCurve = {
     {
       "code": 6001,
       "name": "MathOverflow",
-      "msg": "Provide a smaller amount. Use normalized tokens (e.g., raw value / 10 ** decimals)."
+      "msg": "Math overflow: the amount is out of range for the bonding curve."
     },
     {
       "code": 6002,
@@ -1476,6 +5636,86 @@ export const IDL: BondingCurve = {
       "code": 6009,
       "name": "WrongPaymentMint",
       "msg": "Wrong payment mint provided."
+    },
+    {
+      "code": 6010,
+      "name": "NothingToSweep",
+      "msg": "Nothing to sweep: the escrow holds no base tokens beyond the tracked reserve."
+    },
+    {
+      "code": 6011,
+      "name": "InvalidCurveParameters",
+      "msg": "Invalid curve parameters: every parameter must be non-zero and keep the curve in range."
+    },
+    {
+      "code": 6012,
+      "name": "CurveStartsAboveZero",
+      "msg": "Invalid curve: y(0) must be 0, but K / C is smaller than A."
+    },
+    {
+      "code": 6013,
+      "name": "CurveOffsetExceedsTotalTokens",
+      "msg": "Invalid curve: K / C is larger than A."
+    },
+    {
+      "code": 6014,
+      "name": "CurveExceedsTotalSupply",
+      "msg": "Invalid curve: the curve can sell more tokens than the total supply."
+    },
+    {
+      "code": 6015,
+      "name": "InvalidGradThreshold",
+      "msg": "Invalid graduation threshold: it must be non-zero and reachable within the total supply."
+    },
+    {
+      "code": 6016,
+      "name": "InvalidCurveSegments",
+      "msg": "Invalid piecewise curve: segments must start at 0, have increasing breakpoints and end on a bounded curve."
+    },
+    {
+      "code": 6017,
+      "name": "PriceImpactExceeded",
+      "msg": "Price impact exceeded: the trade moves the spot price more than the core allows."
+    },
+    {
+      "code": 6018,
+      "name": "InvalidFeeBps",
+      "msg": "Invalid fee: a fee rate must be below 10000 bps."
+    },
+    {
+      "code": 6019,
+      "name": "NothingToClaim",
+      "msg": "Nothing to claim: the fee vault is empty."
+    },
+    {
+      "code": 6020,
+      "name": "InvalidReferrer",
+      "msg": "Invalid referrer: traders cannot refer themselves, or replace or leave out their first referrer."
+    },
+    {
+      "code": 6021,
+      "name": "MissingCreationFeeAccounts",
+      "msg": "Missing creation fee accounts: a base mint creation fee needs the creator's token account and the fee vault."
+    },
+    {
+      "code": 6022,
+      "name": "InvalidTotalChains",
+      "msg": "Invalid total chains: a token must live on 1 to MAX_TOTAL_CHAINS chains."
+    },
+    {
+      "code": 6023,
+      "name": "UnreachableChainThreshold",
+      "msg": "Unreachable graduation threshold: the chain-adjusted threshold exceeds what the curve can take in."
+    },
+    {
+      "code": 6024,
+      "name": "AlreadyMigrated",
+      "msg": "Already migrated: the token's liquidity has moved into its AMM pool."
+    },
+    {
+      "code": 6025,
+      "name": "AlreadyWithdrawn",
+      "msg": "Already withdrawn: the token's liquidity has gone to the admin."
     }
   ]
 };
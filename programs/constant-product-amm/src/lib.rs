@@ -0,0 +1,363 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("6YiGBs6Hp1c7zRF4bq8bWMKj2JLDdN3t6ihXWJ7NpG49");
+
+/// A minimal constant-product (x * y = k) pool, standing in for a real AMM so that graduated
+/// tokens can be migrated and traded locally without mainnet programs.
+///
+/// Pools have no LP tokens and charge no swap fee: the liquidity deposited at creation
+/// stays in the pool for good.
+#[account]
+pub struct Pool {
+    pub base_mint: Pubkey,
+    pub token_mint: Pubkey,
+    // Owner of the initial reserves; part of the pool's address, so nobody else can take it
+    pub depositor: Pubkey,
+    // Pool-owned token accounts holding the reserves
+    pub base_vault: Pubkey,
+    pub token_vault: Pubkey,
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 8 // Discriminator
+        + 32 // base_mint
+        + 32 // token_mint
+        + 32 // depositor
+        + 32 // base_vault
+        + 32 // token_vault
+        + 1; // bump
+
+    /// The address of the pool of `base_mint` and `token_mint` created by `depositor`.
+    pub fn address(base_mint: &Pubkey, token_mint: &Pubkey, depositor: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"pool",
+                base_mint.as_ref(),
+                token_mint.as_ref(),
+                depositor.as_ref(),
+            ],
+            &ID,
+        )
+    }
+}
+
+#[error_code]
+pub enum AmmError {
+    #[msg("Zero amount: pool deposits and swaps need a non-zero amount.")]
+    ZeroAmount,
+
+    #[msg("Slippage exceeded user-defined limit.")]
+    SlippageExceeded,
+
+    #[msg("Math overflow: the amount is out of range for the pool.")]
+    MathOverflow,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub token_mint: Pubkey,
+    pub base_amount: u64,
+    pub token_amount: u64,
+}
+
+#[event]
+pub struct PoolSwap {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub base_in: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+/// Tokens out of a constant-product pool for `amount_in` paid into it, rounded down so the
+/// product of the reserves never shrinks.
+pub fn swap_output(
+    reserve_in: u64,
+    reserve_out: u64,
+    amount_in: u64,
+) -> std::result::Result<u64, AmmError> {
+    let new_reserve_in = (reserve_in as u128)
+        .checked_add(amount_in as u128)
+        .ok_or(AmmError::MathOverflow)?;
+    let amount_out = (reserve_out as u128)
+        .checked_mul(amount_in as u128)
+        .ok_or(AmmError::MathOverflow)?
+        / new_reserve_in.max(1);
+    u64::try_from(amount_out).map_err(|_| AmmError::MathOverflow)
+}
+
+#[program]
+pub mod constant_product_amm {
+    use super::*;
+
+    /// Creates the depositor's pool of `base_mint` and `token_mint`, seeded with `base_amount`
+    /// and `token_amount` from the depositor. The ratio of the two sets the opening price.
+    pub fn create_pool(
+        ctx: Context<CreatePool>,
+        base_amount: u64,
+        token_amount: u64,
+    ) -> Result<()> {
+        require!(base_amount > 0 && token_amount > 0, AmmError::ZeroAmount);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.base_mint = ctx.accounts.base_mint.key();
+        pool.token_mint = ctx.accounts.token_mint.key();
+        pool.depositor = ctx.accounts.depositor.key();
+        pool.base_vault = ctx.accounts.base_vault.key();
+        pool.token_vault = ctx.accounts.token_vault.key();
+        pool.bump = ctx.bumps.pool;
+
+        for (from, to, amount) in [
+            (
+                &ctx.accounts.depositor_base_account,
+                &ctx.accounts.base_vault,
+                base_amount,
+            ),
+            (
+                &ctx.accounts.depositor_token_account,
+                &ctx.accounts.token_vault,
+                token_amount,
+            ),
+        ] {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: ctx.accounts.depositor.to_account_info(),
+                },
+            );
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        emit!(PoolCreated {
+            pool: ctx.accounts.pool.key(),
+            base_mint: ctx.accounts.base_mint.key(),
+            token_mint: ctx.accounts.token_mint.key(),
+            base_amount,
+            token_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Swaps `amount_in` base for tokens (`base_in`) or tokens for base at the pool's
+    /// constant-product price, failing if less than `min_amount_out` would come out.
+    pub fn swap(
+        ctx: Context<Swap>,
+        amount_in: u64,
+        min_amount_out: u64,
+        base_in: bool,
+    ) -> Result<()> {
+        require!(amount_in > 0, AmmError::ZeroAmount);
+
+        let accounts = &ctx.accounts;
+        let (vault_in, vault_out, user_in, user_out) = if base_in {
+            (
+                &accounts.base_vault,
+                &accounts.token_vault,
+                &accounts.user_base_account,
+                &accounts.user_token_account,
+            )
+        } else {
+            (
+                &accounts.token_vault,
+                &accounts.base_vault,
+                &accounts.user_token_account,
+                &accounts.user_base_account,
+            )
+        };
+        let amount_out = swap_output(vault_in.amount, vault_out.amount, amount_in)?;
+        require!(amount_out > 0, AmmError::ZeroAmount);
+        require!(amount_out >= min_amount_out, AmmError::SlippageExceeded);
+
+        let pay_in_ctx = CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: user_in.to_account_info(),
+                to: vault_in.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        );
+        token::transfer(pay_in_ctx, amount_in)?;
+
+        let pool = &accounts.pool;
+        let seeds: [&[u8]; 5] = [
+            b"pool",
+            pool.base_mint.as_ref(),
+            pool.token_mint.as_ref(),
+            pool.depositor.as_ref(),
+            &[pool.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let pay_out_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: vault_out.to_account_info(),
+                to: user_out.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(pay_out_ctx, amount_out)?;
+
+        emit!(PoolSwap {
+            pool: pool.key(),
+            user: accounts.user.key(),
+            base_in,
+            amount_in,
+            amount_out,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreatePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Owner of the deposited reserves; a PDA signing through CPI when a program migrates
+    pub depositor: Signer<'info>,
+
+    pub base_mint: Box<Account<'info, Mint>>,
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"pool",
+            base_mint.key().as_ref(),
+            token_mint.key().as_ref(),
+            depositor.key().as_ref()
+        ],
+        bump,
+        space = Pool::LEN
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"pool_vault", pool.key().as_ref(), base_mint.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = pool,
+    )]
+    pub base_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"pool_vault", pool.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool,
+    )]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = depositor,
+    )]
+    pub depositor_base_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = depositor,
+    )]
+    pub depositor_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"pool",
+            pool.base_mint.as_ref(),
+            pool.token_mint.as_ref(),
+            pool.depositor.as_ref()
+        ],
+        bump = pool.bump,
+        has_one = base_vault,
+        has_one = token_vault
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub base_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = pool.base_mint,
+        token::authority = user,
+    )]
+    pub user_base_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = pool.token_mint,
+        token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_output() {
+        // 1_000 in against a 10_000 / 20_000 pool: 20_000 * 1_000 / 11_000 = 1_818.18...
+        assert_eq!(swap_output(10_000, 20_000, 1_000).unwrap(), 1_818);
+
+        // The product of the reserves never shrinks.
+        for (reserve_in, reserve_out, amount_in) in [
+            (1, 1, 1),
+            (7, 1_000_003, 13),
+            (u64::MAX / 2, 3, u64::MAX / 2),
+        ] {
+            let amount_out = swap_output(reserve_in, reserve_out, amount_in).unwrap();
+            let k_before = reserve_in as u128 * reserve_out as u128;
+            let k_after = (reserve_in as u128 + amount_in as u128)
+                * (reserve_out as u128 - amount_out as u128);
+            assert!(k_after >= k_before);
+        }
+
+        assert_eq!(swap_output(0, 0, 0).unwrap(), 0);
+        assert_eq!(swap_output(10, 10, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_pool_address() {
+        let (base_mint, token_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (migrator, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // A pool created first by someone else for the same mints lives at another
+        // address, so it can't take the migrator's.
+        let (pool, _) = Pool::address(&base_mint, &token_mint, &migrator);
+        assert_ne!(pool, Pool::address(&base_mint, &token_mint, &other).0);
+        assert_eq!(pool, Pool::address(&base_mint, &token_mint, &migrator).0);
+    }
+}
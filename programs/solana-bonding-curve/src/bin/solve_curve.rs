@@ -0,0 +1,127 @@
+//! Solves smooth curve parameters (A, K, C) from launch economics.
+//!
+//! cargo run --bin solve-curve --features cli -- \
+//!     <total_supply> <sold_before_graduation_bps> <grad_threshold> <start_price> \
+//!     [token_decimals=9] [base_decimals=9]
+//!
+//! `start_price` is in whole base tokens per whole token, e.g. `0.000000028`.
+
+use std::process::ExitCode;
+
+use bonding_curve::curves::math::{mul_div, BPS_DENOMINATOR, WAD};
+use bonding_curve::curves::solver::{solve_smooth_curve, LaunchEconomics};
+
+const USAGE: &str = "usage: solve-curve <total_supply> <sold_before_graduation_bps> \
+<grad_threshold> <start_price> [token_decimals=9] [base_decimals=9]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let economics = match parse_args(&args) {
+        Ok(economics) => economics,
+        Err(message) => {
+            eprintln!("error: {}\n{}", message, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let solved = match solve_smooth_curve(&economics) {
+        Ok(solved) => solved,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let curve = &solved.curve;
+    let raw_total_supply =
+        economics.total_supply as u128 * 10_u128.pow(economics.token_decimals as u32);
+    println!("Solved smooth curve y(x) = A - K / (C + x):");
+    println!("  a_total_tokens         (A) = {}", curve.a_total_tokens);
+    println!(
+        "  k_virtual_pool_offset  (K) = {}",
+        curve.k_virtual_pool_offset
+    );
+    println!(
+        "  c_bonding_scale_factor (C) = {}",
+        curve.c_bonding_scale_factor
+    );
+    println!("Verified against SmoothBondingCurve:");
+    println!(
+        "  tokens sold at graduation   = {} (target {})",
+        solved.tokens_sold_at_graduation,
+        raw_total_supply * economics.sold_before_graduation_bps as u128 / BPS_DENOMINATOR as u128
+    );
+    println!(
+        "  start price (WAD per raw)   = {} (target {})",
+        solved.start_price, economics.start_price
+    );
+    ExitCode::SUCCESS
+}
+
+fn parse_args(args: &[String]) -> Result<LaunchEconomics, String> {
+    if !(4..=6).contains(&args.len()) {
+        return Err("expected 4 to 6 arguments".to_string());
+    }
+
+    let number = |name: &str, value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|_| format!("{} must be a whole number, got `{}`", name, value))
+    };
+    let decimals = |name: &str, value: Option<&String>| match value {
+        Some(value) => value
+            .parse::<u8>()
+            .map_err(|_| format!("{} must be a number of decimals, got `{}`", name, value)),
+        None => Ok(9),
+    };
+
+    let token_decimals = decimals("token_decimals", args.get(4))?;
+    let base_decimals = decimals("base_decimals", args.get(5))?;
+
+    // Whole base tokens per whole token -> base units per raw token, WAD-scaled.
+    let price_per_whole_token = parse_wad(&args[3])?;
+    let start_price = mul_div(
+        price_per_whole_token,
+        10_u128
+            .checked_pow(base_decimals as u32)
+            .ok_or("base_decimals is too large")?,
+        10_u128
+            .checked_pow(token_decimals as u32)
+            .ok_or("token_decimals is too large")?,
+    )
+    .ok_or("start_price is out of range")?;
+
+    Ok(LaunchEconomics {
+        total_supply: number("total_supply", &args[0])?,
+        token_decimals,
+        sold_before_graduation_bps: number("sold_before_graduation_bps", &args[1])?,
+        grad_threshold: number("grad_threshold", &args[2])?,
+        base_decimals,
+        start_price,
+    })
+}
+
+/// Parses a non-negative decimal such as `0.000000028` into a WAD-scaled integer.
+fn parse_wad(value: &str) -> Result<u128, String> {
+    let invalid = || format!("start_price must be a decimal number, got `{}`", value);
+
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if (whole.is_empty() && fraction.is_empty()) || fraction.len() > 18 {
+        return Err(invalid());
+    }
+    let digits = |part: &str| -> Result<u128, String> {
+        if part.is_empty() {
+            return Ok(0);
+        }
+        if !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        part.parse::<u128>().map_err(|_| invalid())
+    };
+
+    let fraction_wad = digits(fraction)? * 10_u128.pow(18 - fraction.len() as u32);
+    digits(whole)?
+        .checked_mul(WAD)
+        .and_then(|whole_wad| whole_wad.checked_add(fraction_wad))
+        .ok_or_else(invalid)
+}
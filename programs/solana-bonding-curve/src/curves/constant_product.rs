@@ -0,0 +1,486 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::math::{div_ceil, U256, WAD};
+use crate::curves::{BondingCurveTrait, CurvePosition};
+use crate::errors::CustomError;
+
+//==============================================================================
+/// A constant-product (x * y = k) curve over virtual reserves, as used by pump.fun.
+///
+/// Invariant: (V_base + x) * (V_token - y) = V_base * V_token
+/// Inverse:   y(x) = V_token - V_base * V_token / (V_base + x)
+/// - V_base  = virtual base reserve (in base_tokens)
+/// - V_token = virtual token reserve (in raw token base units, i.e. scaled by 10^decimals)
+/// - R       = real token reserve, the tokens actually sold on the curve (R < V_token)
+///
+/// Token amounts are rounded down and base amounts up.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct ConstantProductBondingCurve {
+    /// Virtual base reserve (in base_tokens)
+    pub virtual_base_reserve: u64,
+    /// Virtual token reserve (in raw tokens)
+    pub virtual_token_reserve: u64,
+    /// Tokens that can actually be sold on the curve
+    pub real_token_reserve: u64,
+}
+
+impl ConstantProductBondingCurve {
+    pub const LEN: usize = 8 // virtual_base_reserve
+        + 8 // virtual_token_reserve
+        + 8; // real_token_reserve
+
+    /// Checks that the reserves are non-zero and that the real reserve is strictly
+    /// below the virtual one, so selling out the curve stays in range.
+    pub fn validate(&self) -> std::result::Result<(), CustomError> {
+        if self.virtual_base_reserve == 0
+            || self.real_token_reserve == 0
+            || self.real_token_reserve >= self.virtual_token_reserve
+        {
+            return Err(CustomError::InvalidCurveParameters);
+        }
+        self.x_of_y(self.real_token_reserve)
+            .map_err(|_| CustomError::InvalidCurveParameters)?;
+        Ok(())
+    }
+
+    fn invariant(&self) -> u128 {
+        (self.virtual_base_reserve as u128) * (self.virtual_token_reserve as u128)
+    }
+}
+
+impl CurvePosition for ConstantProductBondingCurve {
+    /// Calculates the total minted tokens at `x_val` base_tokens in the pool:
+    /// y(x) = V_token - k / (V_base + x) (rounded down)
+    fn y_of_x(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let base_reserve = (self.virtual_base_reserve as u128)
+            .checked_add(x_val as u128)
+            .ok_or(CustomError::MathOverflow)?;
+        let token_reserve =
+            div_ceil(self.invariant(), base_reserve).ok_or(CustomError::MathOverflow)?;
+
+        let y = (self.virtual_token_reserve as u128)
+            .checked_sub(token_reserve)
+            .ok_or(CustomError::MathOverflow)?;
+
+        u64::try_from(y).map_err(|_| CustomError::MathOverflow)
+    }
+
+    /// Same as `y_of_x`, rounded up.
+    fn y_of_x_ceil(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let base_reserve = (self.virtual_base_reserve as u128)
+            .checked_add(x_val as u128)
+            .ok_or(CustomError::MathOverflow)?;
+        let token_reserve = self.invariant() / base_reserve;
+
+        let y = (self.virtual_token_reserve as u128)
+            .checked_sub(token_reserve)
+            .ok_or(CustomError::MathOverflow)?;
+
+        u64::try_from(y).map_err(|_| CustomError::MathOverflow)
+    }
+
+    /// Calculates the base_tokens that must be in the pool for `y_val` tokens to be minted:
+    /// x(y) = k / (V_token - y) - V_base (rounded up)
+    ///
+    /// Returns an error if y exceeds the real token reserve.
+    fn x_of_y(&self, y_val: u64) -> std::result::Result<u64, CustomError> {
+        if y_val > self.real_token_reserve {
+            return Err(CustomError::InsufficientTokenSupply);
+        }
+
+        let token_reserve = self
+            .virtual_token_reserve
+            .checked_sub(y_val)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+        let base_reserve = div_ceil(self.invariant(), token_reserve as u128)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+
+        let x = base_reserve
+            .checked_sub(self.virtual_base_reserve as u128)
+            .ok_or(CustomError::MathOverflow)?;
+
+        u64::try_from(x).map_err(|_| CustomError::MathOverflow)
+    }
+}
+
+impl BondingCurveTrait for ConstantProductBondingCurve {
+    /// Buys with exact base_tokens in, returning the exact number of minted tokens (Δy),
+    /// plus the updated x.
+    fn buy_exact_input(
+        &self,
+        old_x: u64,
+        base_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let new_x = old_x
+            .checked_add(base_in)
+            .ok_or(CustomError::MathOverflow)?;
+
+        // minted = floor(y(new_x)) - ceil(y(old_x)), zero when less than one raw token
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+        if new_y > self.real_token_reserve {
+            return Err(CustomError::InsufficientTokenSupply);
+        }
+
+        let minted = new_y.saturating_sub(old_y);
+
+        Ok((minted, new_x))
+    }
+
+    /// Buys an exact number of tokens out (tokens_out), returning the exact base_tokens required,
+    /// plus the updated x.
+    fn buy_exact_output(
+        &self,
+        old_x: u64,
+        tokens_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let old_y = self.y_of_x_ceil(old_x)?;
+
+        let new_y = old_y
+            .checked_add(tokens_out)
+            .ok_or(CustomError::MathOverflow)?;
+
+        let x_prime = self.x_of_y(new_y)?;
+
+        let base_in = x_prime
+            .checked_sub(old_x)
+            .ok_or(CustomError::MathOverflow)?;
+
+        Ok((base_in, x_prime))
+    }
+
+    /// Sells an exact number of tokens in, returning the exact base_tokens out,
+    /// plus the updated x.
+    fn sell_exact_input(
+        &self,
+        old_x: u64,
+        tokens_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let old_y = self.y_of_x_ceil(old_x)?;
+
+        let new_y = old_y
+            .checked_sub(tokens_in)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+
+        // The pool never moves above old_x
+        let x_prime = self.x_of_y(new_y)?.min(old_x);
+
+        let base_out = old_x
+            .checked_sub(x_prime)
+            .ok_or(CustomError::MathOverflow)?;
+
+        Ok((base_out, x_prime))
+    }
+
+    /// Sells enough tokens to receive exactly `base_out` from the curve.
+    /// Returns the number of "pool tokens" that must be burned,
+    /// plus the updated x.
+    fn sell_exact_output(
+        &self,
+        old_x: u64,
+        base_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let new_x = old_x
+            .checked_sub(base_out)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+
+        // tokens_to_burn = ceil(y(old_x)) - floor(y(new_x))
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+
+        let tokens_to_burn = old_y.checked_sub(new_y).ok_or(CustomError::MathOverflow)?;
+
+        Ok((tokens_to_burn, new_x))
+    }
+
+    fn total_tokens(&self) -> std::result::Result<u64, CustomError> {
+        Ok(self.real_token_reserve)
+    }
+
+    /// y(x), capped at the real token reserve: rounding its deposit up can leave the
+    /// position a little past it.
+    fn tokens_sold(&self, x: u64) -> std::result::Result<u64, CustomError> {
+        Ok(self.y_of_x(x)?.min(self.real_token_reserve))
+    }
+
+    /// dx/dy = (V_base + x)^2 / k
+    fn spot_price(&self, x: u64) -> std::result::Result<u128, CustomError> {
+        let base_reserve = (self.virtual_base_reserve as u128)
+            .checked_add(x as u128)
+            .ok_or(CustomError::MathOverflow)?;
+
+        U256::full_mul(base_reserve, base_reserve)
+            .checked_mul(WAD)
+            .and_then(|scaled| scaled.div_rem(self.invariant()))
+            .map(|(price, _)| price)
+            .ok_or(CustomError::MathOverflow)
+    }
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    mod pump_params {
+        use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+        /// One whole token in raw base units (9 decimals).
+        pub const TOKEN: u64 = 1_000_000_000;
+
+        // pump.fun launch reserves: 30 virtual SOL against 1.073B virtual tokens.
+        pub const VIRTUAL_BASE_RESERVE: u64 = 30 * LAMPORTS_PER_SOL;
+        pub const VIRTUAL_TOKEN_RESERVE: u64 = 1_073_000_191 * TOKEN;
+        pub const REAL_TOKEN_RESERVE: u64 = 793_100_000 * TOKEN;
+    }
+
+    /// Helper function for building the default test curve.
+    fn default_curve() -> ConstantProductBondingCurve {
+        ConstantProductBondingCurve {
+            virtual_base_reserve: pump_params::VIRTUAL_BASE_RESERVE,
+            virtual_token_reserve: pump_params::VIRTUAL_TOKEN_RESERVE,
+            real_token_reserve: pump_params::REAL_TOKEN_RESERVE,
+        }
+    }
+
+    #[test]
+    fn test_buy_exact_input() {
+        let curve = default_curve();
+        let old_x = 0; // start with empty pool
+        let base_in = (0.01_f64 * LAMPORTS_PER_SOL as f64) as u64;
+
+        let (minted, new_x) = curve.buy_exact_input(old_x, base_in).unwrap();
+        println!("minted: {}", minted);
+        println!("new_x (pool deposit) after buy: {}", new_x);
+
+        // Same launch economics as the smooth curve test parameters.
+        assert!(
+            (357_000 * pump_params::TOKEN..358_000 * pump_params::TOKEN).contains(&minted),
+            "Minted tokens out of expected range: {}",
+            minted
+        );
+        assert_eq!(new_x, base_in);
+    }
+
+    #[test]
+    fn test_buy_exact_output() {
+        let curve = default_curve();
+
+        let old_x = 0;
+        let tokens_out = 10_000 * pump_params::TOKEN;
+
+        let (lamports_required, new_x) = curve.buy_exact_output(old_x, tokens_out).unwrap();
+        println!("Tokens to buy: {}", tokens_out);
+        println!("Lamports required: {}", lamports_required);
+
+        assert!(
+            lamports_required > 0,
+            "Lamports required should be greater than 0"
+        );
+
+        let real_new_y = curve.y_of_x(new_x).unwrap();
+        assert!(
+            real_new_y >= tokens_out,
+            "The curve state should reflect at least the number of tokens bought"
+        );
+        assert!(
+            curve.y_of_x(new_x - 1).unwrap() <= tokens_out,
+            "The buyer should not be charged more than needed"
+        );
+    }
+
+    #[test]
+    fn test_buy_various_inputs() {
+        let base_in = [0.01, 0.1, 1.0, 10.0, 50.0];
+        let mut prev_minted = u64::MIN;
+        let curve = default_curve();
+
+        for base_token in base_in {
+            let lamports_in = (LAMPORTS_PER_SOL as f64 * base_token) as u64;
+            let (minted, _new_x) = curve.buy_exact_input(0, lamports_in).unwrap();
+
+            println!(
+                "base_token in = {:.4}, lamports_in = {}, minted = {}",
+                base_token, lamports_in, minted
+            );
+
+            assert!(
+                minted > prev_minted,
+                "Expected minted tokens to grow with the base input"
+            );
+            prev_minted = minted;
+        }
+    }
+
+    #[test]
+    fn test_buy_beyond_supply() {
+        let curve = default_curve();
+
+        // Selling the whole real reserve takes ~85 SOL.
+        let result = curve.buy_exact_input(0, 100 * LAMPORTS_PER_SOL);
+        assert!(matches!(result, Err(CustomError::InsufficientTokenSupply)));
+
+        let (base_in, _) = curve
+            .buy_exact_output(0, pump_params::REAL_TOKEN_RESERVE)
+            .unwrap();
+        assert!(
+            (84 * LAMPORTS_PER_SOL..86 * LAMPORTS_PER_SOL).contains(&base_in),
+            "Unexpected sell-out deposit: {}",
+            base_in
+        );
+    }
+
+    #[test]
+    fn test_sell_exact_input() {
+        let curve = default_curve();
+
+        let sol_in = (0.1 * LAMPORTS_PER_SOL as f64) as u64;
+        let (minted_tokens, x) = curve.buy_exact_input(0, sol_in).unwrap();
+        assert!(minted_tokens > 0);
+
+        let tokens_to_sell = minted_tokens / 2;
+        let (lamports_out, next_x) = curve.sell_exact_input(x, tokens_to_sell).unwrap();
+        println!("lamports_out: {}", lamports_out);
+        println!("new pool x after sell: {}", next_x);
+
+        // The half sold back was bought at the higher end of the curve.
+        assert!(
+            lamports_out > sol_in / 2 && lamports_out < sol_in,
+            "Selling half the tokens should return more than half the deposit: {}",
+            lamports_out
+        );
+    }
+
+    #[test]
+    fn test_sell_exact_output() {
+        let curve = default_curve();
+
+        let base_in = (0.1 * LAMPORTS_PER_SOL as f64) as u64;
+        let (minted_tokens, x) = curve.buy_exact_input(0, base_in).unwrap();
+        assert!(minted_tokens > 0, "Initial token minting failed");
+
+        let base_out = x / 2;
+        let (tokens_burned, after_x) = curve.sell_exact_output(x, base_out).unwrap();
+        println!("tokens_burned: {}", tokens_burned);
+
+        assert_eq!(
+            after_x,
+            x - base_out,
+            "Pool's base_tokens did not decrease correctly by base_out"
+        );
+
+        let real_burn = curve.y_of_x_ceil(x).unwrap() - curve.y_of_x(after_x).unwrap();
+        assert_eq!(
+            tokens_burned, real_burn,
+            "Mismatch in token burn calculation"
+        );
+    }
+
+    #[test]
+    fn test_buy_sell_symmetry() {
+        let curve = default_curve();
+
+        // (A) Buy Exact Input -> Sell Exact Input (from scratch)
+        let lamports_in_a: u64 = 2 * LAMPORTS_PER_SOL;
+        let (minted_a, x) = curve.buy_exact_input(0, lamports_in_a).unwrap();
+        let (lamports_out_a, _) = curve.sell_exact_input(x, minted_a).unwrap();
+        // Rounding in the pool's favor may leave a single base unit of dust in the pool.
+        assert!(
+            lamports_out_a <= lamports_in_a && lamports_in_a - lamports_out_a <= 1,
+            "Unexpected slippage in (A): got {}, expected {}",
+            lamports_out_a,
+            lamports_in_a
+        );
+
+        // (B) Buy Exact Output -> Sell Exact Input
+        let tokens_out_b = 50_000 * pump_params::TOKEN;
+        let (lamports_in_b, x2) = curve.buy_exact_output(0, tokens_out_b).unwrap();
+        let (lamports_out_b, _) = curve.sell_exact_input(x2, tokens_out_b).unwrap();
+        assert!(
+            lamports_out_b <= lamports_in_b && lamports_in_b - lamports_out_b <= 1,
+            "Unexpected slippage in (B): got {}, expected {}",
+            lamports_out_b,
+            lamports_in_b
+        );
+    }
+
+    #[test]
+    fn test_matches_smooth_curve() {
+        // The smooth curve with A = V_token, K = V_base * V_token, C = V_base is the same curve.
+        let curve = default_curve();
+        let smooth = crate::curves::SmoothBondingCurve {
+            a_total_tokens: pump_params::VIRTUAL_TOKEN_RESERVE,
+            k_virtual_pool_offset: curve.invariant(),
+            c_bonding_scale_factor: pump_params::VIRTUAL_BASE_RESERVE,
+        };
+
+        for base_in in [
+            LAMPORTS_PER_SOL / 100,
+            LAMPORTS_PER_SOL,
+            20 * LAMPORTS_PER_SOL,
+        ] {
+            let (minted, _) = curve.buy_exact_input(0, base_in).unwrap();
+            let (smooth_minted, _) = smooth.buy_exact_input(0, base_in).unwrap();
+            assert!(
+                minted.abs_diff(smooth_minted) <= 1,
+                "Diverged from the smooth curve: {} vs {}",
+                minted,
+                smooth_minted
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(default_curve().validate().is_ok());
+
+        let mut curve = default_curve();
+        curve.real_token_reserve = curve.virtual_token_reserve;
+        assert!(matches!(
+            curve.validate(),
+            Err(CustomError::InvalidCurveParameters)
+        ));
+
+        let mut curve = default_curve();
+        curve.virtual_base_reserve = 0;
+        assert!(matches!(
+            curve.validate(),
+            Err(CustomError::InvalidCurveParameters)
+        ));
+    }
+
+    #[test]
+    fn test_spot_price_and_market_cap() {
+        let curve = default_curve();
+
+        // V_base / V_token ~= 27.96 lamports per whole token (WAD-scaled per raw token).
+        let start_price = curve.spot_price(0).unwrap();
+        assert!(
+            (27_950_000_000..27_970_000_000).contains(&start_price),
+            "Unexpected starting price: {}",
+            start_price
+        );
+        assert_eq!(curve.market_cap(0).unwrap(), 0);
+        assert_eq!(
+            curve.tokens_remaining(0).unwrap(),
+            pump_params::REAL_TOKEN_RESERVE
+        );
+
+        let (minted, x) = curve.buy_exact_input(0, 10 * LAMPORTS_PER_SOL).unwrap();
+        assert!(curve.spot_price(x).unwrap() > start_price);
+        assert_eq!(
+            curve.tokens_remaining(x).unwrap(),
+            pump_params::REAL_TOKEN_RESERVE - minted
+        );
+
+        // The price only grows, so every token sold so far is worth more than it cost.
+        let market_cap = curve.market_cap(x).unwrap();
+        assert!(
+            market_cap > x as u128,
+            "Unexpected market cap: {}",
+            market_cap
+        );
+    }
+}
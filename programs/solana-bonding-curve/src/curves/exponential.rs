@@ -0,0 +1,468 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::math::{div_ceil, exp_wad, ln_wad, mul_div, LN_WAD_MAX_ERROR, WAD};
+use crate::curves::{BondingCurveTrait, CurvePosition};
+use crate::errors::CustomError;
+
+//==============================================================================
+/// A bonding curve whose marginal price grows exponentially with the tokens sold.
+///
+/// Price:   p(y) = (V / L) * e^(y / L)
+/// Deposit: x(y) = V * (e^(y / L) - 1)
+/// Inverse: y(x) = L * ln((V + x) / V)
+/// - A = total tokens sold on the curve (in raw token base units, i.e. scaled by 10^decimals)
+/// - V = virtual base reserve (in base_tokens), sets the starting price V / L
+/// - L = growth length (in raw tokens): the price grows by a factor of e every L tokens
+///
+/// `exp` and `ln` are evaluated in WAD fixed-point (see `curves::math`); `exp` is
+/// rounded up and `ln` down, so every trade still rounds in the pool's favor.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct ExponentialBondingCurve {
+    /// Total tokens that can be sold on the curve
+    pub a_total_tokens: u64,
+    /// Virtual base reserve that sets the starting price (in base_tokens)
+    pub v_virtual_base_reserve: u64,
+    /// Tokens sold per e-fold of the price
+    pub l_growth_tokens: u64,
+}
+
+impl ExponentialBondingCurve {
+    pub const LEN: usize = 8 // a_total_tokens
+        + 8 // v_virtual_base_reserve
+        + 8; // l_growth_tokens
+
+    /// Checks that the parameters are non-zero and that selling out the curve stays in range.
+    pub fn validate(&self) -> std::result::Result<(), CustomError> {
+        if self.a_total_tokens == 0 || self.v_virtual_base_reserve == 0 || self.l_growth_tokens == 0
+        {
+            return Err(CustomError::InvalidCurveParameters);
+        }
+        self.x_of_y(self.a_total_tokens)
+            .map_err(|_| CustomError::InvalidCurveParameters)?;
+        Ok(())
+    }
+}
+
+impl CurvePosition for ExponentialBondingCurve {
+    /// Calculates the total minted tokens at `x_val` base_tokens in the pool:
+    /// y(x) = L * ln((V + x) / V) (rounded down)
+    fn y_of_x(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let v = self.v_virtual_base_reserve as u128;
+        let ratio_wad = v
+            .checked_add(x_val as u128)
+            .and_then(|v_plus_x| v_plus_x.checked_mul(WAD))
+            .and_then(|scaled| scaled.checked_div(v))
+            .ok_or(CustomError::MathOverflow)?;
+
+        let ln = ln_wad(ratio_wad).ok_or(CustomError::MathOverflow)?;
+        let y = (self.l_growth_tokens as u128)
+            .checked_mul(ln)
+            .ok_or(CustomError::MathOverflow)?
+            / WAD;
+
+        u64::try_from(y).map_err(|_| CustomError::MathOverflow)
+    }
+
+    /// Same as `y_of_x`, rounded up.
+    ///
+    /// `ln_wad` only bounds ln from below, so its worst-case error is added back first.
+    fn y_of_x_ceil(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        if x_val == 0 {
+            return Ok(0);
+        }
+
+        let v = self.v_virtual_base_reserve as u128;
+        let ratio_wad = v
+            .checked_add(x_val as u128)
+            .and_then(|v_plus_x| v_plus_x.checked_mul(WAD))
+            .and_then(|scaled| div_ceil(scaled, v))
+            .ok_or(CustomError::MathOverflow)?;
+
+        let ln = ln_wad(ratio_wad)
+            .and_then(|ln| ln.checked_add(LN_WAD_MAX_ERROR))
+            .ok_or(CustomError::MathOverflow)?;
+        let y = (self.l_growth_tokens as u128)
+            .checked_mul(ln)
+            .and_then(|scaled| div_ceil(scaled, WAD))
+            .ok_or(CustomError::MathOverflow)?;
+
+        u64::try_from(y).map_err(|_| CustomError::MathOverflow)
+    }
+
+    /// Calculates the base_tokens that must be in the pool for `y_val` tokens to be minted:
+    /// x(y) = V * (e^(y / L) - 1) (rounded up)
+    ///
+    /// Returns an error if y exceeds A.
+    fn x_of_y(&self, y_val: u64) -> std::result::Result<u64, CustomError> {
+        if y_val > self.a_total_tokens {
+            return Err(CustomError::InsufficientTokenSupply);
+        }
+
+        let exponent_wad = div_ceil(
+            (y_val as u128)
+                .checked_mul(WAD)
+                .ok_or(CustomError::MathOverflow)?,
+            self.l_growth_tokens as u128,
+        )
+        .ok_or(CustomError::MathOverflow)?;
+
+        let growth_wad = exp_wad(exponent_wad).ok_or(CustomError::MathOverflow)? - WAD;
+        let x = (self.v_virtual_base_reserve as u128)
+            .checked_mul(growth_wad)
+            .and_then(|scaled| div_ceil(scaled, WAD))
+            .ok_or(CustomError::MathOverflow)?;
+
+        u64::try_from(x).map_err(|_| CustomError::MathOverflow)
+    }
+}
+
+impl BondingCurveTrait for ExponentialBondingCurve {
+    /// Buys with exact base_tokens in, returning the exact number of minted tokens (Δy),
+    /// plus the updated x.
+    fn buy_exact_input(
+        &self,
+        old_x: u64,
+        base_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let new_x = old_x
+            .checked_add(base_in)
+            .ok_or(CustomError::MathOverflow)?;
+
+        // minted = floor(y(new_x)) - ceil(y(old_x)), zero when less than one raw token
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+        if new_y > self.a_total_tokens {
+            return Err(CustomError::InsufficientTokenSupply);
+        }
+
+        let minted = new_y.saturating_sub(old_y);
+
+        Ok((minted, new_x))
+    }
+
+    /// Buys an exact number of tokens out (tokens_out), returning the exact base_tokens required,
+    /// plus the updated x.
+    fn buy_exact_output(
+        &self,
+        old_x: u64,
+        tokens_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let old_y = self.y_of_x_ceil(old_x)?;
+
+        let new_y = old_y
+            .checked_add(tokens_out)
+            .ok_or(CustomError::MathOverflow)?;
+
+        let x_prime = self.x_of_y(new_y)?;
+
+        let base_in = x_prime
+            .checked_sub(old_x)
+            .ok_or(CustomError::MathOverflow)?;
+
+        Ok((base_in, x_prime))
+    }
+
+    /// Sells an exact number of tokens in, returning the exact base_tokens out,
+    /// plus the updated x.
+    fn sell_exact_input(
+        &self,
+        old_x: u64,
+        tokens_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let old_y = self.y_of_x_ceil(old_x)?;
+
+        let new_y = old_y
+            .checked_sub(tokens_in)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+
+        // The pool never moves above old_x
+        let x_prime = self.x_of_y(new_y)?.min(old_x);
+
+        let base_out = old_x
+            .checked_sub(x_prime)
+            .ok_or(CustomError::MathOverflow)?;
+
+        Ok((base_out, x_prime))
+    }
+
+    /// Sells enough tokens to receive exactly `base_out` from the curve.
+    /// Returns the number of "pool tokens" that must be burned,
+    /// plus the updated x.
+    fn sell_exact_output(
+        &self,
+        old_x: u64,
+        base_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let new_x = old_x
+            .checked_sub(base_out)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+
+        // tokens_to_burn = ceil(y(old_x)) - floor(y(new_x))
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+
+        let tokens_to_burn = old_y.checked_sub(new_y).ok_or(CustomError::MathOverflow)?;
+
+        Ok((tokens_to_burn, new_x))
+    }
+
+    fn total_tokens(&self) -> std::result::Result<u64, CustomError> {
+        Ok(self.a_total_tokens)
+    }
+
+    /// y(x), capped at A: rounding x(A) up can leave the position a little past A.
+    fn tokens_sold(&self, x: u64) -> std::result::Result<u64, CustomError> {
+        Ok(self.y_of_x(x)?.min(self.a_total_tokens))
+    }
+
+    /// dx/dy = (V / L) * e^(y / L) = (V + x) / L
+    fn spot_price(&self, x: u64) -> std::result::Result<u128, CustomError> {
+        let v_plus_x = (self.v_virtual_base_reserve as u128)
+            .checked_add(x as u128)
+            .ok_or(CustomError::MathOverflow)?;
+
+        mul_div(v_plus_x, WAD, self.l_growth_tokens as u128).ok_or(CustomError::MathOverflow)
+    }
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    mod exponential_params {
+        use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+        /// One whole token in raw base units (9 decimals).
+        pub const TOKEN: u64 = 1_000_000_000;
+
+        // Starts at 28 lamports per whole token (V / L); the price is e^4 higher once sold out.
+        pub const A_TOTAL_TOKENS: u64 = 800_000_000 * TOKEN;
+        pub const V_VIRTUAL_BASE_RESERVE: u64 = 5_600_000_000;
+        pub const L_GROWTH_TOKENS: u64 = 200_000_000 * TOKEN;
+
+        // x(A) = V * (e^4 - 1) ~= 300 SOL
+        pub const SELL_OUT_BASE: u64 = 300 * LAMPORTS_PER_SOL;
+    }
+
+    /// Helper function for building the default test curve.
+    fn default_curve() -> ExponentialBondingCurve {
+        ExponentialBondingCurve {
+            a_total_tokens: exponential_params::A_TOTAL_TOKENS,
+            v_virtual_base_reserve: exponential_params::V_VIRTUAL_BASE_RESERVE,
+            l_growth_tokens: exponential_params::L_GROWTH_TOKENS,
+        }
+    }
+
+    #[test]
+    fn test_buy_exact_input() {
+        let curve = default_curve();
+        let old_x = 0; // start with empty pool
+        let base_in = (0.01_f64 * LAMPORTS_PER_SOL as f64) as u64;
+
+        let (minted, new_x) = curve.buy_exact_input(old_x, base_in).unwrap();
+        println!("minted: {}", minted);
+        println!("new_x (pool deposit) after buy: {}", new_x);
+
+        // ~0.01 SOL / 28 lamports per token
+        assert!(
+            (356_000 * exponential_params::TOKEN..358_000 * exponential_params::TOKEN)
+                .contains(&minted),
+            "Minted tokens out of expected range: {}",
+            minted
+        );
+        assert_eq!(new_x, base_in);
+    }
+
+    #[test]
+    fn test_buy_exact_output() {
+        let curve = default_curve();
+
+        let old_x = 0;
+        let tokens_out = 10_000 * exponential_params::TOKEN;
+
+        let (lamports_required, new_x) = curve.buy_exact_output(old_x, tokens_out).unwrap();
+        println!("Tokens to buy: {}", tokens_out);
+        println!("Lamports required: {}", lamports_required);
+
+        assert!(
+            lamports_required > 0,
+            "Lamports required should be greater than 0"
+        );
+
+        let real_new_y = curve.y_of_x(new_x).unwrap();
+        assert!(
+            real_new_y >= tokens_out,
+            "The curve state should reflect at least the number of tokens bought"
+        );
+        assert!(
+            curve.y_of_x(new_x - 1).unwrap() <= tokens_out,
+            "The buyer should not be charged more than needed"
+        );
+    }
+
+    #[test]
+    fn test_buy_various_inputs() {
+        let base_in = [0.01, 0.1, 1.0, 10.0, 100.0];
+        let mut prev_minted = u64::MIN;
+        let curve = default_curve();
+
+        for base_token in base_in {
+            let lamports_in = (LAMPORTS_PER_SOL as f64 * base_token) as u64;
+            let (minted, _new_x) = curve.buy_exact_input(0, lamports_in).unwrap();
+
+            println!(
+                "base_token in = {:.4}, lamports_in = {}, minted = {}",
+                base_token, lamports_in, minted
+            );
+
+            assert!(
+                minted > prev_minted,
+                "Expected minted tokens to grow with the base input"
+            );
+            prev_minted = minted;
+        }
+    }
+
+    #[test]
+    fn test_buy_beyond_supply() {
+        let curve = default_curve();
+
+        let (minted, _) = curve
+            .buy_exact_input(0, exponential_params::SELL_OUT_BASE)
+            .unwrap();
+        assert!(minted <= exponential_params::A_TOTAL_TOKENS);
+
+        let result = curve.buy_exact_input(0, 2 * exponential_params::SELL_OUT_BASE);
+        assert!(matches!(result, Err(CustomError::InsufficientTokenSupply)));
+    }
+
+    #[test]
+    fn test_sell_exact_input() {
+        let curve = default_curve();
+
+        let sol_in = (0.1 * LAMPORTS_PER_SOL as f64) as u64;
+        let (minted_tokens, x) = curve.buy_exact_input(0, sol_in).unwrap();
+        assert!(minted_tokens > 0);
+
+        let tokens_to_sell = minted_tokens / 2;
+        let (lamports_out, next_x) = curve.sell_exact_input(x, tokens_to_sell).unwrap();
+        println!("lamports_out: {}", lamports_out);
+        println!("new pool x after sell: {}", next_x);
+
+        // The half sold back was bought at the higher end of the curve.
+        assert!(
+            lamports_out > sol_in / 2 && lamports_out < sol_in,
+            "Selling half the tokens should return more than half the deposit: {}",
+            lamports_out
+        );
+    }
+
+    #[test]
+    fn test_sell_exact_output() {
+        let curve = default_curve();
+
+        let base_in = (0.1 * LAMPORTS_PER_SOL as f64) as u64;
+        let (minted_tokens, x) = curve.buy_exact_input(0, base_in).unwrap();
+        assert!(minted_tokens > 0, "Initial token minting failed");
+
+        let base_out = x / 2;
+        let (tokens_burned, after_x) = curve.sell_exact_output(x, base_out).unwrap();
+        println!("tokens_burned: {}", tokens_burned);
+
+        assert_eq!(
+            after_x,
+            x - base_out,
+            "Pool's base_tokens did not decrease correctly by base_out"
+        );
+
+        let real_burn = curve.y_of_x_ceil(x).unwrap() - curve.y_of_x(after_x).unwrap();
+        assert_eq!(
+            tokens_burned, real_burn,
+            "Mismatch in token burn calculation"
+        );
+    }
+
+    #[test]
+    fn test_buy_sell_symmetry() {
+        let curve = default_curve();
+
+        // (A) Buy Exact Input -> Sell Exact Input (from scratch)
+        let lamports_in_a: u64 = 2 * LAMPORTS_PER_SOL;
+        let (minted_a, x) = curve.buy_exact_input(0, lamports_in_a).unwrap();
+        let (lamports_out_a, _) = curve.sell_exact_input(x, minted_a).unwrap();
+        // Rounding in the pool's favor may leave a single base unit of dust in the pool.
+        assert!(
+            lamports_out_a <= lamports_in_a && lamports_in_a - lamports_out_a <= 1,
+            "Unexpected slippage in (A): got {}, expected {}",
+            lamports_out_a,
+            lamports_in_a
+        );
+
+        // (B) Buy Exact Output -> Sell Exact Input
+        let tokens_out_b = 50_000 * exponential_params::TOKEN;
+        let (lamports_in_b, x2) = curve.buy_exact_output(0, tokens_out_b).unwrap();
+        let (lamports_out_b, _) = curve.sell_exact_input(x2, tokens_out_b).unwrap();
+        assert!(
+            lamports_out_b <= lamports_in_b && lamports_in_b - lamports_out_b <= 1,
+            "Unexpected slippage in (B): got {}, expected {}",
+            lamports_out_b,
+            lamports_in_b
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(default_curve().validate().is_ok());
+
+        let mut curve = default_curve();
+        curve.l_growth_tokens = 0;
+        assert!(matches!(
+            curve.validate(),
+            Err(CustomError::InvalidCurveParameters)
+        ));
+
+        // e^(A / L) overflows long before the curve sells out.
+        let mut curve = default_curve();
+        curve.l_growth_tokens = exponential_params::A_TOTAL_TOKENS / 1_000;
+        assert!(matches!(
+            curve.validate(),
+            Err(CustomError::InvalidCurveParameters)
+        ));
+    }
+
+    #[test]
+    fn test_spot_price_and_market_cap() {
+        let curve = default_curve();
+
+        // V / L = 28 lamports per whole token, i.e. 28e-9 per raw token (WAD-scaled).
+        let start_price = curve.spot_price(0).unwrap();
+        assert!(
+            (27_999_000_000..28_001_000_000).contains(&start_price),
+            "Unexpected starting price: {}",
+            start_price
+        );
+        assert_eq!(curve.market_cap(0).unwrap(), 0);
+        assert_eq!(
+            curve.tokens_remaining(0).unwrap(),
+            exponential_params::A_TOTAL_TOKENS
+        );
+
+        let (minted, x) = curve.buy_exact_input(0, 10 * LAMPORTS_PER_SOL).unwrap();
+        assert!(curve.spot_price(x).unwrap() > start_price);
+        assert_eq!(
+            curve.tokens_remaining(x).unwrap(),
+            exponential_params::A_TOTAL_TOKENS - minted
+        );
+
+        // The price only grows, so every token sold so far is worth more than it cost.
+        let market_cap = curve.market_cap(x).unwrap();
+        assert!(
+            market_cap > x as u128,
+            "Unexpected market cap: {}",
+            market_cap
+        );
+    }
+}
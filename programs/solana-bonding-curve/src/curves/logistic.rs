@@ -0,0 +1,643 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::math::{mul_div, U256, WAD};
+use crate::curves::{BondingCurveTrait, CurvePosition};
+use crate::errors::CustomError;
+
+/// Table knots per unit of z, i.e. the sigmoid is sampled every 0.25.
+const KNOTS_PER_UNIT: u64 = 4;
+
+/// sigma(z) = 1 / (1 + e^-z) for z = 0, 0.25, ..., 8, scaled by `WAD`.
+/// Negative z use sigma(-z) = 1 - sigma(z); beyond |z| = 8 the table is flat.
+const SIGMOID_WAD: [u128; 33] = [
+    500_000_000_000_000_000,
+    562_176_500_885_798_104,
+    622_459_331_201_854_565,
+    679_178_699_175_392_973,
+    731_058_578_630_004_879,
+    777_299_861_174_691_147,
+    817_574_476_193_643_660,
+    851_952_801_968_310_530,
+    880_797_077_977_882_444,
+    904_650_535_100_890_506,
+    924_141_819_978_756_449,
+    939_913_349_825_992_378,
+    952_574_126_822_433_219,
+    962_673_112_655_870_540,
+    970_687_769_248_643_681,
+    977_022_630_089_974_385,
+    982_013_790_037_908_442,
+    985_936_372_956_754_526,
+    989_013_057_369_406_820,
+    991_422_514_586_288_015,
+    993_307_149_075_715_144,
+    994_779_874_306_441_603,
+    995_929_862_284_103_873,
+    996_827_317_157_514_810,
+    997_527_376_843_365_226,
+    998_073_265_336_672_525,
+    998_498_817_743_263_008,
+    998_830_489_734_944_485,
+    999_088_948_805_599_355,
+    999_290_329_600_899_412,
+    999_447_221_363_076_400,
+    999_569_442_918_675_385,
+    999_664_649_869_533_522,
+];
+
+/// Outermost knot index on either side of the midpoint (z = +-8).
+const LAST_KNOT: i128 = (SIGMOID_WAD.len() - 1) as i128;
+
+/// Worst-case gap between the tabulated price and the exact sigmoid, as a share of
+/// P_max - P_min in parts per million: linear interpolation over 0.25 steps is off by
+/// at most 0.25^2 / 8 * max|sigma''| < 752 ppm, the flat tails by sigma(-8) < 336 ppm.
+pub const LOGISTIC_MAX_PRICE_ERROR_PPM: u128 = 1_100;
+
+//==============================================================================
+/// A bonding curve whose price follows a logistic (sigmoid) function of the tokens
+/// sold: nearly flat early, steepest at the midpoint and flat again near the end.
+///
+/// Price:   p(y) = P_min + (P_max - P_min) * sigma((y - M) / S)
+/// Deposit: x(y) = integral of p from 0 to y
+/// - A = total tokens sold on the curve (in raw token base units, i.e. scaled by 10^decimals)
+/// - P_min, P_max = price floor and ceiling (base_tokens per raw token, scaled by WAD)
+/// - M = tokens sold at the midpoint, where the price grows the fastest
+/// - S = tokens per unit of z, i.e. how stretched the S-shape is
+///
+/// On-chain, sigma is read from `SIGMOID_WAD` and interpolated linearly, so p(y) is
+/// piecewise linear and x(y) piecewise quadratic. Both are evaluated exactly as a
+/// 256-bit numerator over 2 * (S / 4) * WAD, and y(x) is found by a binary search
+/// inside the one piece that x falls into. Token amounts are rounded down and base
+/// amounts up.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct LogisticBondingCurve {
+    /// Total tokens that can be sold on the curve
+    pub a_total_tokens: u64,
+    /// Price floor (base_tokens per raw token, scaled by WAD)
+    pub p_min_price: u128,
+    /// Price ceiling (base_tokens per raw token, scaled by WAD)
+    pub p_max_price: u128,
+    /// Tokens sold at the midpoint of the S-shape (in raw tokens)
+    pub m_midpoint_tokens: u64,
+    /// Tokens per unit of the sigmoid's argument, a multiple of 4 (in raw tokens)
+    pub s_scale_tokens: u64,
+}
+
+/// One piece of the interpolated price: p(y) = price + slope * (y - knot) / step.
+struct PricePiece {
+    /// First token position of the piece, clipped to 0
+    start: u64,
+    /// Last token position of the piece, clipped to u64::MAX
+    end: u64,
+    /// Knot position the price is interpolated from (may be negative)
+    knot: i128,
+    /// Price at `knot`
+    price: u128,
+    /// Price increase up to the next knot, zero on the flat tails
+    slope: u128,
+}
+
+impl LogisticBondingCurve {
+    pub const LEN: usize = 8 // a_total_tokens
+        + 16 // p_min_price
+        + 16 // p_max_price
+        + 8 // m_midpoint_tokens
+        + 8; // s_scale_tokens
+
+    /// Checks that the prices increase, the scale is a non-zero multiple of 4 and
+    /// that selling out the curve stays in range.
+    pub fn validate(&self) -> std::result::Result<(), CustomError> {
+        if self.a_total_tokens == 0
+            || self.p_min_price == 0
+            || self.p_max_price <= self.p_min_price
+            || self.s_scale_tokens == 0
+            || self.s_scale_tokens % KNOTS_PER_UNIT != 0
+        {
+            return Err(CustomError::InvalidCurveParameters);
+        }
+        self.x_of_y(self.a_total_tokens)
+            .map_err(|_| CustomError::InvalidCurveParameters)?;
+        Ok(())
+    }
+
+    /// Tokens between two knots.
+    fn step(&self) -> u64 {
+        self.s_scale_tokens / KNOTS_PER_UNIT
+    }
+
+    /// Price at knot `index` (-32..=32): P_min + (P_max - P_min) * sigma(index / 4).
+    fn knot_price(&self, index: i128) -> std::result::Result<u128, CustomError> {
+        let sigmoid = match usize::try_from(index) {
+            Ok(i) => SIGMOID_WAD[i],
+            Err(_) => WAD - SIGMOID_WAD[index.unsigned_abs() as usize],
+        };
+        let spread = self.p_max_price - self.p_min_price;
+        mul_div(spread, sigmoid, WAD)
+            .and_then(|scaled| scaled.checked_add(self.p_min_price))
+            .ok_or(CustomError::MathOverflow)
+    }
+
+    /// Position of knot `index` in tokens sold: M + index * S / 4.
+    fn knot_position(&self, index: i128) -> i128 {
+        self.m_midpoint_tokens as i128 + index * self.step() as i128
+    }
+
+    /// The price piece `k` in 0..=2 * LAST_KNOT + 1, clipped to [0, u64::MAX];
+    /// `None` when it lies entirely outside that range.
+    fn piece(&self, k: i128) -> std::result::Result<Option<PricePiece>, CustomError> {
+        let clip = |position: i128| position.clamp(0, u64::MAX as i128) as u64;
+
+        let (knot_index, start, end, slope) = if k == 0 {
+            // Flat tail below the first knot.
+            let first = self.knot_position(-LAST_KNOT);
+            (-LAST_KNOT, 0, clip(first), 0)
+        } else if k > 2 * LAST_KNOT {
+            // Flat tail above the last knot.
+            let last = self.knot_position(LAST_KNOT);
+            (LAST_KNOT, clip(last), u64::MAX, 0)
+        } else {
+            let left = k - 1 - LAST_KNOT;
+            let slope = self.knot_price(left + 1)? - self.knot_price(left)?;
+            (
+                left,
+                clip(self.knot_position(left)),
+                clip(self.knot_position(left + 1)),
+                slope,
+            )
+        };
+
+        if start >= end && k <= 2 * LAST_KNOT {
+            return Ok(None);
+        }
+        Ok(Some(PricePiece {
+            start,
+            end,
+            knot: self.knot_position(knot_index),
+            price: self.knot_price(knot_index)?,
+            slope,
+        }))
+    }
+
+    /// Common denominator of every deposit numerator: 2 * step * WAD.
+    fn denominator(&self) -> std::result::Result<u128, CustomError> {
+        (2 * self.step() as u128)
+            .checked_mul(WAD)
+            .ok_or(CustomError::MathOverflow)
+    }
+
+    /// Deposit for the tokens from `a` to `b` inside `piece`, as a numerator over
+    /// `denominator()`:
+    /// 2 * step * price * (b - a) + slope * (b - a) * (a + b - 2 * knot)
+    fn piece_cost(
+        &self,
+        piece: &PricePiece,
+        a: u64,
+        b: u64,
+    ) -> std::result::Result<U256, CustomError> {
+        let width = (b - a) as u128;
+        let base = U256::full_mul(2 * self.step() as u128, piece.price)
+            .checked_mul(width)
+            .ok_or(CustomError::MathOverflow)?;
+        if piece.slope == 0 {
+            return Ok(base);
+        }
+
+        let offset = u128::try_from(a as i128 + b as i128 - 2 * piece.knot)
+            .map_err(|_| CustomError::MathOverflow)?;
+        U256::full_mul(width, offset)
+            .checked_mul(piece.slope)
+            .and_then(|ramp| ramp.checked_add(base))
+            .ok_or(CustomError::MathOverflow)
+    }
+
+    /// Exact deposit numerator for the first `y_val` tokens.
+    fn cost_of(&self, y_val: u64) -> std::result::Result<U256, CustomError> {
+        let mut total = U256::ZERO;
+        for k in 0..=2 * LAST_KNOT + 1 {
+            let Some(piece) = self.piece(k)? else {
+                continue;
+            };
+            if piece.start >= y_val {
+                break;
+            }
+            let cost = self.piece_cost(&piece, piece.start, piece.end.min(y_val))?;
+            total = total.checked_add(cost).ok_or(CustomError::MathOverflow)?;
+        }
+        Ok(total)
+    }
+
+    /// The most tokens whose exact deposit fits into `x_val`, and whether it fits exactly.
+    fn solve_for_y(&self, x_val: u64) -> std::result::Result<(u64, bool), CustomError> {
+        let target = U256::full_mul(x_val as u128, self.denominator()?);
+
+        let mut spent = U256::ZERO;
+        for k in 0..=2 * LAST_KNOT + 1 {
+            let Some(piece) = self.piece(k)? else {
+                continue;
+            };
+            let cost = |y: u64| {
+                self.piece_cost(&piece, piece.start, y)?
+                    .checked_add(spent)
+                    .ok_or(CustomError::MathOverflow)
+            };
+
+            let full = cost(piece.end)?;
+            if full <= target {
+                if piece.end == u64::MAX {
+                    return Err(CustomError::MathOverflow);
+                }
+                spent = full;
+                continue;
+            }
+
+            // cost(low) <= target < cost(high); the price is positive, so cost is increasing.
+            let (mut low, mut high) = (piece.start, piece.end);
+            while high - low > 1 {
+                let mid = low + (high - low) / 2;
+                if cost(mid)? <= target {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            return Ok((low, cost(low)? == target));
+        }
+        Err(CustomError::MathOverflow)
+    }
+
+    /// The interpolated price after `y_val` tokens, scaled by WAD (rounded down).
+    fn price_at(&self, y_val: u64) -> std::result::Result<u128, CustomError> {
+        for k in 0..=2 * LAST_KNOT + 1 {
+            let Some(piece) = self.piece(k)? else {
+                continue;
+            };
+            if y_val < piece.end || piece.end == u64::MAX {
+                if piece.slope == 0 {
+                    return Ok(piece.price);
+                }
+                let into_piece = (y_val as i128 - piece.knot) as u128;
+                return mul_div(piece.slope, into_piece, self.step() as u128)
+                    .and_then(|ramp| ramp.checked_add(piece.price))
+                    .ok_or(CustomError::MathOverflow);
+            }
+        }
+        Err(CustomError::MathOverflow)
+    }
+}
+
+impl CurvePosition for LogisticBondingCurve {
+    /// Calculates the total minted tokens at `x_val` base_tokens in the pool,
+    /// the largest y with x(y) <= x_val.
+    fn y_of_x(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        Ok(self.solve_for_y(x_val)?.0)
+    }
+
+    /// Same as `y_of_x`, rounded up.
+    fn y_of_x_ceil(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let (y, exact) = self.solve_for_y(x_val)?;
+        if exact {
+            Ok(y)
+        } else {
+            y.checked_add(1).ok_or(CustomError::MathOverflow)
+        }
+    }
+
+    /// Calculates the base_tokens that must be in the pool for `y_val` tokens to be minted
+    /// (rounded up).
+    ///
+    /// Returns an error if y exceeds A.
+    fn x_of_y(&self, y_val: u64) -> std::result::Result<u64, CustomError> {
+        if y_val > self.a_total_tokens {
+            return Err(CustomError::InsufficientTokenSupply);
+        }
+
+        let (x, remainder) = self
+            .cost_of(y_val)?
+            .div_rem(self.denominator()?)
+            .ok_or(CustomError::MathOverflow)?;
+        let x = if remainder > 0 { x + 1 } else { x };
+
+        u64::try_from(x).map_err(|_| CustomError::MathOverflow)
+    }
+}
+
+impl BondingCurveTrait for LogisticBondingCurve {
+    /// Buys with exact base_tokens in, returning the exact number of minted tokens (Δy),
+    /// plus the updated x.
+    fn buy_exact_input(
+        &self,
+        old_x: u64,
+        base_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let new_x = old_x
+            .checked_add(base_in)
+            .ok_or(CustomError::MathOverflow)?;
+
+        // minted = floor(y(new_x)) - ceil(y(old_x)), zero when less than one raw token
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+        if new_y > self.a_total_tokens {
+            return Err(CustomError::InsufficientTokenSupply);
+        }
+
+        let minted = new_y.saturating_sub(old_y);
+
+        Ok((minted, new_x))
+    }
+
+    /// Buys an exact number of tokens out (tokens_out), returning the exact base_tokens required,
+    /// plus the updated x.
+    fn buy_exact_output(
+        &self,
+        old_x: u64,
+        tokens_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let old_y = self.y_of_x_ceil(old_x)?;
+
+        let new_y = old_y
+            .checked_add(tokens_out)
+            .ok_or(CustomError::MathOverflow)?;
+
+        let x_prime = self.x_of_y(new_y)?;
+
+        let base_in = x_prime
+            .checked_sub(old_x)
+            .ok_or(CustomError::MathOverflow)?;
+
+        Ok((base_in, x_prime))
+    }
+
+    /// Sells an exact number of tokens in, returning the exact base_tokens out,
+    /// plus the updated x.
+    fn sell_exact_input(
+        &self,
+        old_x: u64,
+        tokens_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let old_y = self.y_of_x_ceil(old_x)?;
+
+        let new_y = old_y
+            .checked_sub(tokens_in)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+
+        // The pool never moves above old_x
+        let x_prime = self.x_of_y(new_y)?.min(old_x);
+
+        let base_out = old_x
+            .checked_sub(x_prime)
+            .ok_or(CustomError::MathOverflow)?;
+
+        Ok((base_out, x_prime))
+    }
+
+    /// Sells enough tokens to receive exactly `base_out` from the curve.
+    /// Returns the number of "pool tokens" that must be burned,
+    /// plus the updated x.
+    fn sell_exact_output(
+        &self,
+        old_x: u64,
+        base_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let new_x = old_x
+            .checked_sub(base_out)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+
+        // tokens_to_burn = ceil(y(old_x)) - floor(y(new_x))
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+
+        let tokens_to_burn = old_y.checked_sub(new_y).ok_or(CustomError::MathOverflow)?;
+
+        Ok((tokens_to_burn, new_x))
+    }
+
+    fn total_tokens(&self) -> std::result::Result<u64, CustomError> {
+        Ok(self.a_total_tokens)
+    }
+
+    /// y(x), capped at A: rounding x(A) up can leave the position a little past A.
+    fn tokens_sold(&self, x: u64) -> std::result::Result<u64, CustomError> {
+        Ok(self.y_of_x(x)?.min(self.a_total_tokens))
+    }
+
+    /// dx/dy = p(y(x))
+    fn spot_price(&self, x: u64) -> std::result::Result<u128, CustomError> {
+        self.price_at(self.y_of_x(x)?)
+    }
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::testing::{assert_no_round_trip_profit, RoundTrip};
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    mod logistic_params {
+        /// One whole token in raw base units (9 decimals).
+        pub const TOKEN: u64 = 1_000_000_000;
+
+        // From 28 to 2_800 lamports per whole token, steepest after 400M tokens.
+        pub const A_TOTAL_TOKENS: u64 = 800_000_000 * TOKEN;
+        pub const P_MIN_PRICE: u128 = 28_000_000_000;
+        pub const P_MAX_PRICE: u128 = 2_800_000_000_000;
+        pub const M_MIDPOINT_TOKENS: u64 = 400_000_000 * TOKEN;
+        pub const S_SCALE_TOKENS: u64 = 50_000_000 * TOKEN;
+    }
+
+    use logistic_params::*;
+
+    fn default_curve() -> LogisticBondingCurve {
+        LogisticBondingCurve {
+            a_total_tokens: A_TOTAL_TOKENS,
+            p_min_price: P_MIN_PRICE,
+            p_max_price: P_MAX_PRICE,
+            m_midpoint_tokens: M_MIDPOINT_TOKENS,
+            s_scale_tokens: S_SCALE_TOKENS,
+        }
+    }
+
+    /// Float reference: p(y) = P_min + (P_max - P_min) / (1 + e^-((y - M) / S)).
+    fn reference_price(y: f64) -> f64 {
+        let z = (y - M_MIDPOINT_TOKENS as f64) / S_SCALE_TOKENS as f64;
+        P_MIN_PRICE as f64 + (P_MAX_PRICE - P_MIN_PRICE) as f64 / (1.0 + (-z).exp())
+    }
+
+    /// Float reference: x(y) = P_min * y + (P_max - P_min) * S * (softplus(z(y)) - softplus(z(0))).
+    fn reference_deposit(y: f64) -> f64 {
+        let softplus = |z: f64| z.max(0.0) + (-z.abs()).exp().ln_1p();
+        let z = |y: f64| (y - M_MIDPOINT_TOKENS as f64) / S_SCALE_TOKENS as f64;
+        let spread = (P_MAX_PRICE - P_MIN_PRICE) as f64;
+        (P_MIN_PRICE as f64 * y
+            + spread * S_SCALE_TOKENS as f64 * (softplus(z(y)) - softplus(z(0.0))))
+            / WAD as f64
+    }
+
+    fn max_price_error() -> f64 {
+        (P_MAX_PRICE - P_MIN_PRICE) as f64 * LOGISTIC_MAX_PRICE_ERROR_PPM as f64 / 1e6
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(default_curve().validate().is_ok());
+
+        for broken in [
+            LogisticBondingCurve {
+                p_max_price: P_MIN_PRICE,
+                ..default_curve()
+            },
+            LogisticBondingCurve {
+                p_min_price: 0,
+                ..default_curve()
+            },
+            LogisticBondingCurve {
+                s_scale_tokens: S_SCALE_TOKENS + 1,
+                ..default_curve()
+            },
+            LogisticBondingCurve {
+                s_scale_tokens: 0,
+                ..default_curve()
+            },
+            LogisticBondingCurve {
+                p_max_price: u128::MAX / 2,
+                ..default_curve()
+            },
+        ] {
+            assert!(matches!(
+                broken.validate(),
+                Err(CustomError::InvalidCurveParameters)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_price_within_error_bound() {
+        let curve = default_curve();
+        let bound = max_price_error();
+
+        for i in 0..=1_000u64 {
+            let y = A_TOTAL_TOKENS / 1_000 * i;
+            let price = curve.price_at(y).unwrap() as f64;
+            let expected = reference_price(y as f64);
+            assert!(
+                (price - expected).abs() <= bound,
+                "p({}) = {}, expected {} +- {}",
+                y,
+                price,
+                expected,
+                bound
+            );
+        }
+    }
+
+    #[test]
+    fn test_deposit_within_error_bound() {
+        let curve = default_curve();
+
+        for i in 0..=100u64 {
+            let y = A_TOTAL_TOKENS / 100 * i;
+            let deposit = curve.x_of_y(y).unwrap() as f64;
+            let expected = reference_deposit(y as f64);
+            // The price error integrates over the y tokens, plus one unit of rounding.
+            let bound = max_price_error() * y as f64 / WAD as f64 + 1.0 + expected * 1e-12;
+            assert!(
+                (deposit - expected).abs() <= bound,
+                "x({}) = {}, expected {} +- {}",
+                y,
+                deposit,
+                expected,
+                bound
+            );
+        }
+    }
+
+    #[test]
+    fn test_s_shaped_price() {
+        let curve = default_curve();
+        let price = |share: u64| curve.price_at(A_TOTAL_TOKENS / 100 * share).unwrap();
+
+        // Nearly flat early: the first 20% of tokens move the price by < 2% of the range.
+        let range = P_MAX_PRICE - P_MIN_PRICE;
+        assert!(price(20) - price(0) < range / 50);
+        // Steep in the middle: 30% -> 70% covers most of the range.
+        assert!(price(70) - price(30) > range * 9 / 10);
+        // Flat again near the end.
+        assert!(price(100) - price(80) < range / 50);
+
+        // The spot price tracks the curve position.
+        let x = curve.x_of_y(M_MIDPOINT_TOKENS).unwrap();
+        let mid = curve.spot_price(x).unwrap();
+        assert!(mid.abs_diff((P_MIN_PRICE + P_MAX_PRICE) / 2) <= range / 1_000);
+    }
+
+    #[test]
+    fn test_inverse_is_exact() {
+        let curve = default_curve();
+
+        for y in [
+            0,
+            1,
+            TOKEN,
+            M_MIDPOINT_TOKENS - 7 * S_SCALE_TOKENS - 1,
+            M_MIDPOINT_TOKENS,
+            M_MIDPOINT_TOKENS + 3 * S_SCALE_TOKENS / 4 + 17,
+            A_TOTAL_TOKENS,
+        ] {
+            let x = curve.x_of_y(y).unwrap();
+            assert!(curve.y_of_x(x).unwrap() >= y, "y(x({})) < {}", y, y);
+            if x > 0 {
+                assert!(
+                    curve.y_of_x_ceil(x - 1).unwrap() <= y,
+                    "x({}) not minimal",
+                    y
+                );
+            }
+        }
+
+        for x in [
+            1,
+            LAMPORTS_PER_SOL,
+            100 * LAMPORTS_PER_SOL,
+            1_000 * LAMPORTS_PER_SOL,
+        ] {
+            let floor = curve.y_of_x(x).unwrap();
+            let ceil = curve.y_of_x_ceil(x).unwrap();
+            assert!(ceil - floor <= 1);
+            assert!(curve.x_of_y(floor).unwrap() <= x);
+        }
+    }
+
+    #[test]
+    fn test_sells_out() {
+        let curve = default_curve();
+        let (cost, x) = curve.buy_exact_output(0, A_TOTAL_TOKENS).unwrap();
+        assert_eq!(cost, x);
+        // x is rounded up, so the position may sit slightly past A, but no more is sold.
+        assert!(curve.y_of_x(x).unwrap() >= A_TOTAL_TOKENS);
+        assert_eq!(curve.tokens_sold(x).unwrap(), A_TOTAL_TOKENS);
+        assert_eq!(curve.tokens_remaining(x).unwrap(), 0);
+
+        assert!(matches!(
+            curve.buy_exact_output(x, 1),
+            Err(CustomError::InsufficientTokenSupply)
+        ));
+        assert!(matches!(
+            curve.buy_exact_input(x, LAMPORTS_PER_SOL),
+            Err(CustomError::InsufficientTokenSupply)
+        ));
+    }
+
+    #[test]
+    fn test_no_round_trip_extracts_base() {
+        assert_no_round_trip_profit(
+            &default_curve(),
+            RoundTrip {
+                seeds: 50,
+                steps: 30,
+                max_first_buy: 500 * LAMPORTS_PER_SOL,
+                max_buy: 100 * LAMPORTS_PER_SOL,
+            },
+        );
+    }
+}
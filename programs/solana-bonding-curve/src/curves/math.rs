@@ -0,0 +1,361 @@
+//! Integer-only helpers shared by the curve implementations.
+//!
+//! Fixed-point values use `WAD` (1e18) as the scaling factor, so `WAD` itself
+//! represents 1.0. Every helper returns `None` instead of wrapping or saturating,
+//! leaving it to the caller to surface the failure as `CustomError::MathOverflow`.
+
+/// Fixed-point scale: 1.0 == 1e18.
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+
+/// ln(2) scaled by `WAD`.
+pub const LN_2_WAD: u128 = 693_147_180_559_945_309;
+
+/// Basis points in 100%.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Ceiling of `numerator / denominator`, `None` when dividing by zero.
+pub fn div_ceil(numerator: u128, denominator: u128) -> Option<u128> {
+    let quotient = numerator.checked_div(denominator)?;
+    if numerator % denominator == 0 {
+        Some(quotient)
+    } else {
+        Some(quotient + 1)
+    }
+}
+
+/// Floor of `a * b / denominator` with a 256-bit intermediate product.
+///
+/// `None` when dividing by zero or when the result does not fit into u128.
+pub fn mul_div(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    match a.checked_mul(b) {
+        Some(product) => product.checked_div(denominator),
+        None => U256::full_mul(a, b).div_rem(denominator).map(|(q, _)| q),
+    }
+}
+
+/// Same as `mul_div`, rounded up.
+pub fn mul_div_ceil(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    let (quotient, remainder) = U256::full_mul(a, b).div_rem(denominator)?;
+    if remainder == 0 {
+        Some(quotient)
+    } else {
+        quotient.checked_add(1)
+    }
+}
+
+/// Minimal unsigned 256-bit integer, just enough for `mul_div` style intermediates.
+///
+/// `hi` is declared first so the derived ordering compares numerically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct U256 {
+    pub hi: u128,
+    pub lo: u128,
+}
+
+impl U256 {
+    /// The full 256-bit product of two u128 values.
+    pub fn full_mul(a: u128, b: u128) -> U256 {
+        const MASK: u128 = u64::MAX as u128;
+        let (a0, a1) = (a & MASK, a >> 64);
+        let (b0, b1) = (b & MASK, b >> 64);
+
+        let p00 = a0 * b0;
+        let p01 = a0 * b1;
+        let p10 = a1 * b0;
+        let p11 = a1 * b1;
+
+        // Middle 64-bit column plus the carry out of the low column (< 3 * 2^64).
+        let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+
+        U256 {
+            hi: p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64),
+            lo: (p00 & MASK) | ((mid & MASK) << 64),
+        }
+    }
+
+    pub const ZERO: U256 = U256 { hi: 0, lo: 0 };
+
+    pub fn from_u128(value: u128) -> U256 {
+        U256 { hi: 0, lo: value }
+    }
+
+    /// `self + other`, `None` on 256-bit overflow.
+    pub fn checked_add(self, other: U256) -> Option<U256> {
+        let (lo, carry) = self.lo.overflowing_add(other.lo);
+        Some(U256 {
+            hi: self.hi.checked_add(other.hi)?.checked_add(carry as u128)?,
+            lo,
+        })
+    }
+
+    /// `self * m`, `None` on 256-bit overflow.
+    pub fn checked_mul(self, m: u128) -> Option<U256> {
+        let low = U256::full_mul(self.lo, m);
+        let high = U256::full_mul(self.hi, m);
+        if high.hi != 0 {
+            return None;
+        }
+        Some(U256 {
+            hi: low.hi.checked_add(high.lo)?,
+            lo: low.lo,
+        })
+    }
+
+    /// Quotient and remainder of `self / d`.
+    ///
+    /// `None` when dividing by zero or when the quotient does not fit into u128.
+    pub fn div_rem(self, d: u128) -> Option<(u128, u128)> {
+        if d == 0 || self.hi >= d {
+            return None;
+        }
+        if self.hi == 0 {
+            return Some((self.lo / d, self.lo % d));
+        }
+
+        // Schoolbook binary long division; the remainder always stays below d.
+        let mut rem = self.hi;
+        let mut quotient = 0u128;
+        for i in (0..128).rev() {
+            let carry = rem >> 127;
+            rem = (rem << 1) | ((self.lo >> i) & 1);
+            if carry == 1 || rem >= d {
+                rem = rem.wrapping_sub(d);
+                quotient |= 1 << i;
+            }
+        }
+        Some((quotient, rem))
+    }
+}
+
+/// Floor of the integer square root of `n` (Newton's method).
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Start from a power of two that is guaranteed to be >= sqrt(n).
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1u128 << ((bits + 1) / 2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Computes e^(x / WAD), scaled by `WAD`, for a non-negative fixed-point exponent.
+///
+/// The exponent is reduced as x = k * ln(2) + r with 0 <= r < ln(2),
+/// e^r is evaluated with a Taylor series and the result is shifted left by k.
+///
+/// The result is never below the exact value: every term is rounded up, a
+/// truncated tail (smaller than the last term) is covered by one extra ulp and
+/// `LN_2_WAD` is rounded down, which only makes r larger.
+pub fn exp_wad(x: u128) -> Option<u128> {
+    let k = x / LN_2_WAD;
+    let r = x % LN_2_WAD;
+
+    // e^r = sum(r^n / n!), every term stays below 2 * WAD.
+    let mut term = WAD;
+    let mut sum = WAD;
+    let mut n = 1u128;
+    while term > 1 {
+        term = div_ceil(term.checked_mul(r)?, WAD * n)?;
+        sum = sum.checked_add(term)?;
+        n += 1;
+    }
+    if term == 1 {
+        sum = sum.checked_add(1)?;
+    }
+
+    if k > sum.leading_zeros() as u128 {
+        return None;
+    }
+    Some(sum << k)
+}
+
+/// Upper bound on how far `ln_wad` can fall below the exact value, in WAD ulps:
+/// at most one ulp per ln(2) multiple (k < 128) plus the rounding of the series.
+pub const LN_WAD_MAX_ERROR: u128 = 256;
+
+/// Computes ln(x / WAD), scaled by `WAD`, for a fixed-point argument x >= 1.0.
+///
+/// The argument is reduced as x = 2^k * m with 1.0 <= m < 2.0, and ln(m) is
+/// evaluated with the series ln(m) = 2 * atanh((m - 1) / (m + 1)).
+///
+/// Every step rounds down, so the result is never above the exact value and
+/// never more than `LN_WAD_MAX_ERROR` below it.
+pub fn ln_wad(x: u128) -> Option<u128> {
+    if x < WAD {
+        return None;
+    }
+
+    let k = 127 - (x / WAD).leading_zeros();
+    let m = x >> k;
+
+    // z <= 1/3, so the odd powers of z shrink by at least 9x per term.
+    let z = (m - WAD).checked_mul(WAD)? / (m + WAD);
+    let z_squared = z * z / WAD;
+
+    let mut term = z;
+    let mut sum = 0u128;
+    let mut n = 1u128;
+    while term > 0 {
+        sum += term / n;
+        term = term * z_squared / WAD;
+        n += 2;
+    }
+
+    (k as u128).checked_mul(LN_2_WAD)?.checked_add(2 * sum)
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: u128, expected: f64, rel_tolerance: f64) {
+        let diff = ((actual as f64) - expected).abs();
+        assert!(
+            diff <= expected.abs() * rel_tolerance,
+            "Expected ~{}, got {} (diff={})",
+            expected,
+            actual,
+            diff
+        );
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in [
+            0u128,
+            1,
+            2,
+            3,
+            4,
+            15,
+            16,
+            17,
+            1_000_000,
+            u64::MAX as u128,
+            u128::MAX,
+        ] {
+            let root = isqrt(n);
+            assert!(root * root <= n, "isqrt({}) = {} is too large", n, root);
+            assert!(
+                (root + 1).checked_mul(root + 1).map_or(true, |sq| sq > n),
+                "isqrt({}) = {} is too small",
+                n,
+                root
+            );
+        }
+    }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_div(6, 7, 4), Some(10));
+        assert_eq!(mul_div(1, 1, 0), None);
+
+        // The intermediate product overflows u128, the result does not.
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, 3, 6), Some(u128::MAX / 2));
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90), Some(1 << 110));
+
+        // The result itself does not fit.
+        assert_eq!(mul_div(u128::MAX, 2, 1), None);
+
+        assert_eq!(mul_div_ceil(6, 7, 4), Some(11));
+        assert_eq!(mul_div_ceil(6, 8, 4), Some(12));
+        assert_eq!(mul_div_ceil(u128::MAX, 3, 3), Some(u128::MAX));
+        assert_eq!(mul_div_ceil(u128::MAX, 2, 2), Some(u128::MAX));
+        assert_eq!(mul_div_ceil(1, 1, 0), None);
+    }
+
+    #[test]
+    fn test_u256() {
+        let product = U256::full_mul(u128::MAX, u128::MAX);
+        assert_eq!(
+            product,
+            U256 {
+                hi: u128::MAX - 1,
+                lo: 1
+            }
+        );
+
+        let scaled = U256::full_mul(1 << 100, 1 << 100)
+            .checked_mul(1 << 20)
+            .unwrap();
+        assert_eq!(scaled, U256 { hi: 1 << 92, lo: 0 });
+        assert_eq!(U256::full_mul(u128::MAX, u128::MAX).checked_mul(2), None);
+
+        assert_eq!(U256 { hi: 0, lo: 17 }.div_rem(5), Some((3, 2)));
+        assert_eq!(U256 { hi: 1, lo: 0 }.div_rem(1), None);
+        assert_eq!(U256 { hi: 1, lo: 6 }.div_rem(2), Some((1 << 127 | 3, 0)));
+
+        let max_lo = U256::from_u128(u128::MAX);
+        assert_eq!(
+            max_lo.checked_add(U256::from_u128(1)),
+            Some(U256 { hi: 1, lo: 0 })
+        );
+        assert_eq!(
+            U256 {
+                hi: u128::MAX,
+                lo: u128::MAX
+            }
+            .checked_add(U256::from_u128(1)),
+            None
+        );
+        assert!(U256 { hi: 1, lo: 0 } > max_lo);
+        assert!(U256::ZERO < U256::from_u128(1));
+    }
+
+    #[test]
+    fn test_exp_wad() {
+        assert_eq!(exp_wad(0), Some(WAD));
+        for x in [0.001_f64, 0.5, 1.0, 2.0, 10.0, 40.0] {
+            let result = exp_wad((x * WAD as f64) as u128).unwrap();
+            assert_close(result, x.exp() * WAD as f64, 1e-12);
+        }
+
+        // e^100 does not fit into a WAD-scaled u128.
+        assert_eq!(exp_wad(100 * WAD), None);
+    }
+
+    #[test]
+    fn test_ln_wad() {
+        assert_eq!(ln_wad(WAD), Some(0));
+        assert_eq!(ln_wad(WAD - 1), None);
+        for x in [1.001_f64, 1.5, 2.0, std::f64::consts::E, 10.0, 1e6, 1e15] {
+            let result = ln_wad((x * WAD as f64) as u128).unwrap();
+            assert_close(result, x.ln() * WAD as f64, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_rounding_direction() {
+        // e^1 = 2.718281828459045235360287..., ln(10) = 2.302585092994045684017991...
+        let e_wad = 2_718_281_828_459_045_235;
+        let ln_10_wad = 2_302_585_092_994_045_684;
+
+        let exp = exp_wad(WAD).unwrap();
+        assert!(exp > e_wad && exp - e_wad < 100, "exp_wad(1) = {}", exp);
+
+        let ln = ln_wad(10 * WAD).unwrap();
+        assert!(
+            ln <= ln_10_wad && ln_10_wad - ln <= LN_WAD_MAX_ERROR,
+            "ln_wad(10) = {}",
+            ln
+        );
+    }
+
+    #[test]
+    fn test_exp_ln_roundtrip() {
+        for x in [WAD, 3 * WAD / 2, 7 * WAD, 1_000 * WAD] {
+            let back = exp_wad(ln_wad(x).unwrap()).unwrap();
+            assert_close(back, x as f64, 1e-15);
+        }
+    }
+}
@@ -1,2 +1,387 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::math::{mul_div, mul_div_ceil, BPS_DENOMINATOR, WAD};
+use crate::errors::CustomError;
+
+pub mod constant_product;
+pub mod exponential;
+pub mod linear;
+pub mod logistic;
+pub mod math;
+pub mod piecewise;
 pub mod smooth;
+pub mod solver;
+
+pub use constant_product::*;
+pub use exponential::*;
+pub use linear::*;
+pub use logistic::*;
+pub use piecewise::*;
 pub use smooth::*;
+
+//==============================================================================
+/// BondingCurveTrait defines the core bonding curve functions.
+///
+/// Here, each method accepts:
+/// - `old_x`: the current base_tokens in the pool (before this new operation),
+/// - and either `base_in`, `tokens_out`, etc.,
+/// returning either the minted/burned tokens or the base tokens
+/// along with the updated pool balance `new_x` if needed.
+///
+/// This way, the curve does NOT store `x` internally.
+///
+/// Rounding policy: every operation rounds in the pool's favor. Trades are priced
+/// from the exact curve position y(x) bracketed by floor(y(x)) and ceil(y(x)):
+/// - tokens paid out (buys) are floor(y(new_x)) - ceil(y(old_x)),
+/// - tokens taken in (sells) are ceil(y(old_x)) - floor(y(new_x)),
+/// - base amounts solved from a target y are rounded up (more in, less out).
+///
+/// Hence no sequence of trades can take more base out of the pool than was put in
+/// for the tokens it returns.
+pub trait BondingCurveTrait: CurvePosition {
+    /// Buys with exact base_tokens in, returning the exact number of minted tokens (Δy)
+    /// plus the new x in the pool.
+    fn buy_exact_input(
+        &self,
+        old_x: u64,
+        base_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError>;
+
+    /// Buys an exact number of tokens out (tokens_out), returning the exact base_tokens required,
+    /// plus the new x in the pool.
+    fn buy_exact_output(
+        &self,
+        old_x: u64,
+        tokens_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError>;
+
+    /// Sells an exact number of tokens in, returning the exact base_tokens out,
+    /// plus the new x in the pool.
+    fn sell_exact_input(
+        &self,
+        old_x: u64,
+        tokens_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError>;
+
+    /// Sells enough tokens to receive exactly `base_out` from the curve.
+    /// Returns the number of "pool tokens" that must be burned,
+    /// plus the new x in the pool.
+    fn sell_exact_output(
+        &self,
+        old_x: u64,
+        base_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError>;
+
+    /// The most tokens (raw units) the curve can ever sell.
+    fn total_tokens(&self) -> std::result::Result<u64, CustomError>;
+
+    /// Tokens (raw units) sold once `x` base_tokens are in the pool, i.e. y(x).
+    fn tokens_sold(&self, x: u64) -> std::result::Result<u64, CustomError>;
+
+    /// Marginal price dx/dy at `x`: base_tokens per raw token, scaled by `math::WAD`.
+    fn spot_price(&self, x: u64) -> std::result::Result<u128, CustomError>;
+
+    /// Tokens (raw units) still for sale on the curve at `x`.
+    fn tokens_remaining(&self, x: u64) -> std::result::Result<u64, CustomError> {
+        self.total_tokens()?
+            .checked_sub(self.tokens_sold(x)?)
+            .ok_or(CustomError::MathOverflow)
+    }
+
+    /// The most tokens (raw units) a buy at `x` can still receive, i.e. the largest
+    /// `tokens_out` that `buy_exact_output` accepts: total tokens minus ceil(y(x)).
+    fn max_tokens_out(&self, x: u64) -> std::result::Result<u64, CustomError> {
+        Ok(self.total_tokens()?.saturating_sub(self.y_of_x_ceil(x)?))
+    }
+
+    /// Market cap at `x` in base_tokens: the spot price times the tokens sold so far.
+    fn market_cap(&self, x: u64) -> std::result::Result<u128, CustomError> {
+        mul_div(self.spot_price(x)?, self.tokens_sold(x)? as u128, WAD)
+            .ok_or(CustomError::MathOverflow)
+    }
+
+    /// How far a trade moving the pool from `old_x` to `new_x` moves the spot price,
+    /// in basis points of the price at `old_x`, rounded up.
+    fn price_impact_bps(&self, old_x: u64, new_x: u64) -> std::result::Result<u64, CustomError> {
+        let price_before = self.spot_price(old_x)?;
+        let price_after = self.spot_price(new_x)?;
+        let impact = mul_div_ceil(
+            price_before.abs_diff(price_after),
+            BPS_DENOMINATOR as u128,
+            price_before,
+        )
+        .ok_or(CustomError::MathOverflow)?;
+        u64::try_from(impact).map_err(|_| CustomError::MathOverflow)
+    }
+
+    /// Fully diluted valuation at `x` in base_tokens: the spot price times `total_supply` (raw units).
+    fn fully_diluted_valuation(
+        &self,
+        x: u64,
+        total_supply: u64,
+    ) -> std::result::Result<u128, CustomError> {
+        mul_div(self.spot_price(x)?, total_supply as u128, WAD).ok_or(CustomError::MathOverflow)
+    }
+}
+
+//==============================================================================
+/// Position primitives of a curve: the tokens out at a given base deposit and back.
+///
+/// The trading methods of `BondingCurveTrait` are built from these, and
+/// `PiecewiseBondingCurve` chains them across its segments.
+pub trait CurvePosition {
+    /// Tokens (raw units) out once `x_val` base_tokens are in the pool, rounded down.
+    fn y_of_x(&self, x_val: u64) -> std::result::Result<u64, CustomError>;
+
+    /// Same as `y_of_x`, rounded up.
+    fn y_of_x_ceil(&self, x_val: u64) -> std::result::Result<u64, CustomError>;
+
+    /// The base_tokens that must be in the pool for `y_val` tokens to be out, rounded up.
+    fn x_of_y(&self, y_val: u64) -> std::result::Result<u64, CustomError>;
+}
+
+//==============================================================================
+/// The curve family used to price a token, stored on-chain in `XyberCore`.
+///
+/// Every variant wraps a concrete curve and all pricing goes through
+/// `BondingCurveTrait`, so instruction handlers never depend on a specific family.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub enum BondingCurveType {
+    /// y(x) = A - K / (C + x)
+    Smooth(SmoothBondingCurve),
+    /// Price grows linearly with the number of tokens sold.
+    Linear(LinearBondingCurve),
+    /// Price grows exponentially with the number of tokens sold.
+    Exponential(ExponentialBondingCurve),
+    /// Constant-product pool over virtual base and token reserves.
+    ConstantProduct(ConstantProductBondingCurve),
+    /// Price follows an S-shape: flat early, steep in the middle, flat near the end.
+    Logistic(LogisticBondingCurve),
+    /// Several of the curves above chained at breakpoints in base deposited.
+    Piecewise(PiecewiseBondingCurve),
+}
+
+impl BondingCurveType {
+    /// Borsh size: 1 byte for the variant tag plus the largest variant.
+    pub const LEN: usize = 1 + max_len(&[
+        SmoothBondingCurve::LEN,
+        LinearBondingCurve::LEN,
+        ExponentialBondingCurve::LEN,
+        ConstantProductBondingCurve::LEN,
+        LogisticBondingCurve::LEN,
+        PiecewiseBondingCurve::LEN,
+    ]);
+
+    /// Rejects parameters that make the curve inconsistent (see each curve's `validate`).
+    pub fn validate(&self) -> std::result::Result<(), CustomError> {
+        match self {
+            BondingCurveType::Smooth(curve) => curve.validate(),
+            BondingCurveType::Linear(curve) => curve.validate(),
+            BondingCurveType::Exponential(curve) => curve.validate(),
+            BondingCurveType::ConstantProduct(curve) => curve.validate(),
+            BondingCurveType::Logistic(curve) => curve.validate(),
+            BondingCurveType::Piecewise(curve) => curve.validate(),
+        }
+    }
+
+    fn curve(&self) -> &dyn BondingCurveTrait {
+        match self {
+            BondingCurveType::Smooth(curve) => curve,
+            BondingCurveType::Linear(curve) => curve,
+            BondingCurveType::Exponential(curve) => curve,
+            BondingCurveType::ConstantProduct(curve) => curve,
+            BondingCurveType::Logistic(curve) => curve,
+            BondingCurveType::Piecewise(curve) => curve,
+        }
+    }
+}
+
+pub(crate) const fn max_len(lens: &[usize]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < lens.len() {
+        if lens[i] > max {
+            max = lens[i];
+        }
+        i += 1;
+    }
+    max
+}
+
+impl CurvePosition for BondingCurveType {
+    fn y_of_x(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        self.curve().y_of_x(x_val)
+    }
+
+    fn y_of_x_ceil(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        self.curve().y_of_x_ceil(x_val)
+    }
+
+    fn x_of_y(&self, y_val: u64) -> std::result::Result<u64, CustomError> {
+        self.curve().x_of_y(y_val)
+    }
+}
+
+impl BondingCurveTrait for BondingCurveType {
+    fn buy_exact_input(
+        &self,
+        old_x: u64,
+        base_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        self.curve().buy_exact_input(old_x, base_in)
+    }
+
+    fn buy_exact_output(
+        &self,
+        old_x: u64,
+        tokens_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        self.curve().buy_exact_output(old_x, tokens_out)
+    }
+
+    fn sell_exact_input(
+        &self,
+        old_x: u64,
+        tokens_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        self.curve().sell_exact_input(old_x, tokens_in)
+    }
+
+    fn sell_exact_output(
+        &self,
+        old_x: u64,
+        base_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        self.curve().sell_exact_output(old_x, base_out)
+    }
+
+    fn total_tokens(&self) -> std::result::Result<u64, CustomError> {
+        self.curve().total_tokens()
+    }
+
+    fn tokens_sold(&self, x: u64) -> std::result::Result<u64, CustomError> {
+        self.curve().tokens_sold(x)
+    }
+
+    fn spot_price(&self, x: u64) -> std::result::Result<u128, CustomError> {
+        self.curve().spot_price(x)
+    }
+}
+
+//==============================================================================
+/// Helpers shared by the curve tests.
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::BondingCurveTrait;
+
+    /// Minimal xorshift generator so the property tests stay deterministic.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// A random amount in 1..=max whose magnitude is spread over many orders.
+        fn amount(&mut self, max: u64) -> u64 {
+            let scale = 10_u64.pow((self.next() % 19) as u32);
+            (self.next() % scale.min(max)) + 1
+        }
+    }
+
+    /// Shape of the random trade sequences run by `assert_no_round_trip_profit`.
+    pub struct RoundTrip {
+        // Number of independent sequences (one per seed) and trades in each
+        pub seeds: u64,
+        pub steps: usize,
+        // Largest base amount of the other holders' opening buy and of the trader's buys
+        pub max_first_buy: u64,
+        pub max_buy: u64,
+    }
+
+    /// Runs random buy/sell sequences against `curve` and asserts that the pool always backs
+    /// every outstanding token and that no trader gets more base out than they put in.
+    pub fn assert_no_round_trip_profit(curve: &dyn BondingCurveTrait, round_trip: RoundTrip) {
+        const MAX_TOKENS_OUT: u64 = 100_000_000 * 1_000_000_000;
+
+        for seed in 1..=round_trip.seeds {
+            let mut rng = XorShift(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+            // Other holders bought first and keep their tokens.
+            let (others_tokens, mut x) = curve
+                .buy_exact_input(0, rng.amount(round_trip.max_first_buy))
+                .unwrap();
+
+            let mut tokens: u64 = 0;
+            let mut base_in: u128 = 0;
+            let mut base_out: u128 = 0;
+
+            for _ in 0..round_trip.steps {
+                match rng.next() % 4 {
+                    0 => {
+                        let amount = rng.amount(round_trip.max_buy);
+                        if let Ok((minted, new_x)) = curve.buy_exact_input(x, amount) {
+                            tokens += minted;
+                            base_in += amount as u128;
+                            x = new_x;
+                        }
+                    }
+                    1 => {
+                        let amount = rng.amount(MAX_TOKENS_OUT);
+                        if let Ok((paid, new_x)) = curve.buy_exact_output(x, amount) {
+                            tokens += amount;
+                            base_in += paid as u128;
+                            x = new_x;
+                        }
+                    }
+                    2 if tokens > 0 => {
+                        let amount = rng.amount(tokens);
+                        let (received, new_x) = curve.sell_exact_input(x, amount).unwrap();
+                        tokens -= amount;
+                        base_out += received as u128;
+                        x = new_x;
+                    }
+                    3 if x > 0 => {
+                        let amount = rng.amount(x);
+                        let (burned, new_x) = curve.sell_exact_output(x, amount).unwrap();
+                        // The trader cannot burn tokens they do not hold.
+                        if burned <= tokens {
+                            tokens -= burned;
+                            base_out += amount as u128;
+                            x = new_x;
+                        }
+                    }
+                    _ => {}
+                }
+
+                // Every outstanding token is still backed by the pool.
+                assert!(
+                    others_tokens + tokens <= curve.y_of_x_ceil(x).unwrap(),
+                    "seed {}: {} tokens outstanding but y(x) = {}",
+                    seed,
+                    others_tokens + tokens,
+                    curve.y_of_x_ceil(x).unwrap()
+                );
+            }
+
+            // Close the position.
+            if tokens > 0 {
+                let (received, new_x) = curve.sell_exact_input(x, tokens).unwrap();
+                base_out += received as u128;
+                x = new_x;
+            }
+
+            assert!(
+                base_out <= base_in,
+                "seed {}: extracted {} base_tokens for a deposit of {}",
+                seed,
+                base_out,
+                base_in
+            );
+            assert!(curve.y_of_x_ceil(x).unwrap() >= others_tokens);
+        }
+    }
+}
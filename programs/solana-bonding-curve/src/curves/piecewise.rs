@@ -0,0 +1,675 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::math::{mul_div, mul_div_ceil, WAD};
+use crate::curves::{
+    max_len, BondingCurveTrait, ConstantProductBondingCurve, CurvePosition,
+    ExponentialBondingCurve, LinearBondingCurve, LogisticBondingCurve, SmoothBondingCurve,
+};
+use crate::errors::CustomError;
+
+/// The most segments a piecewise curve may have, so it fits a fixed account size.
+pub const MAX_SEGMENTS: usize = 4;
+
+//==============================================================================
+/// A fixed-price segment, e.g. a presale before the curve starts moving.
+///
+/// y(x) = x / P
+/// - P = price (base_tokens per raw token, scaled by `math::WAD`)
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct FlatSegment {
+    /// Price in base_tokens per raw token, scaled by WAD
+    pub price: u128,
+}
+
+impl FlatSegment {
+    pub const LEN: usize = 16; // price
+
+    /// Checks that the price is non-zero.
+    pub fn validate(&self) -> std::result::Result<(), CustomError> {
+        if self.price == 0 {
+            return Err(CustomError::InvalidCurveParameters);
+        }
+        Ok(())
+    }
+}
+
+impl CurvePosition for FlatSegment {
+    /// y(x) = x * WAD / P (rounded down)
+    fn y_of_x(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let y = mul_div(x_val as u128, WAD, self.price).ok_or(CustomError::MathOverflow)?;
+        u64::try_from(y).map_err(|_| CustomError::MathOverflow)
+    }
+
+    /// Same as `y_of_x`, rounded up.
+    fn y_of_x_ceil(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let y = mul_div_ceil(x_val as u128, WAD, self.price).ok_or(CustomError::MathOverflow)?;
+        u64::try_from(y).map_err(|_| CustomError::MathOverflow)
+    }
+
+    /// x(y) = y * P / WAD (rounded up)
+    fn x_of_y(&self, y_val: u64) -> std::result::Result<u64, CustomError> {
+        let x = mul_div_ceil(y_val as u128, self.price, WAD).ok_or(CustomError::MathOverflow)?;
+        u64::try_from(x).map_err(|_| CustomError::MathOverflow)
+    }
+}
+
+//==============================================================================
+/// The curve used within one segment, driven by the base deposited since the
+/// segment's breakpoint.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub enum SegmentCurve {
+    /// Fixed price, only allowed before the last segment.
+    Flat(FlatSegment),
+    Smooth(SmoothBondingCurve),
+    Linear(LinearBondingCurve),
+    Exponential(ExponentialBondingCurve),
+    ConstantProduct(ConstantProductBondingCurve),
+    Logistic(LogisticBondingCurve),
+}
+
+impl SegmentCurve {
+    /// Borsh size: 1 byte for the variant tag plus the largest variant.
+    pub const LEN: usize = 1 + max_len(&[
+        FlatSegment::LEN,
+        SmoothBondingCurve::LEN,
+        LinearBondingCurve::LEN,
+        ExponentialBondingCurve::LEN,
+        ConstantProductBondingCurve::LEN,
+        LogisticBondingCurve::LEN,
+    ]);
+
+    pub fn validate(&self) -> std::result::Result<(), CustomError> {
+        match self {
+            SegmentCurve::Flat(curve) => curve.validate(),
+            SegmentCurve::Smooth(curve) => curve.validate(),
+            SegmentCurve::Linear(curve) => curve.validate(),
+            SegmentCurve::Exponential(curve) => curve.validate(),
+            SegmentCurve::ConstantProduct(curve) => curve.validate(),
+            SegmentCurve::Logistic(curve) => curve.validate(),
+        }
+    }
+
+    fn position(&self) -> &dyn CurvePosition {
+        match self {
+            SegmentCurve::Flat(curve) => curve,
+            SegmentCurve::Smooth(curve) => curve,
+            SegmentCurve::Linear(curve) => curve,
+            SegmentCurve::Exponential(curve) => curve,
+            SegmentCurve::ConstantProduct(curve) => curve,
+            SegmentCurve::Logistic(curve) => curve,
+        }
+    }
+
+    /// The most tokens the segment can sell on its own, `None` for an unbounded flat price.
+    fn max_tokens(&self) -> std::result::Result<Option<u64>, CustomError> {
+        match self {
+            SegmentCurve::Flat(_) => Ok(None),
+            SegmentCurve::Smooth(curve) => curve.total_tokens().map(Some),
+            SegmentCurve::Linear(curve) => curve.total_tokens().map(Some),
+            SegmentCurve::Exponential(curve) => curve.total_tokens().map(Some),
+            SegmentCurve::ConstantProduct(curve) => curve.total_tokens().map(Some),
+            SegmentCurve::Logistic(curve) => curve.total_tokens().map(Some),
+        }
+    }
+
+    fn spot_price(&self, x: u64) -> std::result::Result<u128, CustomError> {
+        match self {
+            SegmentCurve::Flat(curve) => Ok(curve.price),
+            SegmentCurve::Smooth(curve) => curve.spot_price(x),
+            SegmentCurve::Linear(curve) => curve.spot_price(x),
+            SegmentCurve::Exponential(curve) => curve.spot_price(x),
+            SegmentCurve::ConstantProduct(curve) => curve.spot_price(x),
+            SegmentCurve::Logistic(curve) => curve.spot_price(x),
+        }
+    }
+}
+
+/// One segment of a piecewise curve, active from `x_start` base_tokens deposited
+/// up to the next segment's `x_start`.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct PiecewiseSegment {
+    /// Breakpoint in base_tokens deposited where this segment starts
+    pub x_start: u64,
+    /// The curve of this segment, evaluated at x - x_start
+    pub curve: SegmentCurve,
+}
+
+impl PiecewiseSegment {
+    pub const LEN: usize = 8 // x_start
+        + SegmentCurve::LEN; // curve
+}
+
+//==============================================================================
+/// A bonding curve made of several segments, e.g. a flat presale price, then a
+/// smooth hyperbolic curve, then a steep tail.
+///
+/// Y(x) = T_i + y_i(x - X_i) for X_i <= x < X_(i+1)
+/// - X_i = breakpoint of segment i in base_tokens deposited (X_0 = 0)
+/// - y_i = the curve of segment i, each starting at y_i(0) = 0
+/// - T_i = tokens sold by all earlier segments, each counted as ceil(y_j(X_(j+1) - X_j))
+///
+/// Rounding each finished segment up keeps Y non-decreasing across breakpoints, and
+/// every trade still brackets Y with floor/ceil exactly like a single curve does, so
+/// a trade spanning several segments is priced in the pool's favor as a whole.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct PiecewiseBondingCurve {
+    /// Segments ordered by breakpoint, the first one starting at 0
+    pub segments: Vec<PiecewiseSegment>,
+}
+
+impl PiecewiseBondingCurve {
+    pub const LEN: usize = 4 // segments vec length
+        + MAX_SEGMENTS * PiecewiseSegment::LEN; // segments
+
+    /// Checks the breakpoints, every segment curve and that the curve ends on a bounded
+    /// segment, and that no finished segment sells more than its own curve allows.
+    pub fn validate(&self) -> std::result::Result<(), CustomError> {
+        let last = match self.segments.last() {
+            Some(last) if self.segments.len() <= MAX_SEGMENTS => last,
+            _ => return Err(CustomError::InvalidCurveSegments),
+        };
+        if self.segments[0].x_start != 0 || matches!(last.curve, SegmentCurve::Flat(_)) {
+            return Err(CustomError::InvalidCurveSegments);
+        }
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            segment.curve.validate()?;
+
+            if let Some(width) = self.segment_width(i)? {
+                let sold = segment
+                    .curve
+                    .position()
+                    .y_of_x_ceil(width)
+                    .map_err(|_| CustomError::InvalidCurveSegments)?;
+                if matches!(segment.curve.max_tokens()?, Some(max) if sold > max) {
+                    return Err(CustomError::InvalidCurveSegments);
+                }
+            }
+        }
+
+        self.total_tokens()
+            .map_err(|_| CustomError::InvalidCurveSegments)?;
+        Ok(())
+    }
+
+    /// Base_tokens spanned by segment `index`, `None` for the open-ended last segment.
+    fn segment_width(&self, index: usize) -> std::result::Result<Option<u64>, CustomError> {
+        match self.segments.get(index + 1) {
+            Some(next) => next
+                .x_start
+                .checked_sub(self.segments[index].x_start)
+                .filter(|width| *width > 0)
+                .map(Some)
+                .ok_or(CustomError::InvalidCurveSegments),
+            None => Ok(None),
+        }
+    }
+
+    /// Tokens sold by a finished segment, ceil(y_i(width)).
+    fn segment_tokens(&self, index: usize, width: u64) -> std::result::Result<u64, CustomError> {
+        self.segments[index].curve.position().y_of_x_ceil(width)
+    }
+
+    /// Finds the segment holding `x_val`, returning its index, the tokens sold by the
+    /// earlier segments (T_i) and x relative to the segment's breakpoint.
+    fn locate(&self, x_val: u64) -> std::result::Result<(usize, u64, u64), CustomError> {
+        let mut tokens_before: u64 = 0;
+        for (i, segment) in self.segments.iter().enumerate() {
+            let local_x = x_val
+                .checked_sub(segment.x_start)
+                .ok_or(CustomError::InvalidCurveSegments)?;
+            match self.segment_width(i)? {
+                Some(width) if local_x >= width => {
+                    tokens_before = tokens_before
+                        .checked_add(self.segment_tokens(i, width)?)
+                        .ok_or(CustomError::MathOverflow)?;
+                }
+                _ => return Ok((i, tokens_before, local_x)),
+            }
+        }
+        Err(CustomError::InvalidCurveSegments)
+    }
+}
+
+impl CurvePosition for PiecewiseBondingCurve {
+    /// Y(x) = T_i + y_i(x - X_i) (rounded down)
+    fn y_of_x(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let (i, tokens_before, local_x) = self.locate(x_val)?;
+        tokens_before
+            .checked_add(self.segments[i].curve.position().y_of_x(local_x)?)
+            .ok_or(CustomError::MathOverflow)
+    }
+
+    /// Same as `y_of_x`, rounded up.
+    fn y_of_x_ceil(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let (i, tokens_before, local_x) = self.locate(x_val)?;
+        tokens_before
+            .checked_add(self.segments[i].curve.position().y_of_x_ceil(local_x)?)
+            .ok_or(CustomError::MathOverflow)
+    }
+
+    /// Calculates the base_tokens that must be in the pool for `y_val` tokens to be minted:
+    /// x(y) = X_i + x_i(y - T_i) in the first segment whose tokens reach past y (rounded up)
+    ///
+    /// Returns an error if y exceeds the tokens of the last segment.
+    fn x_of_y(&self, y_val: u64) -> std::result::Result<u64, CustomError> {
+        let mut tokens_before: u64 = 0;
+        for (i, segment) in self.segments.iter().enumerate() {
+            let position = segment.curve.position();
+            let local_y = y_val
+                .checked_sub(tokens_before)
+                .ok_or(CustomError::MathOverflow)?;
+
+            let local_x = match self.segment_width(i)? {
+                Some(width) => {
+                    let segment_tokens = self.segment_tokens(i, width)?;
+                    if local_y >= segment_tokens {
+                        tokens_before = tokens_before
+                            .checked_add(segment_tokens)
+                            .ok_or(CustomError::MathOverflow)?;
+                        continue;
+                    }
+                    // y_i(width) covers local_y, so the breakpoint bounds the solution.
+                    position.x_of_y(local_y)?.min(width)
+                }
+                None => position.x_of_y(local_y)?,
+            };
+
+            return segment
+                .x_start
+                .checked_add(local_x)
+                .ok_or(CustomError::MathOverflow);
+        }
+        Err(CustomError::InvalidCurveSegments)
+    }
+}
+
+impl BondingCurveTrait for PiecewiseBondingCurve {
+    /// Buys with exact base_tokens in, returning the exact number of minted tokens (Δy),
+    /// plus the updated x.
+    fn buy_exact_input(
+        &self,
+        old_x: u64,
+        base_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let new_x = old_x
+            .checked_add(base_in)
+            .ok_or(CustomError::MathOverflow)?;
+
+        // minted = floor(Y(new_x)) - ceil(Y(old_x)), zero when less than one raw token
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+        if new_y > self.total_tokens()? {
+            return Err(CustomError::InsufficientTokenSupply);
+        }
+
+        let minted = new_y.saturating_sub(old_y);
+
+        Ok((minted, new_x))
+    }
+
+    /// Buys an exact number of tokens out (tokens_out), returning the exact base_tokens required,
+    /// plus the updated x.
+    fn buy_exact_output(
+        &self,
+        old_x: u64,
+        tokens_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let old_y = self.y_of_x_ceil(old_x)?;
+
+        let new_y = old_y
+            .checked_add(tokens_out)
+            .ok_or(CustomError::MathOverflow)?;
+
+        let x_prime = self.x_of_y(new_y)?;
+
+        let base_in = x_prime
+            .checked_sub(old_x)
+            .ok_or(CustomError::MathOverflow)?;
+
+        Ok((base_in, x_prime))
+    }
+
+    /// Sells an exact number of tokens in, returning the exact base_tokens out,
+    /// plus the updated x.
+    fn sell_exact_input(
+        &self,
+        old_x: u64,
+        tokens_in: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let old_y = self.y_of_x_ceil(old_x)?;
+
+        let new_y = old_y
+            .checked_sub(tokens_in)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+
+        // The pool never moves above old_x
+        let x_prime = self.x_of_y(new_y)?.min(old_x);
+
+        let base_out = old_x
+            .checked_sub(x_prime)
+            .ok_or(CustomError::MathOverflow)?;
+
+        Ok((base_out, x_prime))
+    }
+
+    /// Sells enough tokens to receive exactly `base_out` from the curve.
+    /// Returns the number of "pool tokens" that must be burned,
+    /// plus the updated x.
+    fn sell_exact_output(
+        &self,
+        old_x: u64,
+        base_out: u64,
+    ) -> std::result::Result<(u64, u64), CustomError> {
+        let new_x = old_x
+            .checked_sub(base_out)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+
+        // tokens_to_burn = ceil(Y(old_x)) - floor(Y(new_x))
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+
+        let tokens_to_burn = old_y.checked_sub(new_y).ok_or(CustomError::MathOverflow)?;
+
+        Ok((tokens_to_burn, new_x))
+    }
+
+    /// T_last + the tokens of the last segment.
+    fn total_tokens(&self) -> std::result::Result<u64, CustomError> {
+        let last = self
+            .segments
+            .len()
+            .checked_sub(1)
+            .ok_or(CustomError::InvalidCurveSegments)?;
+        let (_, tokens_before, _) = self.locate(self.segments[last].x_start)?;
+        let last_tokens = self.segments[last]
+            .curve
+            .max_tokens()?
+            .ok_or(CustomError::InvalidCurveSegments)?;
+
+        tokens_before
+            .checked_add(last_tokens)
+            .ok_or(CustomError::MathOverflow)
+    }
+
+    /// Y(x), capped at the total: rounding the sell-out deposit up can leave the position
+    /// a little past the last segment.
+    fn tokens_sold(&self, x: u64) -> std::result::Result<u64, CustomError> {
+        Ok(self.y_of_x(x)?.min(self.total_tokens()?))
+    }
+
+    /// The spot price of the segment holding x.
+    fn spot_price(&self, x: u64) -> std::result::Result<u128, CustomError> {
+        let (i, _, local_x) = self.locate(x)?;
+        self.segments[i].curve.spot_price(local_x)
+    }
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::testing::{assert_no_round_trip_profit, RoundTrip};
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    mod piecewise_params {
+        use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+        /// One whole token in raw base units (9 decimals).
+        pub const TOKEN: u64 = 1_000_000_000;
+
+        // Presale: 20 lamports per whole token for the first SOL, below the curve's start.
+        pub const PRESALE_PRICE: u128 = 20 * crate::curves::math::WAD / (TOKEN as u128);
+        pub const SMOOTH_START: u64 = LAMPORTS_PER_SOL;
+
+        // Then the pump.fun curve for the next 50 SOL...
+        pub const A_TOTAL_TOKENS: u64 = 1_073_000_191 * TOKEN;
+        pub const K_VIRTUAL_POOL_OFFSET: u128 =
+            32_190_005_730 * (LAMPORTS_PER_SOL as u128) * (TOKEN as u128);
+        pub const C_BONDING_SCALE_FACTOR: u64 = 30 * LAMPORTS_PER_SOL;
+        pub const TAIL_START: u64 = 51 * LAMPORTS_PER_SOL;
+
+        // ...and a steep linear tail for the last 100M tokens.
+        pub const TAIL_TOKENS: u64 = 100_000_000 * TOKEN;
+        pub const TAIL_OFFSET: u64 = 400_000_000 * TOKEN;
+        pub const TAIL_DIVISOR: u128 = 1_000_000 * (TOKEN as u128) * (TOKEN as u128);
+    }
+
+    use piecewise_params::*;
+
+    fn flat() -> SegmentCurve {
+        SegmentCurve::Flat(FlatSegment {
+            price: PRESALE_PRICE,
+        })
+    }
+
+    fn smooth() -> SegmentCurve {
+        SegmentCurve::Smooth(SmoothBondingCurve {
+            a_total_tokens: A_TOTAL_TOKENS,
+            k_virtual_pool_offset: K_VIRTUAL_POOL_OFFSET,
+            c_bonding_scale_factor: C_BONDING_SCALE_FACTOR,
+        })
+    }
+
+    fn tail() -> SegmentCurve {
+        SegmentCurve::Linear(LinearBondingCurve {
+            a_total_tokens: TAIL_TOKENS,
+            b_virtual_token_offset: TAIL_OFFSET,
+            m_price_divisor: TAIL_DIVISOR,
+        })
+    }
+
+    /// Flat presale -> smooth -> linear tail.
+    fn default_curve() -> PiecewiseBondingCurve {
+        PiecewiseBondingCurve {
+            segments: vec![
+                PiecewiseSegment {
+                    x_start: 0,
+                    curve: flat(),
+                },
+                PiecewiseSegment {
+                    x_start: SMOOTH_START,
+                    curve: smooth(),
+                },
+                PiecewiseSegment {
+                    x_start: TAIL_START,
+                    curve: tail(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(default_curve().validate().is_ok());
+
+        let invalid = |segments: Vec<PiecewiseSegment>| {
+            matches!(
+                PiecewiseBondingCurve { segments }.validate(),
+                Err(CustomError::InvalidCurveSegments)
+            )
+        };
+        let segment = |x_start, curve| PiecewiseSegment { x_start, curve };
+
+        assert!(invalid(vec![]));
+        // The first segment must start at 0.
+        assert!(invalid(vec![segment(1, smooth())]));
+        // Breakpoints must strictly increase.
+        assert!(invalid(vec![segment(0, flat()), segment(0, smooth())]));
+        // The last segment must be bounded.
+        assert!(invalid(vec![
+            segment(0, smooth()),
+            segment(SMOOTH_START, flat())
+        ]));
+        // A finished segment cannot sell more than its own curve.
+        assert!(invalid(vec![
+            segment(0, tail()),
+            segment(u64::MAX / 2, smooth())
+        ]));
+        assert!(invalid(
+            (0..=MAX_SEGMENTS as u64)
+                .map(|i| segment(i * SMOOTH_START, smooth()))
+                .collect()
+        ));
+
+        // Each segment curve is validated on its own.
+        let mut curve = default_curve();
+        curve.segments[0].curve = SegmentCurve::Flat(FlatSegment { price: 0 });
+        assert!(matches!(
+            curve.validate(),
+            Err(CustomError::InvalidCurveParameters)
+        ));
+    }
+
+    #[test]
+    fn test_len_fits_max_segments() {
+        let curve = PiecewiseBondingCurve {
+            segments: (0..MAX_SEGMENTS as u64)
+                .map(|i| PiecewiseSegment {
+                    x_start: i * SMOOTH_START,
+                    curve: smooth(),
+                })
+                .collect(),
+        };
+        assert!(curve.try_to_vec().unwrap().len() <= PiecewiseBondingCurve::LEN);
+    }
+
+    #[test]
+    fn test_segments_chain_at_breakpoints() {
+        let curve = default_curve();
+        let SegmentCurve::Smooth(smooth_curve) = smooth() else {
+            unreachable!()
+        };
+
+        // The presale sells at a fixed price...
+        assert_eq!(
+            curve.y_of_x(SMOOTH_START / 2).unwrap(),
+            25_000_000_000_000_000
+        );
+        let presale_tokens = curve.y_of_x(SMOOTH_START).unwrap();
+        assert_eq!(presale_tokens, 50_000_000_000_000_000);
+
+        // ...then the smooth curve continues from the presale's tokens.
+        let x = SMOOTH_START + 20 * LAMPORTS_PER_SOL;
+        assert_eq!(
+            curve.y_of_x(x).unwrap(),
+            presale_tokens + smooth_curve.y_of_x(20 * LAMPORTS_PER_SOL).unwrap()
+        );
+
+        // Y never decreases across a breakpoint.
+        for breakpoint in [SMOOTH_START, TAIL_START] {
+            assert!(
+                curve.y_of_x_ceil(breakpoint - 1).unwrap() <= curve.y_of_x(breakpoint).unwrap()
+            );
+            assert!(
+                curve.spot_price(breakpoint - 1).unwrap() <= curve.spot_price(breakpoint).unwrap()
+            );
+        }
+        assert_eq!(curve.spot_price(0).unwrap(), PRESALE_PRICE);
+
+        let tail_start_tokens = curve.y_of_x(TAIL_START).unwrap();
+        assert_eq!(
+            curve.total_tokens().unwrap(),
+            tail_start_tokens + TAIL_TOKENS
+        );
+
+        // x_of_y inverts y_of_x in every segment.
+        for x in [
+            1,
+            SMOOTH_START - 1,
+            SMOOTH_START,
+            TAIL_START - 1,
+            TAIL_START,
+            TAIL_START + LAMPORTS_PER_SOL,
+        ] {
+            let y = curve.y_of_x(x).unwrap();
+            let x_back = curve.x_of_y(y).unwrap();
+            assert!(x_back <= x, "x_of_y(y_of_x({})) = {}", x, x_back);
+            assert!(curve.y_of_x_ceil(x_back).unwrap() >= y);
+        }
+    }
+
+    #[test]
+    fn test_single_trade_spans_segments() {
+        let curve = default_curve();
+        let base_in = TAIL_START + LAMPORTS_PER_SOL;
+
+        // One buy through all three segments mints Y(x), like the steps it spans.
+        let (minted, new_x) = curve.buy_exact_input(0, base_in).unwrap();
+        assert_eq!(new_x, base_in);
+        assert_eq!(minted, curve.y_of_x(base_in).unwrap());
+
+        let mut x = 0;
+        let mut minted_in_steps = 0;
+        for step in [
+            SMOOTH_START / 2,
+            SMOOTH_START,
+            TAIL_START - SMOOTH_START / 2,
+            base_in,
+        ] {
+            let (tokens, new_x) = curve.buy_exact_input(x, step - x).unwrap();
+            minted_in_steps += tokens;
+            x = new_x;
+        }
+        assert!(minted_in_steps <= minted);
+        assert!(
+            minted - minted_in_steps <= 3,
+            "Lost {} raw tokens",
+            minted - minted_in_steps
+        );
+
+        // Buying the same tokens back as an exact output costs at most the original deposit.
+        let (cost, x_prime) = curve.buy_exact_output(0, minted).unwrap();
+        assert!(cost <= base_in);
+        assert!(curve.y_of_x(x_prime).unwrap() >= minted);
+
+        // Selling everything back crosses both breakpoints and never pays out more.
+        let (base_out, x_after) = curve.sell_exact_input(new_x, minted).unwrap();
+        assert!(base_out <= base_in);
+        assert!(
+            base_in - base_out <= 1,
+            "Lost {} lamports",
+            base_in - base_out
+        );
+        assert!(x_after <= 1);
+
+        // Withdrawing the whole deposit burns at least every minted token.
+        let (burned, x_after) = curve.sell_exact_output(new_x, base_in).unwrap();
+        assert!(burned >= minted);
+        assert_eq!(x_after, 0);
+    }
+
+    #[test]
+    fn test_sells_out_on_the_last_segment() {
+        let curve = default_curve();
+        let total = curve.total_tokens().unwrap();
+
+        let (cost, x) = curve.buy_exact_output(0, total).unwrap();
+        assert_eq!(curve.tokens_remaining(x).unwrap(), 0);
+        assert!(cost > TAIL_START);
+
+        assert!(matches!(
+            curve.buy_exact_output(0, total + 1),
+            Err(CustomError::InsufficientTokenSupply)
+        ));
+        assert!(matches!(
+            curve.buy_exact_input(x, LAMPORTS_PER_SOL),
+            Err(CustomError::InsufficientTokenSupply)
+        ));
+    }
+
+    #[test]
+    fn test_no_round_trip_extracts_base() {
+        // Other holders sometimes still buy in the presale.
+        assert_no_round_trip_profit(
+            &default_curve(),
+            RoundTrip {
+                seeds: 100,
+                steps: 50,
+                max_first_buy: TAIL_START,
+                max_buy: 20 * LAMPORTS_PER_SOL,
+            },
+        );
+    }
+}
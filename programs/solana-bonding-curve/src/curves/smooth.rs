@@ -1,58 +1,15 @@
 use anchor_lang::prelude::*;
 
+use crate::curves::math::{div_ceil, U256, WAD};
+use crate::curves::{BondingCurveTrait, CurvePosition};
 use crate::errors::CustomError;
 
-//==============================================================================
-/// BondingCurveTrait defines the core bonding curve functions.
-///
-/// Here, each method accepts:
-/// - `old_x`: the current base_tokens in the pool (before this new operation),
-/// - and either `base_in`, `tokens_out`, etc.,
-/// returning either the minted/burned tokens or the base tokens
-/// along with the updated pool balance `new_x` if needed.
-///
-/// This way, the curve does NOT store `x` internally.
-pub trait BondingCurveTrait {
-    /// Buys with exact base_tokens in, returning the exact number of minted tokens (Δy)
-    /// plus the new x in the pool.
-    fn buy_exact_input(
-        &self,
-        old_x: u64,
-        base_in: u64,
-    ) -> std::result::Result<(u64, u64), CustomError>;
-
-    /// Buys an exact number of tokens out (tokens_out), returning the exact base_tokens required,
-    /// plus the new x in the pool.
-    fn _buy_exact_output(
-        &self,
-        old_x: u64,
-        tokens_out: u64,
-    ) -> std::result::Result<(u64, u64), CustomError>;
-
-    /// Sells an exact number of tokens in, returning the exact base_tokens out,
-    /// plus the new x in the pool.
-    fn sell_exact_input(
-        &self,
-        old_x: u64,
-        tokens_in: u64,
-    ) -> std::result::Result<(u64, u64), CustomError>;
-
-    /// Sells enough tokens to receive exactly `base_out` from the curve.
-    /// Returns the number of "pool tokens" that must be burned,
-    /// plus the new x in the pool.
-    fn _sell_exact_output(
-        &self,
-        old_x: u64,
-        base_out: u64,
-    ) -> std::result::Result<(u64, u64), CustomError>;
-}
-
 //==============================================================================
 /// A smooth bonding curve referencing the base asset (e.g., SOL, XBT) deposited.
 ///
 /// Formula: y(x) = A - (K / (C + x))
-/// - A = asymptotic max token supply (in integer "token units")
-/// - K = (token * lamport), controlling how quickly we approach A
+/// - A = asymptotic max token supply (in raw token base units, i.e. scaled by 10^decimals)
+/// - K = (raw token * base unit), controlling how quickly we approach A
 /// - C = virtual pool offset (in base_tokens)
 ///
 /// NOTE: We no longer store `x_total_base_deposit` inside the struct.
@@ -61,25 +18,48 @@ pub trait BondingCurveTrait {
 pub struct SmoothBondingCurve {
     /// Asymptotic total token supply (in "raw" tokens)
     pub a_total_tokens: u64,
-    /// Controls how quickly we approach A (token * lamport)
+    /// Controls how quickly we approach A (raw token * base unit)
     pub k_virtual_pool_offset: u128,
     /// Virtual pool offset (in base_tokens)
     pub c_bonding_scale_factor: u64,
 }
 
 impl SmoothBondingCurve {
-    /// Calculates the total minted tokens at `x_val` base_tokens in the pool:
-    /// y(x) = A - (K / (C + x)) (all integer math)
-    fn y_of_x(&self, x_val: u64) -> u64 {
-        let denom = self
-            .c_bonding_scale_factor
-            .checked_add(x_val)
-            .unwrap_or(u64::MAX); // If overflow occurs, fallback to maximum value.
-        let k_over_denom = self
-            .k_virtual_pool_offset
-            .checked_div(denom as u128)
-            .unwrap_or(0);
-        self.a_total_tokens.saturating_sub(k_over_denom as u64)
+    pub const LEN: usize = 8 // a_total_tokens
+        + 16 // k_virtual_pool_offset
+        + 8; // c_bonding_scale_factor
+
+    /// Checks that the curve starts at y(0) = 0, i.e. K == A * C.
+    ///
+    /// With K / C < A the curve would start mid-way with tokens nobody paid for,
+    /// with K / C > A the first buys would mint nothing.
+    pub fn validate(&self) -> std::result::Result<(), CustomError> {
+        if self.a_total_tokens == 0
+            || self.k_virtual_pool_offset == 0
+            || self.c_bonding_scale_factor == 0
+        {
+            return Err(CustomError::InvalidCurveParameters);
+        }
+
+        let a_times_c = (self.a_total_tokens as u128) * (self.c_bonding_scale_factor as u128);
+        match self.k_virtual_pool_offset.cmp(&a_times_c) {
+            std::cmp::Ordering::Less => Err(CustomError::CurveStartsAboveZero),
+            std::cmp::Ordering::Greater => Err(CustomError::CurveOffsetExceedsTotalTokens),
+            std::cmp::Ordering::Equal => Ok(()),
+        }
+    }
+
+    /// C + x, widened so it cannot overflow.
+    fn denominator(&self, x_val: u64) -> u128 {
+        (self.c_bonding_scale_factor as u128) + (x_val as u128)
+    }
+
+    /// A - value, failing instead of saturating when value does not fit below A.
+    fn a_minus(&self, value: u128) -> std::result::Result<u64, CustomError> {
+        let value = u64::try_from(value).map_err(|_| CustomError::MathOverflow)?;
+        self.a_total_tokens
+            .checked_sub(value)
+            .ok_or(CustomError::MathOverflow)
     }
 
     /// Computes the new x (x') for a target y = new_y.
@@ -89,27 +69,54 @@ impl SmoothBondingCurve {
     /// => (C + x') = K / (A - new_y)
     /// => x' = (K / (A - new_y)) - C
     ///
-    /// Returns an error if new_y ≥ A or if computations result in an arithmetic error.
-    fn solve_for_x_prime(&self, new_y: u128) -> std::result::Result<u128, CustomError> {
-        if new_y >= self.a_total_tokens as u128 {
+    /// The division rounds up, so the exact y(x') never falls short of new_y even
+    /// though a single base unit buys many raw token units.
+    ///
+    /// Returns an error if new_y ≥ A or if x' does not fit into u64.
+    fn solve_for_x_prime(&self, new_y: u64) -> std::result::Result<u64, CustomError> {
+        if new_y >= self.a_total_tokens {
             return Err(CustomError::InsufficientTokenSupply);
         }
-        let a_minus_new_y = (self.a_total_tokens as u128)
-            .checked_sub(new_y)
-            .ok_or(CustomError::InsufficientTokenSupply)?;
+        let a_minus_new_y = (self.a_total_tokens - new_y) as u128;
 
-        let big_val = self
-            .k_virtual_pool_offset
-            .checked_div(a_minus_new_y)
-            .ok_or(CustomError::MathOverflow)?;
+        let big_val =
+            div_ceil(self.k_virtual_pool_offset, a_minus_new_y).ok_or(CustomError::MathOverflow)?;
 
         if big_val < self.c_bonding_scale_factor as u128 {
             return Err(CustomError::MathOverflow);
         }
 
-        big_val
+        let x_prime = big_val
             .checked_sub(self.c_bonding_scale_factor as u128)
-            .ok_or(CustomError::MathOverflow)
+            .ok_or(CustomError::MathOverflow)?;
+        u64::try_from(x_prime).map_err(|_| CustomError::MathOverflow)
+    }
+}
+
+impl CurvePosition for SmoothBondingCurve {
+    /// Calculates the total minted tokens at `x_val` base_tokens in the pool:
+    /// y(x) = A - (K / (C + x)), rounded down (all integer math)
+    ///
+    /// C + x is evaluated in u128, so it cannot overflow; a curve whose K / (C + x)
+    /// exceeds A (i.e. one that would go below zero) is a `MathOverflow` error.
+    fn y_of_x(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let k_over_denom = div_ceil(self.k_virtual_pool_offset, self.denominator(x_val))
+            .ok_or(CustomError::MathOverflow)?;
+        self.a_minus(k_over_denom)
+    }
+
+    /// Same as `y_of_x`, rounded up.
+    fn y_of_x_ceil(&self, x_val: u64) -> std::result::Result<u64, CustomError> {
+        let k_over_denom = self
+            .k_virtual_pool_offset
+            .checked_div(self.denominator(x_val))
+            .ok_or(CustomError::MathOverflow)?;
+        self.a_minus(k_over_denom)
+    }
+
+    /// Same as `solve_for_x_prime`.
+    fn x_of_y(&self, y_val: u64) -> std::result::Result<u64, CustomError> {
+        self.solve_for_x_prime(y_val)
     }
 }
 
@@ -126,38 +133,36 @@ impl BondingCurveTrait for SmoothBondingCurve {
             .checked_add(base_in)
             .ok_or(CustomError::MathOverflow)?;
 
-        let old_y = self.y_of_x(old_x);
-        let new_y = self.y_of_x(new_x);
-
-        let minted = new_y.checked_sub(old_y).ok_or(CustomError::MathOverflow)?;
+        // minted = floor(y(new_x)) - ceil(y(old_x)), zero when less than one raw token
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+        let minted = new_y.saturating_sub(old_y);
 
         Ok((minted, new_x))
     }
 
     /// Buys an exact number of tokens out (tokens_out), returning the exact base_tokens required,
     /// plus the updated x.
-    fn _buy_exact_output(
+    fn buy_exact_output(
         &self,
         old_x: u64,
         tokens_out: u64,
     ) -> std::result::Result<(u64, u64), CustomError> {
-        // old_y = y_of_x(old_x)
-        let old_y = self.y_of_x(old_x);
-
-        // new_y = old_y + tokens_out
+        // new_y = ceil(y(old_x)) + tokens_out
+        let old_y = self.y_of_x_ceil(old_x)?;
         let new_y = old_y
             .checked_add(tokens_out)
             .ok_or(CustomError::MathOverflow)?;
 
-        // x' = solve_for_x_prime(new_y)
-        let x_prime = self.solve_for_x_prime(new_y as u128)?;
+        // x' = solve_for_x_prime(new_y), rounded up
+        let x_prime = self.solve_for_x_prime(new_y)?;
 
         // base_in = x_prime - old_x
         let base_in = x_prime
-            .checked_sub(old_x as u128)
+            .checked_sub(old_x)
             .ok_or(CustomError::MathOverflow)?;
 
-        Ok((base_in as u64, x_prime as u64))
+        Ok((base_in, x_prime))
     }
 
     /// Sells an exact number of tokens in, returning the exact base_tokens out,
@@ -167,50 +172,61 @@ impl BondingCurveTrait for SmoothBondingCurve {
         old_x: u64,
         tokens_in: u64,
     ) -> std::result::Result<(u64, u64), CustomError> {
-        let old_y = self.y_of_x(old_x);
-
-        // new_y = old_y - tokens_in
+        // new_y = ceil(y(old_x)) - tokens_in
+        let old_y = self.y_of_x_ceil(old_x)?;
         let new_y = old_y
             .checked_sub(tokens_in)
             .ok_or(CustomError::InsufficientTokenSupply)?;
 
-        // x_prime = solve_for_x_prime(new_y)
-        let x_prime = self.solve_for_x_prime(new_y as u128)?;
+        // x' = solve_for_x_prime(new_y), rounded up; the pool never moves above old_x
+        let x_prime = self.solve_for_x_prime(new_y)?.min(old_x);
 
         // base_out = old_x - x_prime
-        let base_out = (old_x as u128)
+        let base_out = old_x
             .checked_sub(x_prime)
             .ok_or(CustomError::MathOverflow)?;
 
-        Ok((base_out as u64, x_prime as u64))
+        Ok((base_out, x_prime))
     }
 
     /// Sells enough tokens to receive exactly `base_out` from the curve.
     /// Returns the number of "pool tokens" that must be burned,
     /// plus the updated x.
-    fn _sell_exact_output(
+    fn sell_exact_output(
         &self,
         old_x: u64,
         base_out: u64,
     ) -> std::result::Result<(u64, u64), CustomError> {
-        let old_y = self.y_of_x(old_x);
+        // new_x = old_x - base_out
+        let new_x = old_x
+            .checked_sub(base_out)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
 
-        if (base_out as u128) > (old_x as u128) {
-            return Err(CustomError::InsufficientTokenSupply);
-        }
+        // tokens_to_burn = ceil(y(old_x)) - floor(y(new_x))
+        let old_y = self.y_of_x_ceil(old_x)?;
+        let new_y = self.y_of_x(new_x)?;
+        let tokens_to_burn = old_y.checked_sub(new_y).ok_or(CustomError::MathOverflow)?;
 
-        // new_x = old_x - base_out
-        let new_x = (old_x as u128)
-            .checked_sub(base_out as u128)
-            .ok_or(CustomError::MathOverflow)?;
+        Ok((tokens_to_burn, new_x))
+    }
 
-        // new_y = y_of_x(new_x)
-        let new_y = self.y_of_x(new_x as u64);
+    fn total_tokens(&self) -> std::result::Result<u64, CustomError> {
+        Ok(self.a_total_tokens)
+    }
 
-        // tokens_to_burn = old_y - new_y
-        let tokens_to_burn = old_y.checked_sub(new_y).ok_or(CustomError::MathOverflow)?;
+    fn tokens_sold(&self, x: u64) -> std::result::Result<u64, CustomError> {
+        self.y_of_x(x)
+    }
+
+    /// dx/dy = (C + x)^2 / K
+    fn spot_price(&self, x: u64) -> std::result::Result<u128, CustomError> {
+        let c_plus_x = self.denominator(x);
 
-        Ok((tokens_to_burn as u64, new_x as u64))
+        U256::full_mul(c_plus_x, c_plus_x)
+            .checked_mul(WAD)
+            .and_then(|scaled| scaled.div_rem(self.k_virtual_pool_offset))
+            .map(|(price, _)| price)
+            .ok_or(CustomError::MathOverflow)
     }
 }
 
@@ -219,19 +235,24 @@ impl BondingCurveTrait for SmoothBondingCurve {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::curves::testing::{assert_no_round_trip_profit, RoundTrip};
     use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
 
     mod xyber_params {
         use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
 
+        /// One whole token in raw base units (9 decimals).
+        pub const TOKEN: u64 = 1_000_000_000;
+
         // Xyber
         // pub const A_TOTAL_TOKENS: u64 = 975_000_000_000;
         // pub const K_VIRTUAL_POOL_OFFSET: u128 = 904_507_500_000_000 * (LAMPORTS_PER_SOL as u128);
         // pub const C_BONDING_SCALE_FACTOR: u64 = 927_000 * LAMPORTS_PER_SOL;
 
         // Solana
-        pub const A_TOTAL_TOKENS: u64 = 1_073_000_191;
-        pub const K_VIRTUAL_POOL_OFFSET: u128 = 32_190_005_730 * (LAMPORTS_PER_SOL as u128);
+        pub const A_TOTAL_TOKENS: u64 = 1_073_000_191 * TOKEN;
+        pub const K_VIRTUAL_POOL_OFFSET: u128 =
+            32_190_005_730 * (LAMPORTS_PER_SOL as u128) * (TOKEN as u128);
         pub const C_BONDING_SCALE_FACTOR: u64 = 30 * LAMPORTS_PER_SOL;
     }
 
@@ -256,7 +277,7 @@ mod tests {
 
         // We'll do a rough check on minted tokens
         assert!(
-            (357_000..358_000).contains(&minted),
+            (357_000 * xyber_params::TOKEN..358_000 * xyber_params::TOKEN).contains(&minted),
             "Minted tokens out of expected range: {}",
             minted
         );
@@ -268,9 +289,9 @@ mod tests {
 
         // Suppose old_x is 0 at the beginning.
         let old_x = 0;
-        let tokens_out = 10_000 as u64;
+        let tokens_out = 10_000 * xyber_params::TOKEN;
 
-        let (lamports_required, new_x) = curve._buy_exact_output(old_x, tokens_out).unwrap();
+        let (lamports_required, new_x) = curve.buy_exact_output(old_x, tokens_out).unwrap();
         println!("Tokens to buy: {}", tokens_out);
         println!("Lamports required: {}", lamports_required);
         println!("New x (pool deposit) after buy: {}", new_x);
@@ -280,11 +301,15 @@ mod tests {
             "Lamports required should be greater than 0"
         );
 
-        // Let's confirm y(new_x) == tokens_out
-        let real_new_y = curve.y_of_x(new_x);
-        assert_eq!(
-            real_new_y, tokens_out,
-            "The curve state should reflect the exact number of tokens bought"
+        // Let's confirm y(new_x) covers tokens_out, and one base unit less would not
+        let real_new_y = curve.y_of_x(new_x).unwrap();
+        assert!(
+            real_new_y >= tokens_out,
+            "The curve state should reflect at least the number of tokens bought"
+        );
+        assert!(
+            curve.y_of_x(new_x - 1).unwrap() <= tokens_out,
+            "The buyer should not be charged more than needed"
         );
     }
 
@@ -369,7 +394,7 @@ mod tests {
         // Let's request exactly half of the current base_in pool
         let base_out = x / 2;
 
-        let (tokens_burned, after_x) = curve._sell_exact_output(x, base_out).unwrap();
+        let (tokens_burned, after_x) = curve.sell_exact_output(x, base_out).unwrap();
         println!("tokens_burned: {}", tokens_burned);
         println!("new pool x after sell: {}", after_x);
 
@@ -381,8 +406,8 @@ mod tests {
         );
 
         // Check the real burn
-        let old_y = curve.y_of_x(x);
-        let new_y = curve.y_of_x(after_x);
+        let old_y = curve.y_of_x_ceil(x).unwrap();
+        let new_y = curve.y_of_x(after_x).unwrap();
         let real_burn = old_y.saturating_sub(new_y);
         assert_eq!(
             tokens_burned, real_burn,
@@ -426,8 +451,8 @@ mod tests {
         // (B) Buy Exact Output -> Sell Exact Input
         // We'll reset the pool to empty
         let mut x2 = 0;
-        let tokens_out_b = 50_000;
-        let (lamports_in_b, x2_after_buy) = curve._buy_exact_output(x2, tokens_out_b).unwrap();
+        let tokens_out_b = 50_000 * xyber_params::TOKEN;
+        let (lamports_in_b, x2_after_buy) = curve.buy_exact_output(x2, tokens_out_b).unwrap();
         x2 = x2_after_buy;
         println!(
             "(B) Bought {} tokens (exact output) for {} base_tokens, new x={}",
@@ -441,7 +466,8 @@ mod tests {
             tokens_out_b, lamports_out_b, x2
         );
 
-        let tolerance_b = 0;
+        // Rounding x' up may leave a single base unit of dust in the pool.
+        let tolerance_b = 1;
         let diff_b = lamports_out_b as i64 - lamports_in_b as i64;
         assert!(
             diff_b.abs() <= tolerance_b,
@@ -452,7 +478,7 @@ mod tests {
         );
     }
 
-    /// Estimates the marginal price (dX/dY) in float, used for logging/tracing price growth.
+    /// Estimates the marginal price (dX/dY) in float, used as a reference for `spot_price`.
     ///
     /// For y(x) = A - (K / (C + x)), the local slope dX/dY is:
     ///     dX/dY = (C + x)^2 / K
@@ -463,6 +489,69 @@ mod tests {
         (denom * denom) / k
     }
 
+    #[test]
+    fn test_spot_price_and_market_cap() {
+        let curve = default_curve();
+
+        for x in [0, LAMPORTS_PER_SOL, 50 * LAMPORTS_PER_SOL] {
+            let price = curve.spot_price(x).unwrap() as f64 / WAD as f64;
+            let expected = approximate_price(&curve, x);
+            assert!(
+                ((price - expected) / expected).abs() < 1e-9,
+                "spot_price({}) = {}, expected ~{}",
+                x,
+                price,
+                expected
+            );
+        }
+
+        assert_eq!(curve.market_cap(0).unwrap(), 0);
+        assert_eq!(curve.tokens_remaining(0).unwrap(), curve.a_total_tokens);
+
+        let (minted, x) = curve.buy_exact_input(0, 10 * LAMPORTS_PER_SOL).unwrap();
+        assert_eq!(
+            curve.tokens_remaining(x).unwrap(),
+            curve.a_total_tokens - minted
+        );
+
+        // The price only grows, so every token sold so far is worth more than it cost.
+        let market_cap = curve.market_cap(x).unwrap();
+        assert!(
+            market_cap > x as u128,
+            "Unexpected market cap: {}",
+            market_cap
+        );
+
+        let fdv = curve
+            .fully_diluted_valuation(x, xyber_params::A_TOTAL_TOKENS)
+            .unwrap();
+        assert!(fdv > market_cap);
+    }
+
+    #[test]
+    fn test_price_impact_bps() {
+        let curve = default_curve();
+        let c = xyber_params::C_BONDING_SCALE_FACTOR;
+
+        // The price is (C + x)^2 / K: doubling C + x quadruples it.
+        let impact = curve.price_impact_bps(0, c).unwrap();
+        assert!(
+            (30_000..=30_001).contains(&impact),
+            "Unexpected buy impact: {}",
+            impact
+        );
+        let impact = curve.price_impact_bps(c, 0).unwrap();
+        assert!(
+            (7_500..=7_501).contains(&impact),
+            "Unexpected sell impact: {}",
+            impact
+        );
+
+        // ~6.67 bps is rounded up, and a trade that does not move the pool has no impact.
+        assert_eq!(curve.price_impact_bps(0, c / 3_000).unwrap(), 7);
+        assert_eq!(curve.price_impact_bps(c, c).unwrap(), 0);
+    }
+
     #[test]
     fn test_buy_until_70k_liquidity() {
         let curve = default_curve();
@@ -490,7 +579,7 @@ mod tests {
             x = new_x;
 
             let total_pool_sol = (x as f64) / (LAMPORTS_PER_SOL as f64);
-            let new_price = approximate_price(&curve, x);
+            let new_price = curve.spot_price(x).unwrap() as f64 / WAD as f64;
 
             println!(
                 "Iteration {}: +{} Xyber => minted {} tokens, approx price={:.2e}, total Xyber={:.1}",
@@ -504,8 +593,7 @@ mod tests {
         let final_usd = final_sol * sol_price_usd;
 
         // Calculate how many tokens have been minted (y_of_x) and how many are left
-        let minted_so_far = curve.y_of_x(x);
-        let tokens_left = curve.a_total_tokens.saturating_sub(minted_so_far);
+        let tokens_left = curve.tokens_remaining(x).unwrap();
 
         println!(
             "Reached target liquidity.\n  - Final Xyber in pool: {:.2}\n  - Final USD value: ${:.2}\n",
@@ -522,4 +610,108 @@ mod tests {
             final_usd
         );
     }
+
+    #[test]
+    fn test_validate() {
+        assert!(default_curve().validate().is_ok());
+
+        // K / C < A: y(0) > 0, the curve would start mid-way.
+        let mut curve = default_curve();
+        curve.a_total_tokens += 1;
+        assert!(matches!(
+            curve.validate(),
+            Err(CustomError::CurveStartsAboveZero)
+        ));
+
+        // K / C > A: the first buys would mint nothing.
+        let mut curve = default_curve();
+        curve.a_total_tokens -= 1;
+        assert!(matches!(
+            curve.validate(),
+            Err(CustomError::CurveOffsetExceedsTotalTokens)
+        ));
+
+        let mut curve = default_curve();
+        curve.c_bonding_scale_factor = 0;
+        assert!(matches!(
+            curve.validate(),
+            Err(CustomError::InvalidCurveParameters)
+        ));
+    }
+
+    #[test]
+    fn test_extreme_inputs_error_instead_of_saturating() {
+        let curve = default_curve();
+
+        // The whole u64 range of x is a valid point on the curve.
+        let (minted, new_x) = curve.buy_exact_input(0, u64::MAX).unwrap();
+        assert_eq!(new_x, u64::MAX);
+        assert!(minted < xyber_params::A_TOTAL_TOKENS);
+
+        // ...but the pool itself cannot grow past it.
+        assert!(matches!(
+            curve.buy_exact_input(u64::MAX, 1),
+            Err(CustomError::MathOverflow)
+        ));
+
+        // Almost the whole supply needs an x' far beyond u64, which used to be truncated.
+        assert!(matches!(
+            curve.buy_exact_output(0, xyber_params::A_TOTAL_TOKENS - 1),
+            Err(CustomError::MathOverflow)
+        ));
+        assert!(matches!(
+            curve.buy_exact_output(0, xyber_params::A_TOTAL_TOKENS),
+            Err(CustomError::InsufficientTokenSupply)
+        ));
+
+        assert!(matches!(
+            curve.sell_exact_output(LAMPORTS_PER_SOL, LAMPORTS_PER_SOL + 1),
+            Err(CustomError::InsufficientTokenSupply)
+        ));
+
+        // An unvalidated curve that dips below zero used to report y = 0.
+        let below_zero = SmoothBondingCurve {
+            a_total_tokens: 1,
+            k_virtual_pool_offset: 100,
+            c_bonding_scale_factor: 1,
+        };
+        assert!(matches!(
+            below_zero.y_of_x(0),
+            Err(CustomError::MathOverflow)
+        ));
+
+        // K / (C + x) beyond u64 must not be truncated either.
+        let huge_k = SmoothBondingCurve {
+            a_total_tokens: u64::MAX,
+            k_virtual_pool_offset: u128::MAX,
+            c_bonding_scale_factor: 1,
+        };
+        assert!(matches!(huge_k.y_of_x(0), Err(CustomError::MathOverflow)));
+        assert!(matches!(
+            huge_k.tokens_sold(0),
+            Err(CustomError::MathOverflow)
+        ));
+
+        let zero_offset = SmoothBondingCurve {
+            c_bonding_scale_factor: 0,
+            ..default_curve()
+        };
+        assert!(matches!(
+            zero_offset.y_of_x_ceil(0),
+            Err(CustomError::MathOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_no_round_trip_extracts_base() {
+        assert_no_round_trip_profit(
+            &default_curve(),
+            RoundTrip {
+                seeds: 200,
+                steps: 50,
+                max_first_buy: 500 * LAMPORTS_PER_SOL,
+                max_buy: 100 * LAMPORTS_PER_SOL,
+            },
+        );
+    }
 }
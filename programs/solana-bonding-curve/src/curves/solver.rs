@@ -0,0 +1,297 @@
+//! Solves the smooth curve parameters A, K and C from launch economics.
+//!
+//! Off-chain helper behind the `solve-curve` binary: instead of tuning A/K/C by
+//! hand, pick how much of the supply sells before graduation, the graduation
+//! threshold and the starting price, and derive the curve that hits all three.
+
+use crate::curves::math::{mul_div, BPS_DENOMINATOR, U256, WAD};
+use crate::curves::{BondingCurveTrait, CurvePosition, SmoothBondingCurve};
+use crate::errors::CustomError;
+
+/// How far (in parts per million) the solved curve may miss each target after
+/// rounding A and C to integers.
+pub const SOLVER_TOLERANCE_PPM: u128 = 100;
+
+/// The launch targets the smooth curve is solved for.
+#[derive(Debug, Clone)]
+pub struct LaunchEconomics {
+    /// Total token supply (whole tokens)
+    pub total_supply: u64,
+    /// Decimals of the launched token
+    pub token_decimals: u8,
+    /// Share of the total supply sold when the token graduates (bps)
+    pub sold_before_graduation_bps: u64,
+    /// Base deposited at graduation (whole base tokens), as in `XyberCore::grad_threshold`
+    pub grad_threshold: u64,
+    /// Decimals of the base token
+    pub base_decimals: u8,
+    /// Starting price in base_tokens per raw token, scaled by WAD (see `spot_price`)
+    pub start_price: u128,
+}
+
+/// A solved curve together with what it actually does at the launch targets.
+#[derive(Debug, Clone)]
+pub struct SolvedCurve {
+    pub curve: SmoothBondingCurve,
+    /// Tokens (raw units) sold once the graduation threshold is deposited
+    pub tokens_sold_at_graduation: u64,
+    /// Spot price at x = 0, scaled by WAD
+    pub start_price: u128,
+}
+
+/// Solves y(x) = A - K / (C + x) for the launch economics.
+///
+/// With T the tokens sold at graduation, G the graduation threshold and p the
+/// starting price (all raw units):
+/// - y(0) = 0        => K = A * C
+/// - y'(0)^-1 = p    => C / A = p
+/// - y(G) = T        => A * G / (C + G) = T  =>  A = T * G / (G - T * p)
+///
+/// A solution only exists while the starting price is below the average price
+/// G / T, and it must not sell more than the total supply. The rounded curve is
+/// verified against `SmoothBondingCurve` before it is returned.
+pub fn solve_smooth_curve(
+    economics: &LaunchEconomics,
+) -> std::result::Result<SolvedCurve, CustomError> {
+    if economics.sold_before_graduation_bps == 0
+        || economics.sold_before_graduation_bps > BPS_DENOMINATOR
+        || economics.start_price == 0
+    {
+        return Err(CustomError::InvalidCurveParameters);
+    }
+
+    let raw_total_supply = raw_amount(economics.total_supply, economics.token_decimals)?;
+    let raw_grad_threshold = raw_amount(economics.grad_threshold, economics.base_decimals)
+        .map_err(|_| CustomError::InvalidGradThreshold)?;
+    if raw_total_supply == 0 {
+        return Err(CustomError::InvalidCurveParameters);
+    }
+    if raw_grad_threshold == 0 {
+        return Err(CustomError::InvalidGradThreshold);
+    }
+
+    let tokens_at_graduation = mul_div(
+        raw_total_supply as u128,
+        economics.sold_before_graduation_bps as u128,
+        BPS_DENOMINATOR as u128,
+    )
+    .ok_or(CustomError::MathOverflow)?;
+
+    // A = T * G * WAD / (G * WAD - T * p)
+    let g_wad = (raw_grad_threshold as u128)
+        .checked_mul(WAD)
+        .ok_or(CustomError::MathOverflow)?;
+    let t_times_p =
+        mul_div(tokens_at_graduation, economics.start_price, 1).ok_or(CustomError::MathOverflow)?;
+    let denominator = g_wad
+        .checked_sub(t_times_p)
+        .filter(|denominator| *denominator > 0)
+        .ok_or(CustomError::InvalidCurveParameters)?;
+    let a_total_tokens = round_div(
+        U256::full_mul(tokens_at_graduation, raw_grad_threshold as u128)
+            .checked_mul(WAD)
+            .ok_or(CustomError::MathOverflow)?,
+        denominator,
+    )?;
+    if a_total_tokens > raw_total_supply as u128 {
+        return Err(CustomError::CurveExceedsTotalSupply);
+    }
+
+    // C = p * A, K = A * C so that the curve starts exactly at y(0) = 0.
+    let c_bonding_scale_factor =
+        round_div(U256::full_mul(economics.start_price, a_total_tokens), WAD)?;
+    let k_virtual_pool_offset = a_total_tokens
+        .checked_mul(c_bonding_scale_factor)
+        .ok_or(CustomError::MathOverflow)?;
+
+    let curve = SmoothBondingCurve {
+        a_total_tokens: u64::try_from(a_total_tokens).map_err(|_| CustomError::MathOverflow)?,
+        k_virtual_pool_offset,
+        c_bonding_scale_factor: u64::try_from(c_bonding_scale_factor)
+            .map_err(|_| CustomError::MathOverflow)?,
+    };
+
+    let solved = verify(&curve, raw_grad_threshold)?;
+    if !within_tolerance(
+        solved.tokens_sold_at_graduation as u128,
+        tokens_at_graduation,
+    ) || !within_tolerance(solved.start_price, economics.start_price)
+    {
+        return Err(CustomError::InvalidCurveParameters);
+    }
+
+    Ok(solved)
+}
+
+/// Runs the curve through `SmoothBondingCurve` itself: it must validate and a single
+/// buy of the whole graduation threshold must go through.
+pub fn verify(
+    curve: &SmoothBondingCurve,
+    raw_grad_threshold: u64,
+) -> std::result::Result<SolvedCurve, CustomError> {
+    curve.validate()?;
+
+    let (minted, _) = curve.buy_exact_input(0, raw_grad_threshold)?;
+    if minted != curve.y_of_x(raw_grad_threshold)? {
+        return Err(CustomError::InvalidCurveParameters);
+    }
+
+    Ok(SolvedCurve {
+        curve: curve.clone(),
+        tokens_sold_at_graduation: minted,
+        start_price: curve.spot_price(0)?,
+    })
+}
+
+/// `amount * 10^decimals`
+fn raw_amount(amount: u64, decimals: u8) -> std::result::Result<u64, CustomError> {
+    10_u64
+        .checked_pow(decimals as u32)
+        .and_then(|scale| amount.checked_mul(scale))
+        .ok_or(CustomError::MathOverflow)
+}
+
+/// `value / denominator`, rounded to the nearest integer.
+fn round_div(value: U256, denominator: u128) -> std::result::Result<u128, CustomError> {
+    let (quotient, remainder) = value
+        .div_rem(denominator)
+        .ok_or(CustomError::MathOverflow)?;
+    if remainder >= denominator - remainder {
+        quotient.checked_add(1).ok_or(CustomError::MathOverflow)
+    } else {
+        Ok(quotient)
+    }
+}
+
+fn within_tolerance(actual: u128, target: u128) -> bool {
+    let diff = actual.abs_diff(target);
+    mul_div(target, SOLVER_TOLERANCE_PPM, 1_000_000).map_or(false, |max_diff| diff <= max_diff)
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    /// pump.fun: A = 1_073_000_191 tokens, C = 30 SOL, graduating at 85 SOL.
+    ///
+    /// A is above pump.fun's 1B supply, so the supply here leaves room for it.
+    fn pump_fun_economics() -> LaunchEconomics {
+        LaunchEconomics {
+            total_supply: 1_100_000_000,
+            token_decimals: 9,
+            // y(85 SOL) = A * 85 / 115 = 793_100_141 tokens = 72.10% of the supply
+            sold_before_graduation_bps: 7_210,
+            grad_threshold: 85,
+            base_decimals: 9,
+            // C / A = 30 SOL / 1_073_000_191 tokens
+            start_price: 27_958_993_476,
+        }
+    }
+
+    fn assert_close(actual: u128, expected: u128, rel_tolerance: f64) {
+        let diff = actual.abs_diff(expected) as f64;
+        assert!(
+            diff <= expected as f64 * rel_tolerance,
+            "Expected ~{}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_recovers_pump_fun_parameters() {
+        let solved = solve_smooth_curve(&pump_fun_economics()).unwrap();
+        let curve = &solved.curve;
+
+        assert_close(
+            curve.a_total_tokens as u128,
+            1_073_000_191 * 1_000_000_000,
+            1e-4,
+        );
+        assert_close(
+            curve.c_bonding_scale_factor as u128,
+            30 * LAMPORTS_PER_SOL as u128,
+            1e-4,
+        );
+        assert_eq!(
+            curve.k_virtual_pool_offset,
+            curve.a_total_tokens as u128 * curve.c_bonding_scale_factor as u128
+        );
+    }
+
+    #[test]
+    fn test_solution_hits_the_targets() {
+        let economics = LaunchEconomics {
+            total_supply: 1_000_000_000,
+            token_decimals: 6,
+            sold_before_graduation_bps: 8_000,
+            grad_threshold: 100_000,
+            base_decimals: 6,
+            // 0.00001 base per whole token
+            start_price: WAD / 100_000,
+        };
+        let solved = solve_smooth_curve(&economics).unwrap();
+
+        let raw_supply = 1_000_000_000 * 1_000_000u128;
+        assert_close(
+            solved.tokens_sold_at_graduation as u128,
+            raw_supply * 8 / 10,
+            1e-4,
+        );
+        assert_close(solved.start_price, economics.start_price, 1e-4);
+        assert!(solved.curve.a_total_tokens as u128 <= raw_supply);
+
+        // The reported numbers are the curve's own.
+        let grad = 100_000 * 1_000_000;
+        let checked = verify(&solved.curve, grad).unwrap();
+        assert_eq!(
+            checked.tokens_sold_at_graduation,
+            solved.tokens_sold_at_graduation
+        );
+        assert_eq!(checked.start_price, solved.start_price);
+    }
+
+    #[test]
+    fn test_rejects_infeasible_economics() {
+        // Starting above the average price G / T has no solution.
+        let mut economics = pump_fun_economics();
+        economics.start_price = 200_000_000_000;
+        assert!(matches!(
+            solve_smooth_curve(&economics),
+            Err(CustomError::InvalidCurveParameters)
+        ));
+
+        // Selling 90% by graduation needs an asymptote above the total supply.
+        let mut economics = pump_fun_economics();
+        economics.sold_before_graduation_bps = 9_000;
+        assert!(matches!(
+            solve_smooth_curve(&economics),
+            Err(CustomError::CurveExceedsTotalSupply)
+        ));
+
+        let mut economics = pump_fun_economics();
+        economics.sold_before_graduation_bps = BPS_DENOMINATOR + 1;
+        assert!(matches!(
+            solve_smooth_curve(&economics),
+            Err(CustomError::InvalidCurveParameters)
+        ));
+
+        let mut economics = pump_fun_economics();
+        economics.grad_threshold = 0;
+        assert!(matches!(
+            solve_smooth_curve(&economics),
+            Err(CustomError::InvalidGradThreshold)
+        ));
+
+        // A start price too small for an integer C to hit it.
+        let mut economics = pump_fun_economics();
+        economics.start_price = 3;
+        assert!(matches!(
+            solve_smooth_curve(&economics),
+            Err(CustomError::InvalidCurveParameters)
+        ));
+    }
+}
@@ -5,7 +5,7 @@ pub enum CustomError {
     #[msg("Custom error: Token supply is not enough to fulfill buy request")]
     InsufficientTokenSupply,
 
-    #[msg("Provide a smaller amount. Use normalized tokens (e.g., raw value / 10 ** decimals).")]
+    #[msg("Math overflow: the amount is out of range for the bonding curve.")]
     MathOverflow,
 
     #[msg("Unauthorized: Caller is not authorized to perform this action.")]
@@ -31,4 +31,58 @@ pub enum CustomError {
 
     #[msg("Wrong payment mint provided.")]
     WrongPaymentMint,
+
+    #[msg("Nothing to sweep: the escrow holds no base tokens beyond the tracked reserve.")]
+    NothingToSweep,
+
+    #[msg(
+        "Invalid curve parameters: every parameter must be non-zero and keep the curve in range."
+    )]
+    InvalidCurveParameters,
+
+    #[msg("Invalid curve: y(0) must be 0, but K / C is smaller than A.")]
+    CurveStartsAboveZero,
+
+    #[msg("Invalid curve: K / C is larger than A.")]
+    CurveOffsetExceedsTotalTokens,
+
+    #[msg("Invalid curve: the curve can sell more tokens than the total supply.")]
+    CurveExceedsTotalSupply,
+
+    #[msg(
+        "Invalid graduation threshold: it must be non-zero and reachable within the total supply."
+    )]
+    InvalidGradThreshold,
+
+    #[msg(
+        "Invalid piecewise curve: segments must start at 0, have increasing breakpoints and end on a bounded curve."
+    )]
+    InvalidCurveSegments,
+
+    #[msg("Price impact exceeded: the trade moves the spot price more than the core allows.")]
+    PriceImpactExceeded,
+
+    #[msg("Invalid fee: a fee rate must be below 10000 bps.")]
+    InvalidFeeBps,
+
+    #[msg("Nothing to claim: the fee vault is empty.")]
+    NothingToClaim,
+
+    #[msg("Invalid referrer: traders cannot refer themselves, or replace or leave out their first referrer.")]
+    InvalidReferrer,
+
+    #[msg("Missing creation fee accounts: a base mint creation fee needs the creator's token account and the fee vault.")]
+    MissingCreationFeeAccounts,
+
+    #[msg("Invalid total chains: a token must live on 1 to MAX_TOTAL_CHAINS chains.")]
+    InvalidTotalChains,
+
+    #[msg("Unreachable graduation threshold: the chain-adjusted threshold exceeds what the curve can take in.")]
+    UnreachableChainThreshold,
+
+    #[msg("Already migrated: the token's liquidity has moved into its AMM pool.")]
+    AlreadyMigrated,
+
+    #[msg("Already withdrawn: the token's liquidity has gone to the admin.")]
+    AlreadyWithdrawn,
 }
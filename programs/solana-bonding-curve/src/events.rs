@@ -4,12 +4,112 @@ use anchor_lang::prelude::*;
 pub struct GraduationTriggered {
     pub buyer: Pubkey,
     pub escrow_balance: u64,
+    pub market_cap: u128,
+    pub fully_diluted_valuation: u128,
     pub vault: Pubkey,
     pub creator: Pubkey,
     pub escrow: Pubkey,
     pub token_seed: Pubkey,
 }
 
+#[event]
+pub struct DonationsSwept {
+    pub token_seed: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub real_base_reserve: u64,
+}
+
+#[event]
+pub struct ProtocolFeesClaimed {
+    pub admin: Pubkey,
+    pub base_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreatorFeesClaimed {
+    pub token_seed: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_accrued: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct ReferralRecorded {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct ReferralFeesClaimed {
+    pub referrer: Pubkey,
+    pub base_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreationFeePaid {
+    pub token_seed: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    // The base mint the fee was paid in, or None for lamports
+    pub base_mint: Option<Pubkey>,
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub token_seed: Pubkey,
+    pub admin: Pubkey,
+    pub creator: Pubkey,
+    // Base tokens in the escrow at withdrawal and how they were split
+    pub escrow_balance: u64,
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub base_liquidity: u64,
+    // Project tokens left in the vault, sent along with the liquidity
+    pub token_liquidity: u64,
+}
+
+#[event]
+pub struct PoolMigrated {
+    pub token_seed: Pubkey,
+    pub pool: Pubkey,
+    // Final curve spot price the pool opened at, WAD-scaled base per raw token
+    pub price: u128,
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub base_liquidity: u64,
+    pub token_liquidity: u64,
+    // Base sent to the admin and unsold tokens burned because the pool couldn't take them
+    pub base_leftover: u64,
+    pub tokens_burned: u64,
+}
+
+/// Emitted by every trade with the fees paid into each vault, for reconciling vault balances.
+#[event]
+pub struct FeeCollected {
+    pub token_seed: Pubkey,
+    pub user: Pubkey,
+    pub base_mint: Pubkey,
+    // Regular fees paid into the protocol's `fee_vault` and the token's `creator_fee_vault`,
+    // as in `XyberSwapEvent`
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    // Paid into the referrer's `referral_vault`
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    // Anti-sniper surcharge, paid into the `creator_fee_vault` if `sniper_fee_to_creator`,
+    // otherwise into the `fee_vault`
+    pub sniper_fee: u64,
+    pub sniper_fee_to_creator: bool,
+    // The token's totals after this trade
+    pub total_protocol_fees: u64,
+    pub total_creator_fees: u64,
+    pub total_referral_fees: u64,
+}
+
 #[event]
 pub struct XyberSwapEvent {
     pub ix_type: XyberInstructionType,
@@ -18,6 +118,25 @@ pub struct XyberSwapEvent {
     pub base_amount: u64,
     pub token_amount: u64,
     pub vault_token_amount: u64,
+    // Curve state after the trade: price is WAD-scaled base_tokens per raw token
+    pub spot_price: u128,
+    pub market_cap: u128,
+    pub tokens_remaining: u64,
+    // A buy capped at the tokens left for sale or at the graduation threshold:
+    // `base_amount` was charged and the base the rest would have cost
+    // (`base_amount_unfilled`) stayed with the buyer
+    pub partial_fill: bool,
+    pub base_amount_unfilled: u64,
+    // Fees paid on top of `base_amount` (buys) or taken out of it (sells)
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    // Referrer paid out of the protocol fee, if any
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    // Anti-sniper surcharge of a buy right after launch, on top of the fees above
+    pub sniper_fee: u64,
+    // Fee rate charged, in bps of `base_amount`: tier discount and anti-sniper fee included
+    pub fee_bps: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -0,0 +1,588 @@
+//! Trading fee math.
+//!
+//! Fees are charged on the base that moves on the curve and rounded up: a buy pays
+//! the curve cost plus the fee, a sell receives the curve proceeds minus the fee.
+//! The fee is charged once at the combined rate and then split between its recipients;
+//! a trader's referrer gets a share of the protocol's part. Right after launch, buys pay
+//! a raised anti-sniper rate; the surcharge above the regular fee is accounted separately.
+//! Traders with enough rolling volume get the regular fee discounted by the core's tier table.
+//! At withdrawal of a graduated token, the protocol and the creator take a share of the escrow.
+
+use crate::curves::math::{div_ceil, mul_div, BPS_DENOMINATOR};
+use crate::errors::CustomError;
+use crate::{TraderStats, XyberCore, XyberToken};
+use anchor_lang::prelude::{borsh, AnchorDeserialize, AnchorSerialize, Pubkey};
+
+/// Number of entries in `XyberCore::fee_tiers` (spelled out there, as the IDL needs a literal).
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// A volume tier: traders with at least `min_volume` rolling base volume (raw units) get
+/// the regular trading fee reduced by `discount_bps` of it. Unused entries are all zero.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_volume: u64,
+    pub discount_bps: u64,
+}
+
+impl FeeTier {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// Rejects fee rates of 100% or more.
+pub fn validate_fee_bps(fee_bps: u64) -> std::result::Result<(), CustomError> {
+    if fee_bps >= BPS_DENOMINATOR {
+        return Err(CustomError::InvalidFeeBps);
+    }
+    Ok(())
+}
+
+/// The combined trading fee rate of `core`: the protocol's and the creator's.
+pub fn trading_fee_bps(core: &XyberCore) -> std::result::Result<u64, CustomError> {
+    core.protocol_fee_bps
+        .checked_add(core.creator_fee_bps)
+        .ok_or(CustomError::InvalidFeeBps)
+}
+
+/// The combined graduation fee rate of `core`: the protocol's and the creator's share.
+pub fn graduation_fee_bps(core: &XyberCore) -> std::result::Result<u64, CustomError> {
+    core.graduation_protocol_fee_bps
+        .checked_add(core.graduation_creator_fee_bps)
+        .ok_or(CustomError::InvalidFeeBps)
+}
+
+/// The graduation fee on an escrow of `escrow_balance` base tokens at the protocol's and the
+/// creator's rates, rounded down so the liquidity keeps the remainder, and split like a
+/// trading fee.
+pub fn split_graduation_fee(
+    escrow_balance: u64,
+    protocol_fee_bps: u64,
+    creator_fee_bps: u64,
+) -> std::result::Result<FeeSplit, CustomError> {
+    let fee_bps = protocol_fee_bps
+        .checked_add(creator_fee_bps)
+        .ok_or(CustomError::InvalidFeeBps)?;
+    validate_fee_bps(fee_bps)?;
+    let fee = mul_div(
+        escrow_balance as u128,
+        fee_bps as u128,
+        BPS_DENOMINATOR as u128,
+    )
+    .ok_or(CustomError::MathOverflow)? as u64;
+    split_fee(fee, protocol_fee_bps, creator_fee_bps)
+}
+
+/// Rejects tier discounts above 100% of the fee.
+pub fn validate_fee_tiers(tiers: &[FeeTier]) -> std::result::Result<(), CustomError> {
+    if tiers.iter().any(|tier| tier.discount_bps > BPS_DENOMINATOR) {
+        return Err(CustomError::InvalidFeeBps);
+    }
+    Ok(())
+}
+
+/// The largest discount among the tiers `volume` reaches (0 if none).
+pub fn tier_discount_bps(tiers: &[FeeTier], volume: u64) -> u64 {
+    tiers
+        .iter()
+        .filter(|tier| volume >= tier.min_volume)
+        .map(|tier| tier.discount_bps)
+        .max()
+        .unwrap_or(0)
+}
+
+/// The regular trading fee rate of a trader with `volume` rolling base volume: the core's
+/// trading fee minus the trader's tier discount (rounded down).
+pub fn trader_fee_bps(core: &XyberCore, volume: u64) -> std::result::Result<u64, CustomError> {
+    let discount_bps = tier_discount_bps(&core.fee_tiers, volume).min(BPS_DENOMINATOR);
+    let fee_bps = mul_div(
+        trading_fee_bps(core)? as u128,
+        (BPS_DENOMINATOR - discount_bps) as u128,
+        BPS_DENOMINATOR as u128,
+    )
+    .ok_or(CustomError::MathOverflow)?;
+    Ok(fee_bps as u64)
+}
+
+impl TraderStats {
+    /// Starts a new volume window once `window_slots` have passed since the current one
+    /// started. With `window_slots == 0` volume never expires.
+    fn roll(&mut self, window_slots: u64, slot: u64) {
+        if window_slots == 0 {
+            return;
+        }
+        let windows_passed = slot.saturating_sub(self.window_start_slot) / window_slots;
+        if windows_passed == 0 {
+            return;
+        }
+        self.previous_volume = if windows_passed == 1 {
+            self.current_volume
+        } else {
+            0
+        };
+        self.current_volume = 0;
+        self.window_start_slot += windows_passed * window_slots;
+    }
+
+    /// The trader's base volume over the last `window_slots` at `slot`: the current window
+    /// plus the part of the previous one that still overlaps the last `window_slots`.
+    pub fn rolling_volume(&self, window_slots: u64, slot: u64) -> u64 {
+        let mut stats = self.clone();
+        stats.roll(window_slots, slot);
+        if window_slots == 0 {
+            return stats.current_volume;
+        }
+
+        let elapsed = slot.saturating_sub(stats.window_start_slot);
+        let previous_overlap = mul_div(
+            stats.previous_volume as u128,
+            window_slots.saturating_sub(elapsed) as u128,
+            window_slots as u128,
+        )
+        .map_or(0, |overlap| overlap as u64);
+        stats.current_volume.saturating_add(previous_overlap)
+    }
+
+    /// Adds `base_amount` to the trader's volume at `slot`.
+    pub fn record_volume(&mut self, window_slots: u64, slot: u64, base_amount: u64) {
+        self.roll(window_slots, slot);
+        self.current_volume = self.current_volume.saturating_add(base_amount);
+    }
+}
+
+/// Adds `amounts` to the matching `totals`, failing if any of them would overflow.
+fn add_fee_totals(
+    totals: [&mut u64; 3],
+    amounts: [u64; 3],
+) -> std::result::Result<(), CustomError> {
+    for (total, amount) in totals.into_iter().zip(amounts) {
+        *total = total.checked_add(amount).ok_or(CustomError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+impl XyberCore {
+    /// Adds the fees a trade paid into the protocol, creator and referral vaults to the
+    /// global totals.
+    pub fn record_fees(
+        &mut self,
+        protocol: u64,
+        creator: u64,
+        referral: u64,
+    ) -> std::result::Result<(), CustomError> {
+        add_fee_totals(
+            [
+                &mut self.total_protocol_fees,
+                &mut self.total_creator_fees,
+                &mut self.total_referral_fees,
+            ],
+            [protocol, creator, referral],
+        )
+    }
+}
+
+impl XyberToken {
+    /// Adds the fees a trade of this token paid into the protocol, creator and referral
+    /// vaults to its totals.
+    pub fn record_fees(
+        &mut self,
+        protocol: u64,
+        creator: u64,
+        referral: u64,
+    ) -> std::result::Result<(), CustomError> {
+        add_fee_totals(
+            [
+                &mut self.total_protocol_fees,
+                &mut self.total_creator_fees,
+                &mut self.total_referral_fees,
+            ],
+            [protocol, creator, referral],
+        )
+    }
+}
+
+/// A trading fee divided between its recipients.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeSplit {
+    pub protocol: u64,
+    pub creator: u64,
+    pub referral: u64,
+    // Anti-sniper surcharge on top of the regular fee, sent to the protocol or the creator
+    pub sniper: u64,
+}
+
+impl FeeSplit {
+    /// Moves `referral_share_bps` of the protocol's part (rounded down) to the referrer.
+    pub fn with_referral(self, referral_share_bps: u64) -> std::result::Result<Self, CustomError> {
+        let referral = mul_div(
+            self.protocol as u128,
+            referral_share_bps.min(BPS_DENOMINATOR) as u128,
+            BPS_DENOMINATOR as u128,
+        )
+        .ok_or(CustomError::MathOverflow)? as u64;
+        Ok(FeeSplit {
+            protocol: self.protocol - referral,
+            referral: self.referral + referral,
+            ..self
+        })
+    }
+}
+
+/// Rejects referral shares above 100% of the protocol fee.
+pub fn validate_referral_share_bps(share_bps: u64) -> std::result::Result<(), CustomError> {
+    if share_bps > BPS_DENOMINATOR {
+        return Err(CustomError::InvalidFeeBps);
+    }
+    Ok(())
+}
+
+/// The referrer to pay for a trade by `trader` who passed `offered` as its referrer.
+///
+/// The first referrer recorded for a trader sticks: once `recorded` is set, every trade has to
+/// offer it and pays it. Until then, a trade without an `offered` referrer pays nobody and the
+/// share stays with the protocol.
+pub fn resolve_referrer(
+    recorded: Pubkey,
+    trader: Pubkey,
+    offered: Option<Pubkey>,
+) -> std::result::Result<Option<Pubkey>, CustomError> {
+    match offered {
+        None if recorded == Pubkey::default() => Ok(None),
+        None => Err(CustomError::InvalidReferrer),
+        Some(referrer) if referrer == trader => Err(CustomError::InvalidReferrer),
+        Some(referrer) if recorded == Pubkey::default() || recorded == referrer => {
+            Ok(Some(referrer))
+        }
+        Some(_) => Err(CustomError::InvalidReferrer),
+    }
+}
+
+/// Splits `fee`, charged at `protocol_fee_bps + creator_fee_bps`, in proportion to the two
+/// rates. The creator's share is rounded down; the rounding remainder goes to the protocol.
+pub fn split_fee(
+    fee: u64,
+    protocol_fee_bps: u64,
+    creator_fee_bps: u64,
+) -> std::result::Result<FeeSplit, CustomError> {
+    let total_fee_bps = protocol_fee_bps
+        .checked_add(creator_fee_bps)
+        .ok_or(CustomError::InvalidFeeBps)?;
+    if total_fee_bps == 0 {
+        return Ok(FeeSplit {
+            protocol: fee,
+            ..FeeSplit::default()
+        });
+    }
+
+    let creator = mul_div(fee as u128, creator_fee_bps as u128, total_fee_bps as u128)
+        .ok_or(CustomError::MathOverflow)? as u64;
+    Ok(FeeSplit {
+        protocol: fee - creator,
+        creator,
+        ..FeeSplit::default()
+    })
+}
+
+/// Splits the fee of a buy of `base_in`, which may be charged at a raised anti-sniper rate:
+/// the fee at the trader's `regular_fee_bps` is split as usual and the rest is the
+/// anti-sniper surcharge.
+pub fn split_buy_fee(
+    fee: u64,
+    base_in: u64,
+    regular_fee_bps: u64,
+    protocol_fee_bps: u64,
+    creator_fee_bps: u64,
+) -> std::result::Result<FeeSplit, CustomError> {
+    let regular_fee = fee_on(base_in, regular_fee_bps)?.min(fee);
+    Ok(FeeSplit {
+        sniper: fee - regular_fee,
+        ..split_fee(regular_fee, protocol_fee_bps, creator_fee_bps)?
+    })
+}
+
+/// The fee rate `elapsed_slots` after launch: `launch_fee_bps` at the launch slot, decaying
+/// linearly to `regular_fee_bps` over `decay_slots` (rounded up). Never below the regular rate.
+pub fn decayed_fee_bps(
+    regular_fee_bps: u64,
+    launch_fee_bps: u64,
+    decay_slots: u64,
+    elapsed_slots: u64,
+) -> u64 {
+    if launch_fee_bps <= regular_fee_bps || elapsed_slots >= decay_slots {
+        return regular_fee_bps;
+    }
+    let surcharge = div_ceil(
+        (launch_fee_bps - regular_fee_bps) as u128 * (decay_slots - elapsed_slots) as u128,
+        decay_slots as u128,
+    )
+    .map_or(0, |surcharge| surcharge as u64);
+    regular_fee_bps + surcharge
+}
+
+/// The fee rate of a buy of `token` in `slot`: the trader's `regular_fee_bps`, raised by
+/// the token's anti-sniper fee while it decays.
+pub fn buy_fee_bps(regular_fee_bps: u64, token: &XyberToken, slot: u64) -> u64 {
+    decayed_fee_bps(
+        regular_fee_bps,
+        token.sniper_fee_bps,
+        token.sniper_fee_decay_slots,
+        slot.saturating_sub(token.launch_slot),
+    )
+}
+
+/// The fee on `amount` base tokens at `fee_bps`, rounded up.
+pub fn fee_on(amount: u64, fee_bps: u64) -> std::result::Result<u64, CustomError> {
+    let fee = div_ceil(amount as u128 * fee_bps as u128, BPS_DENOMINATOR as u128)
+        .ok_or(CustomError::MathOverflow)?;
+    u64::try_from(fee).map_err(|_| CustomError::MathOverflow)
+}
+
+/// The most base that can go into the curve when `total` has to pay for it and its fee,
+/// i.e. the largest `amount` with `amount + fee_on(amount) <= total`.
+pub fn amount_before_fee(total: u64, fee_bps: u64) -> std::result::Result<u64, CustomError> {
+    let amount = mul_div(
+        total as u128,
+        BPS_DENOMINATOR as u128,
+        (BPS_DENOMINATOR + fee_bps) as u128,
+    )
+    .ok_or(CustomError::MathOverflow)?;
+    u64::try_from(amount).map_err(|_| CustomError::MathOverflow)
+}
+
+/// The least base that has to come out of the curve for `net` to remain after the fee,
+/// i.e. the smallest `amount` with `amount - fee_on(amount) >= net`.
+pub fn gross_up_for_fee(net: u64, fee_bps: u64) -> std::result::Result<u64, CustomError> {
+    validate_fee_bps(fee_bps)?;
+    let amount = div_ceil(
+        net as u128 * BPS_DENOMINATOR as u128,
+        (BPS_DENOMINATOR - fee_bps) as u128,
+    )
+    .ok_or(CustomError::MathOverflow)?;
+    u64::try_from(amount).map_err(|_| CustomError::MathOverflow)
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_on_rounds_up() {
+        assert_eq!(fee_on(10_000, 100).unwrap(), 100);
+        assert_eq!(fee_on(10_001, 100).unwrap(), 101);
+        assert_eq!(fee_on(1, 1).unwrap(), 1);
+        assert_eq!(fee_on(0, 100).unwrap(), 0);
+        assert_eq!(fee_on(u64::MAX, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fee_inverses() {
+        for fee_bps in [0, 1, 30, 100, 2_500, 9_999] {
+            for total in [0, 1, 99, 10_000, 1_000_000_007, u64::MAX / 2] {
+                // The amount left for the curve, plus its fee, never exceeds the total...
+                let amount = amount_before_fee(total, fee_bps).unwrap();
+                assert!(amount + fee_on(amount, fee_bps).unwrap() <= total);
+                // ...and one more base unit would.
+                assert!(amount + 1 + fee_on(amount + 1, fee_bps).unwrap() > total);
+
+                // Grossing up leaves at least `net` after the fee, and nothing less would.
+                let Ok(gross) = gross_up_for_fee(total, fee_bps) else {
+                    // Only a net amount close to u64::MAX at a steep fee has no gross in range.
+                    assert!(total > u64::MAX / 4);
+                    continue;
+                };
+                assert!(gross - fee_on(gross, fee_bps).unwrap() >= total);
+                if gross > 0 {
+                    let less = gross - 1;
+                    assert!(less - fee_on(less, fee_bps).unwrap() < total);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_fee() {
+        // 1% protocol + 0.5% creator on a 150 base unit fee.
+        let split = split_fee(150, 100, 50).unwrap();
+        assert_eq!((split.protocol, split.creator), (100, 50));
+
+        // The creator's share is rounded down and nothing is lost.
+        let split = split_fee(101, 100, 100).unwrap();
+        assert_eq!((split.protocol, split.creator), (51, 50));
+
+        assert_eq!(split_fee(7, 0, 30).unwrap().creator, 7);
+        assert_eq!(split_fee(0, 0, 0).unwrap(), FeeSplit::default());
+
+        // A referrer with a 25% share takes it out of the protocol's part only.
+        let split = split_fee(150, 100, 50)
+            .unwrap()
+            .with_referral(2_500)
+            .unwrap();
+        assert_eq!(
+            (split.protocol, split.creator, split.referral),
+            (75, 50, 25)
+        );
+    }
+
+    #[test]
+    fn test_decayed_fee_bps() {
+        // 50% at launch, decaying to 1% over 100 slots.
+        assert_eq!(decayed_fee_bps(100, 5_000, 100, 0), 5_000);
+        assert_eq!(decayed_fee_bps(100, 5_000, 100, 50), 2_550);
+        // The surcharge is rounded up until the last slot of the decay.
+        assert_eq!(decayed_fee_bps(100, 5_000, 100, 99), 149);
+        assert_eq!(decayed_fee_bps(100, 5_000, 100, 100), 100);
+        assert_eq!(decayed_fee_bps(100, 5_000, 100, u64::MAX), 100);
+
+        // No schedule, or one below the regular rate, leaves the regular rate.
+        assert_eq!(decayed_fee_bps(100, 5_000, 0, 0), 100);
+        assert_eq!(decayed_fee_bps(100, 50, 100, 0), 100);
+    }
+
+    #[test]
+    fn test_add_fee_totals() {
+        let (mut protocol, mut creator, mut referral) = (10, 20, 0);
+        add_fee_totals([&mut protocol, &mut creator, &mut referral], [5, 0, 7]).unwrap();
+        assert_eq!((protocol, creator, referral), (15, 20, 7));
+
+        let mut full = u64::MAX;
+        assert!(matches!(
+            add_fee_totals([&mut protocol, &mut full, &mut referral], [0, 1, 0]),
+            Err(CustomError::MathOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_split_graduation_fee() {
+        // 1% protocol + 2% creator of a 10_000 base unit escrow.
+        let split = split_graduation_fee(10_000, 100, 200).unwrap();
+        assert_eq!((split.protocol, split.creator), (100, 200));
+
+        // The fee is rounded down, leaving the remainder to the liquidity.
+        let split = split_graduation_fee(99, 100, 200).unwrap();
+        assert_eq!((split.protocol, split.creator), (1, 1));
+        let split = split_graduation_fee(101, 5_000, 0).unwrap();
+        assert_eq!((split.protocol, split.creator), (50, 0));
+
+        assert_eq!(
+            split_graduation_fee(u64::MAX, 0, 0).unwrap(),
+            FeeSplit::default()
+        );
+        assert!(matches!(
+            split_graduation_fee(1, 5_000, 5_000),
+            Err(CustomError::InvalidFeeBps)
+        ));
+    }
+
+    #[test]
+    fn test_split_buy_fee() {
+        // 1 SOL bought at 25.5% instead of 1% protocol + 0.5% creator.
+        let base_in = 1_000_000_000;
+        let fee = fee_on(base_in, 2_550).unwrap();
+        let split = split_buy_fee(fee, base_in, 150, 100, 50).unwrap();
+        assert_eq!((split.protocol, split.creator), (10_000_000, 5_000_000));
+        assert_eq!(split.sniper, fee - 15_000_000);
+
+        // At the regular rate there is no surcharge.
+        let fee = fee_on(base_in, 150).unwrap();
+        assert_eq!(split_buy_fee(fee, base_in, 150, 100, 50).unwrap().sniper, 0);
+    }
+
+    #[test]
+    fn test_tier_discount_bps() {
+        let mut tiers = [FeeTier::default(); MAX_FEE_TIERS];
+        tiers[0] = FeeTier {
+            min_volume: 100,
+            discount_bps: 1_000,
+        };
+        tiers[1] = FeeTier {
+            min_volume: 1_000,
+            discount_bps: 2_500,
+        };
+        assert!(validate_fee_tiers(&tiers).is_ok());
+
+        // Unused (all-zero) entries grant nothing.
+        assert_eq!(tier_discount_bps(&tiers, 0), 0);
+        assert_eq!(tier_discount_bps(&tiers, 99), 0);
+        assert_eq!(tier_discount_bps(&tiers, 100), 1_000);
+        assert_eq!(tier_discount_bps(&tiers, 5_000), 2_500);
+
+        tiers[2].discount_bps = BPS_DENOMINATOR + 1;
+        assert!(matches!(
+            validate_fee_tiers(&tiers),
+            Err(CustomError::InvalidFeeBps)
+        ));
+    }
+
+    #[test]
+    fn test_rolling_volume() {
+        let window = 100;
+        let mut stats = TraderStats::default();
+
+        // A fresh account starts its first window at the first trade.
+        stats.record_volume(window, 1_050, 400);
+        assert_eq!(stats.window_start_slot, 1_000);
+        assert_eq!(stats.rolling_volume(window, 1_050), 400);
+
+        // Half-way through the next window, half of the previous one still counts.
+        stats.record_volume(window, 1_120, 100);
+        assert_eq!(stats.rolling_volume(window, 1_150), 100 + 200);
+
+        // After two idle windows everything has expired.
+        assert_eq!(stats.rolling_volume(window, 1_300), 0);
+
+        // Without a window volume never expires.
+        let mut stats = TraderStats::default();
+        stats.record_volume(0, 5, 10);
+        stats.record_volume(0, 1_000_000, 10);
+        assert_eq!(stats.rolling_volume(0, u64::MAX), 20);
+    }
+
+    #[test]
+    fn test_resolve_referrer() {
+        let trader = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let none = Pubkey::default();
+
+        // The first referrer is accepted and then sticks.
+        assert_eq!(
+            resolve_referrer(none, trader, Some(referrer)).unwrap(),
+            Some(referrer)
+        );
+        assert_eq!(
+            resolve_referrer(referrer, trader, Some(referrer)).unwrap(),
+            Some(referrer)
+        );
+        assert!(matches!(
+            resolve_referrer(referrer, trader, Some(other)),
+            Err(CustomError::InvalidReferrer)
+        ));
+
+        // No self-referrals; no referrer, no payout.
+        assert!(matches!(
+            resolve_referrer(none, trader, Some(trader)),
+            Err(CustomError::InvalidReferrer)
+        ));
+        assert_eq!(resolve_referrer(none, trader, None).unwrap(), None);
+
+        // Once recorded, the referrer can't be left out to skip its payout.
+        assert!(matches!(
+            resolve_referrer(referrer, trader, None),
+            Err(CustomError::InvalidReferrer)
+        ));
+    }
+
+    #[test]
+    fn test_validate_fee_bps() {
+        assert!(validate_fee_bps(0).is_ok());
+        assert!(validate_fee_bps(BPS_DENOMINATOR - 1).is_ok());
+        assert!(matches!(
+            validate_fee_bps(BPS_DENOMINATOR),
+            Err(CustomError::InvalidFeeBps)
+        ));
+        assert!(matches!(
+            gross_up_for_fee(1, BPS_DENOMINATOR),
+            Err(CustomError::InvalidFeeBps)
+        ));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::errors::CustomError;
+use crate::events::{CreatorFeesClaimed, ProtocolFeesClaimed, ReferralFeesClaimed};
+use crate::{XyberCore, XyberToken};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct ClaimProtocolFees<'info> {
+    #[account(
+        seeds = [b"xyber_core"],
+        bump
+    )]
+    pub xyber_core: Account<'info, XyberCore>,
+
+    /// CHECK: Admin from xyber_core, receives the collected fees as the treasury
+    #[account(
+        address = xyber_core.admin,
+        mut,
+        signer
+    )]
+    pub admin: AccountInfo<'info>,
+
+    pub base_token_mint: Account<'info, Mint>,
+
+    /// Protocol fees collected in `base_token_mint`
+    #[account(
+        mut,
+        seeds = [b"fee_vault", base_token_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = admin
+    )]
+    pub admin_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves every protocol fee collected in `base_token_mint` to the treasury.
+pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+    let amount = ctx.accounts.fee_vault.amount;
+    require!(amount > 0, CustomError::NothingToClaim);
+
+    let bump = ctx.bumps.xyber_core;
+    let seeds = &[b"xyber_core".as_ref(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            to: ctx.accounts.admin_token_account.to_account_info(),
+            authority: ctx.accounts.xyber_core.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    emit!(ProtocolFeesClaimed {
+        admin: ctx.accounts.admin.key(),
+        base_mint: ctx.accounts.base_token_mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"xyber_token", token_seed.key().as_ref()],
+        bump,
+        has_one = creator @ CustomError::Unauthorized
+    )]
+    pub xyber_token: Account<'info, XyberToken>,
+
+    /// CHECK: Used only for PDA seed derivation
+    pub token_seed: UncheckedAccount<'info>,
+
+    pub base_token_mint: Account<'info, Mint>,
+
+    /// Creator fees accrued by this token's trades
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", token_seed.key().as_ref()],
+        bump,
+        token::mint = base_token_mint
+    )]
+    pub creator_fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves the creator fees accrued by a token to its creator. Works before and after
+/// graduation.
+pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    let amount = ctx.accounts.creator_fee_vault.amount;
+    require!(amount > 0, CustomError::NothingToClaim);
+
+    let bump = ctx.bumps.xyber_token;
+    let seeds = &[
+        b"xyber_token".as_ref(),
+        ctx.accounts.token_seed.key.as_ref(),
+        &[bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.creator_fee_vault.to_account_info(),
+            to: ctx.accounts.creator_token_account.to_account_info(),
+            authority: ctx.accounts.xyber_token.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let xyber_token = &mut ctx.accounts.xyber_token;
+    xyber_token.creator_fees_claimed = xyber_token
+        .creator_fees_claimed
+        .checked_add(amount)
+        .ok_or(CustomError::MathOverflow)?;
+
+    emit!(CreatorFeesClaimed {
+        token_seed: ctx.accounts.token_seed.key(),
+        creator: ctx.accounts.creator.key(),
+        amount,
+        total_accrued: xyber_token.total_creator_fees,
+        total_claimed: xyber_token.creator_fees_claimed,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(
+        seeds = [b"xyber_core"],
+        bump
+    )]
+    pub xyber_core: Account<'info, XyberCore>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub base_token_mint: Account<'info, Mint>,
+
+    /// Referral fees earned by `referrer` in `base_token_mint`
+    #[account(
+        mut,
+        seeds = [b"referral_vault", referrer.key().as_ref(), base_token_mint.key().as_ref()],
+        bump
+    )]
+    pub referral_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = referrer,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = referrer
+    )]
+    pub referrer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves the referral fees a referrer earned in `base_token_mint` to the referrer.
+pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let amount = ctx.accounts.referral_vault.amount;
+    require!(amount > 0, CustomError::NothingToClaim);
+
+    let bump = ctx.bumps.xyber_core;
+    let seeds = &[b"xyber_core".as_ref(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.referral_vault.to_account_info(),
+            to: ctx.accounts.referrer_token_account.to_account_info(),
+            authority: ctx.accounts.xyber_core.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    emit!(ReferralFeesClaimed {
+        referrer: ctx.accounts.referrer.key(),
+        base_mint: ctx.accounts.base_token_mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
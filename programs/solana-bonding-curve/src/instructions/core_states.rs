@@ -4,7 +4,16 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{xyber_params::InitCoreParams, XyberCore};
+use crate::curves::BondingCurveTrait;
+use crate::errors::CustomError;
+use crate::fees::{
+    graduation_fee_bps, trading_fee_bps, validate_fee_bps, validate_fee_tiers,
+    validate_referral_share_bps,
+};
+use crate::{
+    xyber_params::{InitCoreParams, DECIMALS},
+    XyberCore,
+};
 
 pub fn fill_core_fields(core: &mut XyberCore, params: &InitCoreParams) {
     if let Some(admin) = params.admin {
@@ -22,6 +31,86 @@ pub fn fill_core_fields(core: &mut XyberCore, params: &InitCoreParams) {
     if let Some(total_supply) = params.total_supply {
         core.total_supply = total_supply;
     }
+    if let Some(max_price_impact_bps) = params.max_price_impact_bps {
+        core.max_price_impact_bps = max_price_impact_bps;
+    }
+    if let Some(protocol_fee_bps) = params.protocol_fee_bps {
+        core.protocol_fee_bps = protocol_fee_bps;
+    }
+    if let Some(creator_fee_bps) = params.creator_fee_bps {
+        core.creator_fee_bps = creator_fee_bps;
+    }
+    if let Some(referral_share_bps) = params.referral_share_bps {
+        core.referral_share_bps = referral_share_bps;
+    }
+    if let Some(sniper_fee_bps) = params.sniper_fee_bps {
+        core.sniper_fee_bps = sniper_fee_bps;
+    }
+    if let Some(sniper_fee_decay_slots) = params.sniper_fee_decay_slots {
+        core.sniper_fee_decay_slots = sniper_fee_decay_slots;
+    }
+    if let Some(sniper_fee_to_creator) = params.sniper_fee_to_creator {
+        core.sniper_fee_to_creator = sniper_fee_to_creator;
+    }
+    if let Some(fee_tiers) = params.fee_tiers {
+        core.fee_tiers = fee_tiers;
+    }
+    if let Some(volume_window_slots) = params.volume_window_slots {
+        core.volume_window_slots = volume_window_slots;
+    }
+    if let Some(graduation_protocol_fee_bps) = params.graduation_protocol_fee_bps {
+        core.graduation_protocol_fee_bps = graduation_protocol_fee_bps;
+    }
+    if let Some(graduation_creator_fee_bps) = params.graduation_creator_fee_bps {
+        core.graduation_creator_fee_bps = graduation_creator_fee_bps;
+    }
+}
+
+/// Rejects core configurations that would launch broken tokens:
+/// an inconsistent curve, a curve that can sell more than the minted supply,
+/// a graduation threshold (in whole base tokens) the curve can never reach,
+/// trading fees (protocol plus creator), graduation fees or an anti-sniper fee of 100% or more,
+/// or a referral share or tier discount above 100%.
+pub fn validate_core_fields(
+    core: &XyberCore,
+    base_decimals: u8,
+) -> std::result::Result<(), CustomError> {
+    core.bonding_curve.validate()?;
+    validate_fee_bps(trading_fee_bps(core)?)?;
+    validate_referral_share_bps(core.referral_share_bps)?;
+    validate_fee_bps(core.sniper_fee_bps)?;
+    validate_fee_bps(graduation_fee_bps(core)?)?;
+    validate_fee_tiers(&core.fee_tiers)?;
+
+    let raw_total_supply = core
+        .total_supply
+        .checked_mul(10_u64.pow(DECIMALS as u32))
+        .ok_or(CustomError::MathOverflow)?;
+    if core.bonding_curve.total_tokens()? > raw_total_supply {
+        return Err(CustomError::CurveExceedsTotalSupply);
+    }
+
+    if core.grad_threshold == 0 {
+        return Err(CustomError::InvalidGradThreshold);
+    }
+    let raw_grad_threshold = core
+        .grad_threshold
+        .checked_mul(10_u64.pow(base_decimals as u32))
+        .ok_or(CustomError::InvalidGradThreshold)?;
+    core.bonding_curve
+        .buy_exact_input(0, raw_grad_threshold)
+        .map_err(|_| CustomError::InvalidGradThreshold)?;
+
+    Ok(())
+}
+
+/// Only the admin may update an existing core. A core that was just created has no admin
+/// yet; the first update sets one.
+pub fn check_core_admin(core: &XyberCore, signer: Pubkey) -> std::result::Result<(), CustomError> {
+    if core.admin != Pubkey::default() && core.admin != signer {
+        return Err(CustomError::Unauthorized);
+    }
+    Ok(())
 }
 
 #[derive(Accounts)]
@@ -49,6 +138,17 @@ pub struct UpdateXyberCore<'info> {
     )]
     pub escrow_token_account: Account<'info, TokenAccount>,
 
+    /// Collects the protocol fee of every trade paid in `new_accepted_base_mint`.
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"fee_vault", new_accepted_base_mint.key().as_ref()],
+        bump,
+        token::mint = new_accepted_base_mint,
+        token::authority = xyber_core
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 
@@ -60,6 +160,146 @@ pub fn update_xyber_core_instruction(
     ctx: Context<UpdateXyberCore>,
     params: InitCoreParams,
 ) -> Result<()> {
+    check_core_admin(&ctx.accounts.xyber_core, ctx.accounts.admin.key())?;
     fill_core_fields(&mut ctx.accounts.xyber_core, &params);
+    // A core left without an admin could be taken over by the next update.
+    require_keys_neq!(
+        ctx.accounts.xyber_core.admin,
+        Pubkey::default(),
+        CustomError::Unauthorized
+    );
+
+    // The graduation threshold is denominated in the accepted base mint.
+    require_keys_eq!(
+        ctx.accounts.new_accepted_base_mint.key(),
+        ctx.accounts.xyber_core.accepted_base_mint,
+        CustomError::WrongPaymentMint
+    );
+    validate_core_fields(
+        &ctx.accounts.xyber_core,
+        ctx.accounts.new_accepted_base_mint.decimals,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCreationFee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"xyber_core"],
+        bump,
+        has_one = admin
+    )]
+    pub xyber_core: Account<'info, XyberCore>,
+}
+
+/// Sets the fee charged for every new launch: `creation_fee` lamports, or raw units of the
+/// accepted base mint if `in_base_mint`. A fee of 0 makes launches free again.
+pub fn set_creation_fee_instruction(
+    ctx: Context<SetCreationFee>,
+    creation_fee: u64,
+    in_base_mint: bool,
+) -> Result<()> {
+    let xyber_core = &mut ctx.accounts.xyber_core;
+    xyber_core.creation_fee = creation_fee;
+    xyber_core.creation_fee_in_base_mint = in_base_mint;
+
     Ok(())
 }
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::{BondingCurveType, SmoothBondingCurve};
+    use crate::instructions::testing::{
+        constant_product_curve, core, smooth_curve, TOKEN, TOTAL_SUPPLY,
+    };
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    fn smooth() -> BondingCurveType {
+        BondingCurveType::Smooth(smooth_curve())
+    }
+
+    #[test]
+    fn test_valid_core() {
+        let result = validate_core_fields(&core(smooth(), TOTAL_SUPPLY, 85), 9);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_rejects_invalid_curve() {
+        let curve = BondingCurveType::Smooth(SmoothBondingCurve {
+            a_total_tokens: TOTAL_SUPPLY * TOKEN,
+            k_virtual_pool_offset: 0,
+            c_bonding_scale_factor: 30 * LAMPORTS_PER_SOL,
+        });
+        let result = validate_core_fields(&core(curve, TOTAL_SUPPLY, 85), 9);
+        assert!(matches!(result, Err(CustomError::InvalidCurveParameters)));
+    }
+
+    #[test]
+    fn test_rejects_curve_above_total_supply() {
+        let result = validate_core_fields(&core(smooth(), TOTAL_SUPPLY - 1, 85), 9);
+        assert!(matches!(result, Err(CustomError::CurveExceedsTotalSupply)));
+    }
+
+    #[test]
+    fn test_rejects_zero_grad_threshold() {
+        let result = validate_core_fields(&core(smooth(), TOTAL_SUPPLY, 0), 9);
+        assert!(matches!(result, Err(CustomError::InvalidGradThreshold)));
+    }
+
+    #[test]
+    fn test_rejects_unreachable_grad_threshold() {
+        // Selling the whole real reserve takes ~85 SOL.
+        let curve = BondingCurveType::ConstantProduct(constant_product_curve());
+        let result = validate_core_fields(&core(curve.clone(), TOTAL_SUPPLY, 80), 9);
+        assert!(result.is_ok());
+
+        let result = validate_core_fields(&core(curve, TOTAL_SUPPLY, 100), 9);
+        assert!(matches!(result, Err(CustomError::InvalidGradThreshold)));
+
+        // A threshold that does not even fit into u64 once scaled by the base decimals.
+        let result = validate_core_fields(&core(smooth(), TOTAL_SUPPLY, u64::MAX), 9);
+        assert!(matches!(result, Err(CustomError::InvalidGradThreshold)));
+    }
+
+    #[test]
+    fn test_rejects_full_trading_fee() {
+        let mut xyber_core = core(smooth(), TOTAL_SUPPLY, 85);
+        xyber_core.protocol_fee_bps = 100;
+        assert!(validate_core_fields(&xyber_core, 9).is_ok());
+
+        xyber_core.protocol_fee_bps = 10_000;
+        let result = validate_core_fields(&xyber_core, 9);
+        assert!(matches!(result, Err(CustomError::InvalidFeeBps)));
+
+        // The creator fee is charged on top of the protocol fee.
+        xyber_core.protocol_fee_bps = 5_000;
+        xyber_core.creator_fee_bps = 5_000;
+        let result = validate_core_fields(&xyber_core, 9);
+        assert!(matches!(result, Err(CustomError::InvalidFeeBps)));
+    }
+
+    #[test]
+    fn test_check_core_admin() {
+        let admin = Pubkey::new_unique();
+        let mut xyber_core = core(smooth(), TOTAL_SUPPLY, 85);
+
+        // Whoever creates the core sets its admin.
+        assert!(check_core_admin(&xyber_core, Pubkey::new_unique()).is_ok());
+
+        // From then on, only the admin may update it.
+        xyber_core.admin = admin;
+        assert!(check_core_admin(&xyber_core, admin).is_ok());
+        assert!(matches!(
+            check_core_admin(&xyber_core, Pubkey::new_unique()),
+            Err(CustomError::Unauthorized)
+        ));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::curves::math::{mul_div, mul_div_ceil, WAD};
+use crate::curves::BondingCurveTrait;
+use crate::errors::CustomError;
+use crate::events::PoolMigrated;
+use crate::fees::split_graduation_fee;
+use crate::{XyberCore, XyberToken};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use constant_product_amm::cpi::accounts::CreatePool;
+use constant_product_amm::program::ConstantProductAmm;
+
+#[derive(Accounts)]
+pub struct MigrateToAmm<'info> {
+    #[account(
+        seeds = [b"xyber_core"],
+        bump
+    )]
+    pub xyber_core: Box<Account<'info, XyberCore>>,
+
+    /// CHECK: Admin from xyber_core, pays for the pool and receives base the pool can't take
+    #[account(
+        address = xyber_core.admin,
+        mut,
+        signer
+    )]
+    pub admin: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"xyber_token", token_seed.key().as_ref()],
+        bump
+    )]
+    pub xyber_token: Box<Account<'info, XyberToken>>,
+
+    /// CHECK: Used only for PDA seed derivation
+    pub token_seed: UncheckedAccount<'info>,
+
+    /// CHECK: Creator of the token, receives the creator's graduation fee
+    #[account(address = xyber_token.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = xyber_token.mint
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = xyber_token
+    )]
+    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Escrow token account holding the payment tokens (e.g. USDC)
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = xyber_token
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        address = xyber_core.accepted_base_mint
+    )]
+    pub base_token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = admin
+    )]
+    pub admin_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Receives the protocol's graduation fee
+    #[account(
+        mut,
+        seeds = [b"fee_vault", base_token_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: `xyber_token`'s pool of the base mint and the token, created and verified by the AMM
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Base reserve of the pool, created and verified by the AMM
+    #[account(mut)]
+    pub pool_base_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Token reserve of the pool, created and verified by the AMM
+    #[account(mut)]
+    pub pool_token_vault: UncheckedAccount<'info>,
+
+    pub amm_program: Program<'info, ConstantProductAmm>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateToAmm<'info> {
+    fn transfer_from_escrow(
+        &self,
+        to: AccountInfo<'info>,
+        amount: u64,
+        xyber_token_bump: u8,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let token_seed_key = self.token_seed.key();
+        let seeds: [&[u8]; 3] = [b"xyber_token", token_seed_key.as_ref(), &[xyber_token_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let escrow_transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.escrow_token_account.to_account_info(),
+                to,
+                authority: self.xyber_token.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(escrow_transfer_ctx, amount)
+    }
+
+    /// Creates the AMM pool with `base_amount` from escrow and `token_amount` from the vault,
+    /// deposited by the XyberToken PDA.
+    fn create_pool(&self, base_amount: u64, token_amount: u64, xyber_token_bump: u8) -> Result<()> {
+        let token_seed_key = self.token_seed.key();
+        let seeds: [&[u8]; 3] = [b"xyber_token", token_seed_key.as_ref(), &[xyber_token_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.amm_program.to_account_info(),
+            CreatePool {
+                payer: self.admin.to_account_info(),
+                depositor: self.xyber_token.to_account_info(),
+                base_mint: self.base_token_mint.to_account_info(),
+                token_mint: self.mint.to_account_info(),
+                pool: self.pool.to_account_info(),
+                base_vault: self.pool_base_vault.to_account_info(),
+                token_vault: self.pool_token_vault.to_account_info(),
+                depositor_base_account: self.escrow_token_account.to_account_info(),
+                depositor_token_account: self.vault_token_account.to_account_info(),
+                rent: self.rent.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+        constant_product_amm::cpi::create_pool(cpi_ctx, base_amount, token_amount)
+    }
+
+    /// Burns `amount` unsold tokens from the vault, signed by the XyberToken PDA.
+    fn burn_from_vault(&self, amount: u64, xyber_token_bump: u8) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let token_seed_key = self.token_seed.key();
+        let seeds: [&[u8]; 3] = [b"xyber_token", token_seed_key.as_ref(), &[xyber_token_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let burn_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Burn {
+                mint: self.mint.to_account_info(),
+                from: self.vault_token_account.to_account_info(),
+                authority: self.xyber_token.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::burn(burn_ctx, amount)
+    }
+}
+
+/// The pool deposit that opens trading at `price` (base per raw token, scaled by `WAD`)
+/// out of `base_available` and `tokens_available`: all of one side and as much of the
+/// other as the price allows. Rounding keeps the pool price at or just above `price`.
+pub fn pool_deposit(
+    base_available: u64,
+    tokens_available: u64,
+    price: u128,
+) -> std::result::Result<(u64, u64), CustomError> {
+    if price == 0 {
+        return Err(CustomError::MathOverflow);
+    }
+    let tokens_for_base =
+        mul_div(base_available as u128, WAD, price).ok_or(CustomError::MathOverflow)?;
+    if tokens_for_base <= tokens_available as u128 {
+        return Ok((base_available, tokens_for_base as u64));
+    }
+    let base_for_tokens =
+        mul_div_ceil(tokens_available as u128, price, WAD).ok_or(CustomError::MathOverflow)?;
+    Ok((
+        base_for_tokens.min(base_available as u128) as u64,
+        tokens_available,
+    ))
+}
+
+/// Migrates a graduated token into a constant-product AMM pool: pays the graduation fee out
+/// of the escrow like `withdraw_liquidity`, then seeds the pool with the remaining base and
+/// the unsold tokens at the curve's final spot price. Base the pool can't take goes to the
+/// admin; tokens it can't take are burned.
+pub fn migrate(ctx: Context<MigrateToAmm>) -> Result<()> {
+    require!(
+        ctx.accounts.xyber_token.is_graduated,
+        CustomError::BondingCurveNotGraduated
+    );
+    require!(
+        !ctx.accounts.xyber_token.migrated,
+        CustomError::AlreadyMigrated
+    );
+    require!(
+        !ctx.accounts.xyber_token.withdrawn,
+        CustomError::AlreadyWithdrawn
+    );
+
+    let bump = ctx.bumps.xyber_token;
+    let accounts = &ctx.accounts;
+
+    // 1) Graduation fees first, out of everything in escrow
+    let escrow_balance = accounts.escrow_token_account.amount;
+    let fees = split_graduation_fee(
+        escrow_balance,
+        accounts.xyber_core.graduation_protocol_fee_bps,
+        accounts.xyber_core.graduation_creator_fee_bps,
+    )?;
+    accounts.transfer_from_escrow(accounts.fee_vault.to_account_info(), fees.protocol, bump)?;
+    accounts.transfer_from_escrow(
+        accounts.creator_token_account.to_account_info(),
+        fees.creator,
+        bump,
+    )?;
+
+    // 2) Seed the pool at the final curve price
+    let base_available = escrow_balance - fees.protocol - fees.creator;
+    let tokens_available = accounts.vault_token_account.amount;
+    let xyber_token = &accounts.xyber_token;
+    let price = xyber_token
+        .bonding_curve
+        .spot_price(xyber_token.real_base_reserve)?;
+    let (base_liquidity, token_liquidity) = pool_deposit(base_available, tokens_available, price)?;
+    accounts.create_pool(base_liquidity, token_liquidity, bump)?;
+
+    // 3) Hand back what the pool couldn't take at that price
+    let base_leftover = base_available - base_liquidity;
+    let tokens_burned = tokens_available - token_liquidity;
+    accounts.transfer_from_escrow(
+        accounts.admin_token_account.to_account_info(),
+        base_leftover,
+        bump,
+    )?;
+    accounts.burn_from_vault(tokens_burned, bump)?;
+
+    let xyber_token = &mut ctx.accounts.xyber_token;
+    xyber_token.real_base_reserve = 0;
+    xyber_token.migrated = true;
+
+    emit!(PoolMigrated {
+        token_seed: ctx.accounts.token_seed.key(),
+        pool: ctx.accounts.pool.key(),
+        price,
+        protocol_fee: fees.protocol,
+        creator_fee: fees.creator,
+        base_liquidity,
+        token_liquidity,
+        base_leftover,
+        tokens_burned,
+    });
+
+    Ok(())
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_deposit() {
+        // 0.5 base per token: 100 base pairs with 200 of the 1_000 tokens.
+        assert_eq!(pool_deposit(100, 1_000, WAD / 2).unwrap(), (100, 200));
+
+        // 2 base per token: the 30 tokens take only 60 of the 100 base.
+        assert_eq!(pool_deposit(100, 30, 2 * WAD).unwrap(), (60, 30));
+
+        // Both sides fit exactly.
+        assert_eq!(pool_deposit(300, 100, 3 * WAD).unwrap(), (300, 100));
+
+        // The pool always opens at (or just above) the curve price.
+        for (base, tokens, price) in [(1_000_003, 7_777, WAD / 3), (17, 1_000_000, WAD + 1)] {
+            let (base_in, tokens_in) = pool_deposit(base, tokens, price).unwrap();
+            assert!(base_in <= base && tokens_in <= tokens);
+            assert!(base_in as u128 * WAD >= tokens_in as u128 * price);
+        }
+
+        assert!(matches!(
+            pool_deposit(1, 1, 0),
+            Err(CustomError::MathOverflow)
+        ));
+    }
+}
@@ -1,12 +1,18 @@
+use crate::curves::{BondingCurveTrait, BondingCurveType};
 use crate::errors::CustomError;
+use crate::events::CreationFeePaid;
+use crate::instructions::raw_grad_threshold;
 use crate::xyber_params;
-use crate::xyber_params::TokenParams;
+use crate::xyber_params::{TokenParams, MAX_TOTAL_CHAINS};
 use crate::XyberCore;
 use crate::XyberToken;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token;
 use anchor_spl::token::Mint;
 use anchor_spl::token::Token;
 use anchor_spl::token::TokenAccount;
+use anchor_spl::token::Transfer;
 use token_factory::cpi;
 use token_factory::cpi::accounts::CreateAndMintToken;
 
@@ -48,9 +54,44 @@ pub struct InitAndMint<'info> {
     )]
     pub escrow_token_account: Box<Account<'info, TokenAccount>>,
 
-    #[account()]
+    /// The core's accepted base mint; its decimals scale the graduation threshold.
+    #[account(address = xyber_core.accepted_base_mint @ CustomError::WrongPaymentMint)]
     pub payment_mint: Box<Account<'info, Mint>>,
 
+    /// Collects the creator's slice of every trade until the creator claims it.
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"creator_fee_vault", params.token_seed.as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = xyber_token,
+    )]
+    pub creator_fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Admin from xyber_core, receives a creation fee charged in lamports as the treasury
+    #[account(
+        mut,
+        address = xyber_core.admin
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Creator's base token account; only needed when the creation fee is in the base mint
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = creator,
+    )]
+    pub creator_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Protocol fee vault of the base mint; only needed when the creation fee is in it
+    #[account(
+        mut,
+        seeds = [b"fee_vault", payment_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<Box<Account<'info, TokenAccount>>>,
+
     /// CHECK: Metadata account created by the factory
     #[account(mut)]
     pub metadata_account: UncheckedAccount<'info>,
@@ -69,11 +110,86 @@ pub struct InitAndMint<'info> {
     pub token_factory_program: Program<'info, token_factory::program::TokenFactory>,
 }
 
+impl<'info> InitAndMint<'info> {
+    /// Charges the creator the core's creation fee, in lamports to the treasury or in the
+    /// accepted base mint to its `fee_vault`. Does nothing while the fee is 0.
+    fn collect_creation_fee(&self, token_seed: Pubkey) -> Result<()> {
+        let xyber_core = &self.xyber_core;
+        let amount = xyber_core.creation_fee;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let base_mint = if xyber_core.creation_fee_in_base_mint {
+            let (Some(creator_token_account), Some(fee_vault)) =
+                (&self.creator_token_account, &self.fee_vault)
+            else {
+                return err!(CustomError::MissingCreationFeeAccounts);
+            };
+            let cpi_ctx = CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: creator_token_account.to_account_info(),
+                    to: fee_vault.to_account_info(),
+                    authority: self.creator.to_account_info(),
+                },
+            );
+            token::transfer(cpi_ctx, amount)?;
+            Some(xyber_core.accepted_base_mint)
+        } else {
+            let cpi_ctx = CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.creator.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                },
+            );
+            system_program::transfer(cpi_ctx, amount)?;
+            None
+        };
+
+        emit!(CreationFeePaid {
+            token_seed,
+            creator: self.creator.key(),
+            amount,
+            base_mint,
+        });
+
+        Ok(())
+    }
+}
+
+/// Rejects a chain count outside `1..=MAX_TOTAL_CHAINS`, or one that raises the graduation
+/// threshold beyond what `bonding_curve` can ever take in, so the token could never graduate.
+pub fn validate_total_chains(
+    bonding_curve: &BondingCurveType,
+    grad_threshold: u64,
+    total_chains: u8,
+    base_decimals: u8,
+) -> std::result::Result<(), CustomError> {
+    if total_chains == 0 || total_chains > MAX_TOTAL_CHAINS {
+        return Err(CustomError::InvalidTotalChains);
+    }
+    let threshold = raw_grad_threshold(grad_threshold, total_chains, base_decimals)?;
+    bonding_curve
+        .buy_exact_input(0, threshold)
+        .map_err(|_| CustomError::UnreachableChainThreshold)?;
+    Ok(())
+}
+
 pub fn mint_full_supply_instruction(ctx: Context<InitAndMint>, params: TokenParams) -> Result<()> {
     let total_supply = ctx.accounts.xyber_core.total_supply;
 
     let token_seed_vec = params.token_seed.key().to_bytes().to_vec();
     require_eq!(token_seed_vec.len(), 32, CustomError::InvalidSeed);
+    validate_total_chains(
+        &ctx.accounts.xyber_core.bonding_curve,
+        ctx.accounts.xyber_core.grad_threshold,
+        params.total_chains,
+        ctx.accounts.payment_mint.decimals,
+    )?;
+
+    ctx.accounts.collect_creation_fee(params.token_seed)?;
 
     let cpi_accounts = CreateAndMintToken {
         payer: ctx.accounts.creator.to_account_info(),
@@ -108,6 +224,56 @@ pub fn mint_full_supply_instruction(ctx: Context<InitAndMint>, params: TokenPara
     xyber_token.vault = ctx.accounts.vault_token_account.key();
     xyber_token.creator = ctx.accounts.creator.key();
     xyber_token.total_chains = params.total_chains;
+    xyber_token.real_base_reserve = 0;
+    xyber_token.tokens_sold = 0;
+    xyber_token.total_protocol_fees = 0;
+    xyber_token.total_creator_fees = 0;
+    xyber_token.total_referral_fees = 0;
+    xyber_token.creator_fees_claimed = 0;
+
+    // Snapshot the launch terms; trades never read them from XyberCore again.
+    let xyber_core = &ctx.accounts.xyber_core;
+    xyber_token.bonding_curve = xyber_core.bonding_curve.clone();
+    xyber_token.grad_threshold = xyber_core.grad_threshold;
+    xyber_token.total_supply = total_supply;
+    xyber_token.launch_slot = Clock::get()?.slot;
+    xyber_token.sniper_fee_bps = xyber_core.sniper_fee_bps;
+    xyber_token.sniper_fee_decay_slots = xyber_core.sniper_fee_decay_slots;
+    xyber_token.sniper_fee_to_creator = xyber_core.sniper_fee_to_creator;
+    xyber_token.migrated = false;
+    xyber_token.withdrawn = false;
 
     Ok(())
 }
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::testing::constant_product_curve;
+
+    #[test]
+    fn test_validate_total_chains() {
+        // Selling the whole real reserve takes ~85 SOL.
+        let curve = BondingCurveType::ConstantProduct(constant_product_curve());
+        assert!(validate_total_chains(&curve, 60, 1, 9).is_ok());
+        // 60 SOL * 125% = 75 SOL is still reachable...
+        assert!(validate_total_chains(&curve, 60, 2, 9).is_ok());
+        // ...60 SOL * 150% = 90 SOL is not.
+        assert!(matches!(
+            validate_total_chains(&curve, 60, 3, 9),
+            Err(CustomError::UnreachableChainThreshold)
+        ));
+
+        assert!(matches!(
+            validate_total_chains(&curve, 1, 0, 9),
+            Err(CustomError::InvalidTotalChains)
+        ));
+        assert!(validate_total_chains(&curve, 1, MAX_TOTAL_CHAINS, 9).is_ok());
+        assert!(matches!(
+            validate_total_chains(&curve, 1, MAX_TOTAL_CHAINS + 1, 9),
+            Err(CustomError::InvalidTotalChains)
+        ));
+    }
+}
@@ -1,11 +1,114 @@
+pub mod claim_fees;
 pub mod core_states;
+pub mod migrate;
 pub mod mint;
+pub mod quote;
+pub mod sweep;
 pub mod trade_buy;
 pub mod trade_sell;
 pub mod withdraw;
 
+pub use claim_fees::*;
 pub use core_states::*;
+pub use migrate::*;
 pub use mint::*;
+pub use quote::*;
+pub use sweep::*;
 pub use trade_buy::*;
 pub use trade_sell::*;
 pub use withdraw::*;
+
+//==============================================================================
+/// Fixtures shared by the instruction tests.
+#[cfg(test)]
+pub(crate) mod testing {
+    use crate::curves::{
+        BondingCurveTrait, BondingCurveType, ConstantProductBondingCurve, SmoothBondingCurve,
+    };
+    use crate::{XyberCore, XyberToken};
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    /// One whole project token in raw units.
+    pub const TOKEN: u64 = 1_000_000_000;
+
+    /// Total supply (whole tokens) of the pump.fun setup.
+    pub const TOTAL_SUPPLY: u64 = 1_073_000_191;
+
+    /// The smooth curve of the pump.fun setup, as configured by the TS tests.
+    pub fn smooth_curve() -> SmoothBondingCurve {
+        SmoothBondingCurve {
+            a_total_tokens: TOTAL_SUPPLY * TOKEN,
+            k_virtual_pool_offset: 32_190_005_730 * (LAMPORTS_PER_SOL as u128) * (TOKEN as u128),
+            c_bonding_scale_factor: 30 * LAMPORTS_PER_SOL,
+        }
+    }
+
+    /// pump.fun reserves: the real token reserve sells out at ~85 SOL.
+    pub fn constant_product_curve() -> ConstantProductBondingCurve {
+        ConstantProductBondingCurve {
+            virtual_base_reserve: 30 * LAMPORTS_PER_SOL,
+            virtual_token_reserve: TOTAL_SUPPLY * TOKEN,
+            real_token_reserve: 793_100_000 * TOKEN,
+        }
+    }
+
+    /// A core launching `total_supply` tokens on `bonding_curve`, without fees or limits.
+    pub fn core(
+        bonding_curve: BondingCurveType,
+        total_supply: u64,
+        grad_threshold: u64,
+    ) -> XyberCore {
+        XyberCore {
+            admin: Pubkey::default(),
+            grad_threshold,
+            total_supply,
+            bonding_curve,
+            accepted_base_mint: Pubkey::default(),
+            max_price_impact_bps: 0,
+            protocol_fee_bps: 0,
+            creator_fee_bps: 0,
+            referral_share_bps: 0,
+            sniper_fee_bps: 0,
+            sniper_fee_decay_slots: 0,
+            sniper_fee_to_creator: false,
+            fee_tiers: Default::default(),
+            volume_window_slots: 0,
+            creation_fee: 0,
+            creation_fee_in_base_mint: false,
+            graduation_protocol_fee_bps: 0,
+            graduation_creator_fee_bps: 0,
+            total_protocol_fees: 0,
+            total_creator_fees: 0,
+            total_referral_fees: 0,
+        }
+    }
+
+    /// A token launched from `core` in `launch_slot`, with `reserve` already bought.
+    pub fn token(core: &XyberCore, launch_slot: u64, reserve: u64) -> XyberToken {
+        let curve = &core.bonding_curve;
+        let (tokens_sold, _) = curve.buy_exact_input(0, reserve).unwrap();
+        XyberToken {
+            is_graduated: false,
+            mint: Pubkey::default(),
+            vault: Pubkey::default(),
+            creator: Pubkey::default(),
+            total_chains: 1,
+            real_base_reserve: reserve,
+            tokens_sold,
+            bonding_curve: curve.clone(),
+            grad_threshold: core.grad_threshold,
+            total_supply: core.total_supply,
+            total_protocol_fees: 0,
+            total_creator_fees: 0,
+            total_referral_fees: 0,
+            creator_fees_claimed: 0,
+            launch_slot,
+            sniper_fee_bps: core.sniper_fee_bps,
+            sniper_fee_decay_slots: core.sniper_fee_decay_slots,
+            sniper_fee_to_creator: core.sniper_fee_to_creator,
+            migrated: false,
+            withdrawn: false,
+        }
+    }
+}
@@ -0,0 +1,644 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::curves::{BondingCurveTrait, BondingCurveType};
+use crate::errors::CustomError;
+use crate::fees::{
+    buy_fee_bps, resolve_referrer, split_buy_fee, split_fee, trader_fee_bps, FeeSplit,
+};
+use crate::instructions::{
+    check_price_impact, fill_buy_exact_input, fill_buy_exact_output, fill_sell_exact_input,
+    fill_sell_exact_output, raw_grad_threshold, BuyFill, SellFill,
+};
+use crate::{Referral, TraderStats, XyberCore, XyberToken};
+
+#[derive(Accounts)]
+pub struct QuoteTrade<'info> {
+    /// CHECK: Used solely as a seed for PDA derivation.
+    pub token_seed: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"xyber_core"],
+        bump
+    )]
+    pub xyber_core: Account<'info, XyberCore>,
+
+    #[account(
+        seeds = [b"xyber_token", token_seed.key().as_ref()],
+        bump
+    )]
+    pub xyber_token: Account<'info, XyberToken>,
+
+    /// The vault that holds the project's tokens; buys are quoted against its balance.
+    #[account(address = xyber_token.vault)]
+    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// The base mint; its decimals scale the graduation threshold.
+    #[account(address = xyber_core.accepted_base_mint)]
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: The trader being quoted; only used to derive `trader_stats` and `referral`.
+    pub trader: UncheckedAccount<'info>,
+
+    /// The trader's volume stats; quotes at the undiscounted fee without them.
+    #[account(
+        seeds = [b"trader_stats", trader.key().as_ref()],
+        bump
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+
+    /// The trader's recorded referrer, if they have one.
+    #[account(
+        seeds = [b"referral", trader.key().as_ref()],
+        bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    /// CHECK: The referrer the trade would pass along with its `referral_vault`.
+    /// Validated against the recorded one like the trade instructions do.
+    pub referrer: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> QuoteTrade<'info> {
+    /// The fee terms the trader gets in `slot`: their volume tier and, once the referrer
+    /// resolves as it would in a trade, the referral split.
+    fn quoted_trader(&self, slot: u64) -> Result<QuotedTrader> {
+        let volume = self.trader_stats.as_ref().map_or(0, |stats| {
+            stats.rolling_volume(self.xyber_core.volume_window_slots, slot)
+        });
+        let recorded = self
+            .referral
+            .as_ref()
+            .map_or(Pubkey::default(), |referral| referral.referrer);
+        let referrer = resolve_referrer(
+            recorded,
+            self.trader.key(),
+            self.referrer.as_ref().map(|referrer| referrer.key()),
+        )?;
+        Ok(QuotedTrader {
+            regular_fee_bps: trader_fee_bps(&self.xyber_core, volume)?,
+            referred: referrer.is_some(),
+        })
+    }
+}
+
+/// The fee terms of the trader a quote is for.
+#[derive(Clone, Copy, Debug)]
+pub struct QuotedTrader {
+    /// The trader's regular fee rate, discounted by their volume tier
+    pub regular_fee_bps: u64,
+    /// A referrer takes `referral_share_bps` of the protocol fee
+    pub referred: bool,
+}
+
+impl QuotedTrader {
+    /// Splits a trade's fees the way the trade instructions pay them out.
+    fn with_referral(
+        self,
+        fees: FeeSplit,
+        xyber_core: &XyberCore,
+    ) -> std::result::Result<FeeSplit, CustomError> {
+        if self.referred {
+            fees.with_referral(xyber_core.referral_share_bps)
+        } else {
+            Ok(fees)
+        }
+    }
+}
+
+/// The outcome of a trade against the current token state.
+///
+/// Returned through `set_return_data`, so simulating a quote instruction yields the
+/// same numbers the trade instruction would produce in the same slot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TradeQuote {
+    /// Base tokens moving on the curve: paid in (buys) or paid out (sells), before the fee
+    pub base_amount: u64,
+    // Fees paid on top of `base_amount` (buys) or taken out of it (sells)
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    /// The referrer's share, taken out of the protocol fee
+    pub referral_fee: u64,
+    /// Buys only: anti-sniper surcharge right after launch, on top of the fees above
+    pub sniper_fee: u64,
+    /// Project tokens (raw units) received (buys) or paid in (sells)
+    pub token_amount: u64,
+    /// `real_base_reserve` after the trade
+    pub new_reserve: u64,
+    // Spot prices are WAD-scaled base_tokens per raw token
+    pub spot_price_before: u128,
+    pub spot_price_after: u128,
+    /// How far the trade moves the spot price, in bps of `spot_price_before` (rounded up)
+    pub price_impact_bps: u64,
+    /// Tokens (raw units) still for sale on the curve after the trade
+    pub tokens_remaining: u64,
+    /// Buys only: the buy is capped at the tokens left for sale or at the graduation
+    /// threshold, and only `token_amount` for `base_amount` is filled
+    pub partial_fill: bool,
+}
+
+impl TradeQuote {
+    fn new(
+        curve: &BondingCurveType,
+        old_reserve: u64,
+        new_reserve: u64,
+        base_amount: u64,
+        fees: FeeSplit,
+        token_amount: u64,
+        partial_fill: bool,
+    ) -> std::result::Result<Self, CustomError> {
+        Ok(TradeQuote {
+            base_amount,
+            protocol_fee: fees.protocol,
+            creator_fee: fees.creator,
+            referral_fee: fees.referral,
+            sniper_fee: fees.sniper,
+            token_amount,
+            new_reserve,
+            spot_price_before: curve.spot_price(old_reserve)?,
+            spot_price_after: curve.spot_price(new_reserve)?,
+            price_impact_bps: curve.price_impact_bps(old_reserve, new_reserve)?,
+            tokens_remaining: curve.tokens_remaining(new_reserve)?,
+            partial_fill,
+        })
+    }
+}
+
+/// Quotes what `buy_exact_input_instruction` fills for `payment_amount` at the token's current
+/// state, with `vault_amount` tokens in the vault and the fee terms of `trader` in `slot`.
+pub fn quote_buy_exact_input(
+    xyber_token: &XyberToken,
+    xyber_core: &XyberCore,
+    vault_amount: u64,
+    base_decimals: u8,
+    trader: QuotedTrader,
+    slot: u64,
+    payment_amount: u64,
+) -> std::result::Result<TradeQuote, CustomError> {
+    let real_base_reserve = xyber_token.real_base_reserve;
+    let curve = &xyber_token.bonding_curve;
+    let max_tokens_out = curve.max_tokens_out(real_base_reserve)?.min(vault_amount);
+    let fill = fill_buy_exact_input(
+        curve,
+        real_base_reserve,
+        payment_amount,
+        max_base_in(xyber_token, base_decimals)?,
+        max_tokens_out,
+        buy_fee_bps(trader.regular_fee_bps, xyber_token, slot),
+    )?;
+    buy_quote(xyber_token, xyber_core, vault_amount, trader, fill)
+}
+
+/// Quotes what `buy_exact_output_instruction` fills for `tokens_out` at the token's current
+/// state, with `vault_amount` tokens in the vault and the fee terms of `trader` in `slot`.
+pub fn quote_buy_exact_output(
+    xyber_token: &XyberToken,
+    xyber_core: &XyberCore,
+    vault_amount: u64,
+    base_decimals: u8,
+    trader: QuotedTrader,
+    slot: u64,
+    tokens_out: u64,
+) -> std::result::Result<TradeQuote, CustomError> {
+    let fill = fill_buy_exact_output(
+        &xyber_token.bonding_curve,
+        xyber_token.real_base_reserve,
+        tokens_out,
+        max_base_in(xyber_token, base_decimals)?,
+        buy_fee_bps(trader.regular_fee_bps, xyber_token, slot),
+    )?;
+    buy_quote(xyber_token, xyber_core, vault_amount, trader, fill)
+}
+
+/// Quotes what `sell_exact_input_instruction` pays for `token_amount` at the token's
+/// current state and the fee terms of `trader`.
+pub fn quote_sell_exact_input(
+    xyber_token: &XyberToken,
+    xyber_core: &XyberCore,
+    trader: QuotedTrader,
+    token_amount: u64,
+) -> std::result::Result<TradeQuote, CustomError> {
+    let fill = fill_sell_exact_input(
+        &xyber_token.bonding_curve,
+        xyber_token.real_base_reserve,
+        token_amount,
+        trader.regular_fee_bps,
+    )?;
+    sell_quote(xyber_token, xyber_core, trader, fill)
+}
+
+/// Quotes the tokens `sell_exact_output_instruction` takes to pay out `base_amount_out`
+/// after the fee, at the token's current state and the fee terms of `trader`.
+pub fn quote_sell_exact_output(
+    xyber_token: &XyberToken,
+    xyber_core: &XyberCore,
+    trader: QuotedTrader,
+    base_amount_out: u64,
+) -> std::result::Result<TradeQuote, CustomError> {
+    let fill = fill_sell_exact_output(
+        &xyber_token.bonding_curve,
+        xyber_token.real_base_reserve,
+        base_amount_out,
+        trader.regular_fee_bps,
+    )?;
+    sell_quote(xyber_token, xyber_core, trader, fill)
+}
+
+/// Base a buy can still put into the curve before the token graduates.
+fn max_base_in(
+    xyber_token: &XyberToken,
+    base_decimals: u8,
+) -> std::result::Result<u64, CustomError> {
+    Ok(raw_grad_threshold(
+        xyber_token.grad_threshold,
+        xyber_token.total_chains,
+        base_decimals,
+    )?
+    .saturating_sub(xyber_token.real_base_reserve))
+}
+
+/// The quote of a buy filled as `fill`, checked against the price impact limit and the vault
+/// like the buy instructions.
+fn buy_quote(
+    xyber_token: &XyberToken,
+    xyber_core: &XyberCore,
+    vault_amount: u64,
+    trader: QuotedTrader,
+    fill: BuyFill,
+) -> std::result::Result<TradeQuote, CustomError> {
+    check_price_impact(
+        &xyber_token.bonding_curve,
+        xyber_token.real_base_reserve,
+        fill.new_reserve,
+        xyber_core.max_price_impact_bps,
+    )?;
+    if fill.tokens_out > vault_amount {
+        return Err(CustomError::InsufficientTokenVaultBalance);
+    }
+
+    let fees = split_buy_fee(
+        fill.fee,
+        fill.base_in,
+        trader.regular_fee_bps,
+        xyber_core.protocol_fee_bps,
+        xyber_core.creator_fee_bps,
+    )?;
+    TradeQuote::new(
+        &xyber_token.bonding_curve,
+        xyber_token.real_base_reserve,
+        fill.new_reserve,
+        fill.base_in,
+        trader.with_referral(fees, xyber_core)?,
+        fill.tokens_out,
+        fill.partial,
+    )
+}
+
+/// The quote of a sell filled as `fill`, checked against the price impact limit like the sell
+/// instructions; only tokens sold out of the vault can be sold back.
+fn sell_quote(
+    xyber_token: &XyberToken,
+    xyber_core: &XyberCore,
+    trader: QuotedTrader,
+    fill: SellFill,
+) -> std::result::Result<TradeQuote, CustomError> {
+    check_price_impact(
+        &xyber_token.bonding_curve,
+        xyber_token.real_base_reserve,
+        fill.new_reserve,
+        xyber_core.max_price_impact_bps,
+    )?;
+    if fill.tokens_in > xyber_token.tokens_sold {
+        return Err(CustomError::InsufficientTokenSupply);
+    }
+
+    let fees = split_fee(
+        fill.fee,
+        xyber_core.protocol_fee_bps,
+        xyber_core.creator_fee_bps,
+    )?;
+    TradeQuote::new(
+        &xyber_token.bonding_curve,
+        xyber_token.real_base_reserve,
+        fill.new_reserve,
+        fill.base_out,
+        trader.with_referral(fees, xyber_core)?,
+        fill.tokens_in,
+        false,
+    )
+}
+
+/// Quotes a buy without executing it. With `exact_input`, `amount` is the base paid in
+/// (as in `buy_exact_input_instruction`); otherwise it is the tokens out
+/// (as in `buy_exact_output_instruction`).
+pub fn quote_buy_instruction(
+    ctx: Context<QuoteTrade>,
+    amount: u64,
+    exact_input: bool,
+) -> Result<TradeQuote> {
+    let accounts = &ctx.accounts;
+    require!(
+        !accounts.xyber_token.is_graduated,
+        CustomError::TokenIsGraduated
+    );
+
+    let slot = Clock::get()?.slot;
+    let quote = if exact_input {
+        quote_buy_exact_input
+    } else {
+        quote_buy_exact_output
+    };
+    Ok(quote(
+        &accounts.xyber_token,
+        &accounts.xyber_core,
+        accounts.vault_token_account.amount,
+        accounts.payment_mint.decimals,
+        accounts.quoted_trader(slot)?,
+        slot,
+        amount,
+    )?)
+}
+
+/// Quotes a sell without executing it. With `exact_input`, `amount` is the tokens paid in
+/// (as in `sell_exact_input_instruction`); otherwise it is the base out
+/// (as in `sell_exact_output_instruction`).
+pub fn quote_sell_instruction(
+    ctx: Context<QuoteTrade>,
+    amount: u64,
+    exact_input: bool,
+) -> Result<TradeQuote> {
+    let accounts = &ctx.accounts;
+    require!(
+        !accounts.xyber_token.is_graduated,
+        CustomError::TokenIsGraduated
+    );
+
+    let quote = if exact_input {
+        quote_sell_exact_input
+    } else {
+        quote_sell_exact_output
+    };
+    Ok(quote(
+        &accounts.xyber_token,
+        &accounts.xyber_core,
+        accounts.quoted_trader(Clock::get()?.slot)?,
+        amount,
+    )?)
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::testing::{self, smooth_curve, TOKEN, TOTAL_SUPPLY};
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    const LAUNCH_SLOT: u64 = 1_000;
+
+    /// Two slots into the 10-slot decay from 20% to 1.5%: 150 + ceil(1_850 * 8 / 10) bps.
+    const SNIPER_SLOT_FEE_BPS: u64 = 1_630;
+
+    /// A core with trading fees, a 20% referral share and an anti-sniper fee decaying over
+    /// 10 slots.
+    fn core() -> XyberCore {
+        XyberCore {
+            protocol_fee_bps: 100,
+            creator_fee_bps: 50,
+            referral_share_bps: 2_000,
+            sniper_fee_bps: 2_000,
+            sniper_fee_decay_slots: 10,
+            ..testing::core(BondingCurveType::Smooth(smooth_curve()), TOTAL_SUPPLY, 85)
+        }
+    }
+
+    fn token(core: &XyberCore, reserve: u64) -> XyberToken {
+        testing::token(core, LAUNCH_SLOT, reserve)
+    }
+
+    fn vault_amount(token: &XyberToken) -> u64 {
+        token.total_supply * TOKEN - token.tokens_sold
+    }
+
+    fn trader(referred: bool) -> QuotedTrader {
+        QuotedTrader {
+            regular_fee_bps: 150,
+            referred,
+        }
+    }
+
+    /// `amount * bps / 10_000`, rounded up.
+    fn bps_ceil(amount: u64, bps: u64) -> u64 {
+        (amount as u128 * bps as u128).div_ceil(10_000) as u64
+    }
+
+    /// Checks the fees of `quote` on `base_amount`: the regular 1.5% split 2:1 between the
+    /// protocol and the creator, 20% of the protocol's share to a referrer when `referred`,
+    /// and whatever `fee` exceeds the regular fee as the anti-sniper surcharge.
+    fn assert_fees(quote: &TradeQuote, base_amount: u64, fee: u64, referred: bool) {
+        let regular = bps_ceil(base_amount, 150);
+        let creator = regular / 3;
+        let referral = if referred { (regular - creator) / 5 } else { 0 };
+        assert_eq!(
+            (
+                quote.protocol_fee,
+                quote.creator_fee,
+                quote.referral_fee,
+                quote.sniper_fee
+            ),
+            (
+                regular - creator - referral,
+                creator,
+                referral,
+                fee - regular
+            )
+        );
+    }
+
+    /// Checks the prices of `quote` against the curve before and after the trade.
+    fn assert_prices(quote: &TradeQuote, token: &XyberToken) {
+        let curve = &token.bonding_curve;
+        let before = curve.spot_price(token.real_base_reserve).unwrap();
+        let after = curve.spot_price(quote.new_reserve).unwrap();
+        assert_eq!(
+            (quote.spot_price_before, quote.spot_price_after),
+            (before, after)
+        );
+        assert_eq!(
+            quote.price_impact_bps as u128,
+            (before.abs_diff(after) * 10_000).div_ceil(before)
+        );
+        assert_eq!(
+            quote.tokens_remaining,
+            curve.tokens_remaining(quote.new_reserve).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_quote_buy() {
+        let core = core();
+        let token = token(&core, 10 * LAMPORTS_PER_SOL);
+        let (curve, reserve, vault) = (
+            &token.bonding_curve,
+            token.real_base_reserve,
+            vault_amount(&token),
+        );
+        let threshold = 85 * LAMPORTS_PER_SOL;
+        let slot = LAUNCH_SLOT + 2;
+
+        // Exact input: 1 SOL pays for the most base whose fee still fits in it.
+        let payment = LAMPORTS_PER_SOL;
+        let base = (payment as u128 * 10_000 / (10_000 + SNIPER_SLOT_FEE_BPS as u128)) as u64;
+        let fee = bps_ceil(base, SNIPER_SLOT_FEE_BPS);
+        assert!(
+            base + fee <= payment && base + 1 + bps_ceil(base + 1, SNIPER_SLOT_FEE_BPS) > payment
+        );
+        for referred in [false, true] {
+            let quote =
+                quote_buy_exact_input(&token, &core, vault, 9, trader(referred), slot, payment)
+                    .unwrap();
+            let (tokens, new_reserve) = curve.buy_exact_input(reserve, base).unwrap();
+            assert_eq!(
+                (quote.base_amount, quote.token_amount, quote.new_reserve),
+                (base, tokens, new_reserve)
+            );
+            assert!(!quote.partial_fill);
+            assert_fees(&quote, base, fee, referred);
+            assert_prices(&quote, &token);
+        }
+
+        // Exact input over the threshold: only the base up to the threshold is filled.
+        let quote = quote_buy_exact_input(
+            &token,
+            &core,
+            vault,
+            9,
+            trader(false),
+            slot,
+            100 * LAMPORTS_PER_SOL,
+        )
+        .unwrap();
+        let base = threshold - reserve;
+        let (tokens, _) = curve.buy_exact_input(reserve, base).unwrap();
+        assert_eq!(
+            (quote.base_amount, quote.token_amount, quote.new_reserve),
+            (base, tokens, threshold)
+        );
+        assert!(quote.partial_fill);
+        assert_fees(&quote, base, bps_ceil(base, SNIPER_SLOT_FEE_BPS), false);
+
+        // Exact output: 1M tokens cost what the curve charges for them.
+        let tokens = 1_000_000 * TOKEN;
+        let quote =
+            quote_buy_exact_output(&token, &core, vault, 9, trader(true), slot, tokens).unwrap();
+        let (base, new_reserve) = curve.buy_exact_output(reserve, tokens).unwrap();
+        assert_eq!(
+            (quote.base_amount, quote.token_amount, quote.new_reserve),
+            (base, tokens, new_reserve)
+        );
+        assert!(!quote.partial_fill);
+        assert_fees(&quote, base, bps_ceil(base, SNIPER_SLOT_FEE_BPS), true);
+        assert_prices(&quote, &token);
+
+        // Exact output past the threshold: only what the threshold buys is filled.
+        let (tokens_to_90_sol, _) = curve.buy_exact_input(0, 90 * LAMPORTS_PER_SOL).unwrap();
+        let quote = quote_buy_exact_output(
+            &token,
+            &core,
+            vault,
+            9,
+            trader(false),
+            slot,
+            tokens_to_90_sol - token.tokens_sold,
+        )
+        .unwrap();
+        let (tokens, _) = curve.buy_exact_input(reserve, threshold - reserve).unwrap();
+        assert_eq!(
+            (quote.base_amount, quote.token_amount, quote.new_reserve),
+            (threshold - reserve, tokens, threshold)
+        );
+        assert!(quote.partial_fill);
+
+        // A vault short of the tokens fails like the buy instructions.
+        assert!(matches!(
+            quote_buy_exact_output(&token, &core, TOKEN, 9, trader(false), slot, 2 * TOKEN),
+            Err(CustomError::InsufficientTokenVaultBalance)
+        ));
+    }
+
+    #[test]
+    fn test_quote_sell() {
+        let core = core();
+        let token = token(&core, 10 * LAMPORTS_PER_SOL);
+        let (curve, reserve) = (&token.bonding_curve, token.real_base_reserve);
+
+        // Exact input: the 1.5% fee comes out of the curve proceeds.
+        let tokens_in = token.tokens_sold / 3;
+        let (base, new_reserve) = curve.sell_exact_input(reserve, tokens_in).unwrap();
+        for referred in [false, true] {
+            let quote = quote_sell_exact_input(&token, &core, trader(referred), tokens_in).unwrap();
+            assert_eq!(
+                (quote.base_amount, quote.token_amount, quote.new_reserve),
+                (base, tokens_in, new_reserve)
+            );
+            assert!(!quote.partial_fill);
+            assert_fees(&quote, base, bps_ceil(base, 150), referred);
+            assert_prices(&quote, &token);
+        }
+
+        // Exact output: the curve pays out the least base that nets 1 SOL after the fee.
+        let net = LAMPORTS_PER_SOL;
+        let base = (net as u128 * 10_000).div_ceil(9_850) as u64;
+        assert!(base - bps_ceil(base, 150) >= net && base - 1 - bps_ceil(base - 1, 150) < net);
+        let quote = quote_sell_exact_output(&token, &core, trader(true), net).unwrap();
+        let (tokens_in, new_reserve) = curve.sell_exact_output(reserve, base).unwrap();
+        assert_eq!(
+            (quote.base_amount, quote.token_amount, quote.new_reserve),
+            (base, tokens_in, new_reserve)
+        );
+        assert_fees(&quote, base, base - net, true);
+        assert_prices(&quote, &token);
+
+        // Only tokens sold out of the vault can be sold back.
+        assert!(matches!(
+            quote_sell_exact_input(&token, &core, trader(false), token.tokens_sold + 1),
+            Err(CustomError::InsufficientTokenSupply)
+        ));
+    }
+
+    #[test]
+    fn test_quote_price_impact_limit() {
+        let mut core = core();
+        let token = token(&core, 10 * LAMPORTS_PER_SOL);
+        let vault = vault_amount(&token);
+        let slot = LAUNCH_SLOT + 20;
+        let buy = |core: &XyberCore| {
+            quote_buy_exact_input(
+                &token,
+                core,
+                vault,
+                9,
+                trader(false),
+                slot,
+                LAMPORTS_PER_SOL,
+            )
+        };
+        let sell = |core: &XyberCore| {
+            quote_sell_exact_input(&token, core, trader(false), token.tokens_sold / 3)
+        };
+        let buy_impact = buy(&core).unwrap().price_impact_bps;
+        let sell_impact = sell(&core).unwrap().price_impact_bps;
+        assert!(buy_impact > 0 && sell_impact > 0);
+
+        // Quotes fail exactly where the trades would: just past the core's limit.
+        core.max_price_impact_bps = buy_impact;
+        assert!(buy(&core).is_ok());
+        core.max_price_impact_bps = buy_impact - 1;
+        assert!(matches!(buy(&core), Err(CustomError::PriceImpactExceeded)));
+
+        core.max_price_impact_bps = sell_impact;
+        assert!(sell(&core).is_ok());
+        core.max_price_impact_bps = sell_impact - 1;
+        assert!(matches!(sell(&core), Err(CustomError::PriceImpactExceeded)));
+    }
+}
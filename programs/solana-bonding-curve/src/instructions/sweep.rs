@@ -0,0 +1,98 @@
+use crate::errors::CustomError;
+use crate::events::DonationsSwept;
+use crate::{XyberCore, XyberToken};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct SweepDonations<'info> {
+    #[account(
+        seeds = [b"xyber_core"],
+        bump
+    )]
+    pub xyber_core: Account<'info, XyberCore>,
+
+    /// CHECK: Admin from xyber_core, receives the swept base tokens as the treasury
+    #[account(
+        address = xyber_core.admin,
+        mut,
+        signer
+    )]
+    pub admin: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"xyber_token", token_seed.key().as_ref()],
+        bump
+    )]
+    pub xyber_token: Account<'info, XyberToken>,
+
+    /// CHECK: Used only for PDA seed derivation
+    pub token_seed: UncheckedAccount<'info>,
+
+    /// Escrow token account holding the payment tokens (e.g. USDC)
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = xyber_token,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        address = xyber_core.accepted_base_mint
+    )]
+    pub base_token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = admin
+    )]
+    pub admin_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves base tokens that were sent straight to the escrow (and are therefore not part
+/// of `real_base_reserve`) to the treasury. The curve reserve itself is never touched.
+pub fn sweep_donations_instruction(ctx: Context<SweepDonations>) -> Result<()> {
+    let real_base_reserve = ctx.accounts.xyber_token.real_base_reserve;
+    let donations = ctx
+        .accounts
+        .escrow_token_account
+        .amount
+        .checked_sub(real_base_reserve)
+        .ok_or(CustomError::InsufficientEscrowBalance)?;
+    require!(donations > 0, CustomError::NothingToSweep);
+
+    let bump = ctx.bumps.xyber_token;
+    let seeds = &[
+        b"xyber_token".as_ref(),
+        ctx.accounts.token_seed.key.as_ref(),
+        &[bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_ctx_escrow = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.escrow_token_account.to_account_info(),
+            to: ctx.accounts.admin_token_account.to_account_info(),
+            authority: ctx.accounts.xyber_token.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx_escrow, donations)?;
+
+    emit!(DonationsSwept {
+        token_seed: ctx.accounts.token_seed.key(),
+        treasury: ctx.accounts.admin.key(),
+        amount: donations,
+        real_base_reserve,
+    });
+
+    Ok(())
+}
@@ -5,9 +5,17 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 use crate::curves::BondingCurveTrait;
 use crate::errors::CustomError;
+use crate::events::FeeCollected;
 use crate::events::GraduationTriggered;
+use crate::events::ReferralRecorded;
 use crate::events::XyberInstructionType;
 use crate::events::XyberSwapEvent;
+use crate::fees::{
+    amount_before_fee, buy_fee_bps, fee_on, resolve_referrer, split_buy_fee, trader_fee_bps,
+    FeeSplit,
+};
+use crate::Referral;
+use crate::TraderStats;
 use crate::XyberCore;
 use crate::XyberToken;
 
@@ -68,6 +76,61 @@ pub struct BuyToken<'info> {
     )]
     pub buyer_payment_account: Box<Account<'info, TokenAccount>>,
 
+    /// Collects the protocol's trading fees in the base mint.
+    #[account(
+        mut,
+        seeds = [b"fee_vault", payment_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Collects the creator's slice of this token's trades.
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", token_seed.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Records the buyer's referrer; the first one sticks.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        seeds = [b"referral", buyer.key().as_ref()],
+        bump,
+        space = Referral::LEN
+    )]
+    pub referral: Box<Account<'info, Referral>>,
+
+    /// CHECK: Referrer of the buyer, required once one is recorded; only its key is used.
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    /// The referrer's share of the trading fees in the base mint, claimable by the referrer.
+    /// Only used together with `referrer`.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        seeds = [
+            b"referral_vault",
+            referrer.as_ref().map(|r| r.key()).unwrap_or_default().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump,
+        token::mint = payment_mint,
+        token::authority = xyber_core
+    )]
+    pub referral_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The buyer's rolling volume for the fee tiers.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        seeds = [b"trader_stats", buyer.key().as_ref()],
+        bump,
+        space = TraderStats::LEN
+    )]
+    pub trader_stats: Box<Account<'info, TraderStats>>,
+
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
 
@@ -76,6 +139,221 @@ pub struct BuyToken<'info> {
     pub system_program: UncheckedAccount<'info>,
 }
 
+impl<'info> BuyToken<'info> {
+    /// Transfers the buyer’s payment from `buyer_payment_account` -> `escrow_token_account`.
+    fn transfer_payment_to_escrow(&self, payment_amount: u64) -> Result<()> {
+        let transfer_payment_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.buyer_payment_account.to_account_info(),
+                to: self.escrow_token_account.to_account_info(),
+                authority: self.buyer.to_account_info(),
+            },
+        );
+        token::transfer(transfer_payment_ctx, payment_amount)
+    }
+
+    /// Transfers `amount` from `buyer_payment_account` -> `to`.
+    fn transfer_from_buyer(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let transfer_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.buyer_payment_account.to_account_info(),
+                to,
+                authority: self.buyer.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, amount)
+    }
+
+    /// Resolves who referred the buyer, recording the first referrer for good. Only a
+    /// `referrer` passed along with its `referral_vault` counts, and once one is recorded,
+    /// every trade has to pass it.
+    fn record_referrer(&mut self) -> Result<Option<Pubkey>> {
+        let offered = match (&self.referrer, &self.referral_vault) {
+            (Some(referrer), Some(_)) => Some(referrer.key()),
+            _ => None,
+        };
+        let referrer = resolve_referrer(self.referral.referrer, self.buyer.key(), offered)?;
+        if let Some(referrer) = referrer {
+            if self.referral.referrer == Pubkey::default() {
+                self.referral.referrer = referrer;
+                emit!(ReferralRecorded {
+                    user: self.buyer.key(),
+                    referrer,
+                });
+            }
+        }
+        Ok(referrer)
+    }
+
+    /// The buyer's regular fee rate, discounted by their volume tier.
+    fn regular_fee_bps(&self) -> Result<u64> {
+        let volume = self
+            .trader_stats
+            .rolling_volume(self.xyber_core.volume_window_slots, Clock::get()?.slot);
+        Ok(trader_fee_bps(&self.xyber_core, volume)?)
+    }
+
+    /// Adds the buy's base (before fees) to the buyer's rolling volume.
+    fn record_volume(&mut self, base_amount: u64) -> Result<()> {
+        let window_slots = self.xyber_core.volume_window_slots;
+        self.trader_stats
+            .record_volume(window_slots, Clock::get()?.slot, base_amount);
+        Ok(())
+    }
+
+    /// The fee rate of a buy in the current slot: the buyer's regular rate, anti-sniper
+    /// fee included.
+    fn fee_bps(&self) -> Result<u64> {
+        Ok(buy_fee_bps(
+            self.regular_fee_bps()?,
+            &self.xyber_token,
+            Clock::get()?.slot,
+        ))
+    }
+
+    /// Splits the fee of a buy of `base_in` and transfers the protocol's share to `fee_vault`,
+    /// the creator's to `creator_fee_vault` and the referrer's, if any, to `referral_vault`.
+    /// The anti-sniper surcharge goes to the creator or the protocol, as the token says.
+    fn collect_fees(
+        &mut self,
+        fee: u64,
+        base_in: u64,
+        referrer: Option<Pubkey>,
+    ) -> Result<FeeSplit> {
+        let xyber_core = &self.xyber_core;
+        let mut fees = split_buy_fee(
+            fee,
+            base_in,
+            self.regular_fee_bps()?,
+            xyber_core.protocol_fee_bps,
+            xyber_core.creator_fee_bps,
+        )?;
+        if referrer.is_some() {
+            fees = fees.with_referral(xyber_core.referral_share_bps)?;
+        }
+        let (protocol_amount, creator_amount) = if self.xyber_token.sniper_fee_to_creator {
+            (fees.protocol, fees.creator + fees.sniper)
+        } else {
+            (fees.protocol + fees.sniper, fees.creator)
+        };
+        self.transfer_from_buyer(self.fee_vault.to_account_info(), protocol_amount)?;
+        self.transfer_from_buyer(self.creator_fee_vault.to_account_info(), creator_amount)?;
+        if let Some(referral_vault) = &self.referral_vault {
+            self.transfer_from_buyer(referral_vault.to_account_info(), fees.referral)?;
+        }
+
+        self.record_fees(fees, protocol_amount, creator_amount, referrer)?;
+        Ok(fees)
+    }
+
+    /// Adds the fees paid into each vault to the token's and the core's totals and emits
+    /// `FeeCollected`.
+    fn record_fees(
+        &mut self,
+        fees: FeeSplit,
+        protocol_amount: u64,
+        creator_amount: u64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        self.xyber_core
+            .record_fees(protocol_amount, creator_amount, fees.referral)?;
+        let xyber_token = &mut self.xyber_token;
+        xyber_token.record_fees(protocol_amount, creator_amount, fees.referral)?;
+
+        emit!(FeeCollected {
+            token_seed: self.token_seed.key(),
+            user: self.buyer.key(),
+            base_mint: self.payment_mint.key(),
+            protocol_fee: fees.protocol,
+            creator_fee: fees.creator,
+            referrer,
+            referral_fee: fees.referral,
+            sniper_fee: fees.sniper,
+            sniper_fee_to_creator: xyber_token.sniper_fee_to_creator,
+            total_protocol_fees: xyber_token.total_protocol_fees,
+            total_creator_fees: xyber_token.total_creator_fees,
+            total_referral_fees: xyber_token.total_referral_fees,
+        });
+        Ok(())
+    }
+
+    /// Transfers `token_amount` (raw units) from the vault to the buyer, signed by the XyberToken PDA.
+    fn transfer_tokens_to_buyer(&self, token_amount: u64, xyber_token_bump: u8) -> Result<()> {
+        let token_seed_key = self.token_seed.key();
+        let seeds: [&[u8]; 3] = [b"xyber_token", token_seed_key.as_ref(), &[xyber_token_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let vault_transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.vault_token_account.to_account_info(),
+                to: self.buyer_token_account.to_account_info(),
+                authority: self.xyber_token.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(vault_transfer_ctx, token_amount)
+    }
+
+    /// Records a buy in the token's internal accounting: the curve moves to `new_reserve`
+    /// and `token_amount` more tokens are sold out of the vault.
+    fn record_buy(&mut self, new_reserve: u64, token_amount: u64) -> Result<()> {
+        let xyber_token = &mut self.xyber_token;
+        xyber_token.real_base_reserve = new_reserve;
+        xyber_token.tokens_sold = xyber_token
+            .tokens_sold
+            .checked_add(token_amount)
+            .ok_or(CustomError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// The reserve (raw base units) at which this token graduates.
+    fn raw_grad_threshold(&self) -> Result<u64> {
+        Ok(raw_grad_threshold(
+            self.xyber_token.grad_threshold,
+            self.xyber_token.total_chains,
+            self.payment_mint.decimals,
+        )?)
+    }
+
+    /// Marks the token as graduated once the reserve reaches the chain-adjusted threshold.
+    ///
+    /// Only the internally tracked reserve counts, so donations sent straight to the
+    /// escrow cannot trigger graduation.
+    fn graduate_if_threshold_reached(&mut self) -> Result<()> {
+        let real_base_reserve = self.xyber_token.real_base_reserve;
+
+        if real_base_reserve >= self.raw_grad_threshold()? {
+            self.xyber_token.is_graduated = true;
+
+            let curve = &self.xyber_token.bonding_curve;
+            let raw_total_supply = self
+                .xyber_token
+                .total_supply
+                .checked_mul(10_u64.pow(self.mint.decimals as u32))
+                .ok_or(CustomError::MathOverflow)?;
+            emit!(GraduationTriggered {
+                buyer: self.buyer.key(),
+                escrow_balance: real_base_reserve,
+                market_cap: curve.market_cap(real_base_reserve)?,
+                fully_diluted_valuation: curve
+                    .fully_diluted_valuation(real_base_reserve, raw_total_supply)?,
+                vault: self.vault_token_account.key(),
+                creator: self.xyber_token.creator.key(),
+                escrow: self.escrow_token_account.key(),
+                token_seed: self.token_seed.key(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
 pub fn buy_exact_input_instruction(
     ctx: Context<BuyToken>,
     payment_amount: u64,
@@ -93,103 +371,357 @@ pub fn buy_exact_input_instruction(
         CustomError::WrongPaymentMint
     );
 
-    let escrow_balance = ctx.accounts.escrow_token_account.amount;
+    let real_base_reserve = ctx.accounts.xyber_token.real_base_reserve;
 
-    // 1) Determine the token amount for `payment_amount`.
-    let (actual_tokens_out, _new_x) = ctx
+    // 1) Determine the token amount for `payment_amount`, filling at most what the curve
+    //    and the vault can still sell and stopping exactly at the graduation threshold.
+    //    A partial fill only charges the base it needs.
+    let max_base_in = ctx
         .accounts
-        .xyber_core
-        .bonding_curve
-        .buy_exact_input(escrow_balance, payment_amount)?;
+        .raw_grad_threshold()?
+        .saturating_sub(real_base_reserve);
+    let fee_bps = ctx.accounts.fee_bps()?;
+    let curve = &ctx.accounts.xyber_token.bonding_curve;
+    let max_tokens_out = curve
+        .max_tokens_out(real_base_reserve)?
+        .min(ctx.accounts.vault_token_account.amount);
+    let fill = fill_buy_exact_input(
+        curve,
+        real_base_reserve,
+        payment_amount,
+        max_base_in,
+        max_tokens_out,
+        fee_bps,
+    )?;
+    let total_paid = fill.total_paid()?;
+    let (actual_tokens_out, new_reserve) = (fill.tokens_out, fill.new_reserve);
 
     msg!(
         "buy_exact_input actual_tokens_out = {:?}",
         actual_tokens_out
     );
     msg!("Vault amount = {}", ctx.accounts.vault_token_account.amount);
+    if fill.partial {
+        msg!("Partial fill: charged {} of {}", total_paid, payment_amount);
+    }
 
-    // 2) Enforce `actual_tokens_out >= min_amount_out`.
+    // 2) Enforce `actual_tokens_out >= min_amount_out`, scaled down to the base actually
+    //    charged when the buy is only partially filled.
     //    (The front end will handle slippage and supply a proper `min_amount_out`.)
     require!(
-        actual_tokens_out >= min_amount_out,
+        (actual_tokens_out as u128) * (payment_amount as u128)
+            >= (min_amount_out as u128) * (total_paid as u128),
         CustomError::SlippageExceeded
     );
 
+    // 2b) Enforce the core's price-impact limit.
+    check_price_impact(
+        &ctx.accounts.xyber_token.bonding_curve,
+        real_base_reserve,
+        new_reserve,
+        ctx.accounts.xyber_core.max_price_impact_bps,
+    )?;
+
     // 3) Check vault balance.
     require!(
         actual_tokens_out <= ctx.accounts.vault_token_account.amount,
         CustomError::InsufficientTokenVaultBalance
     );
 
-    // 4) Transfer the buyer’s payment from `buyer_payment_account` -> `escrow_token_account`.
-    let transfer_payment_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.buyer_payment_account.to_account_info(),
-            to: ctx.accounts.escrow_token_account.to_account_info(),
-            authority: ctx.accounts.buyer.to_account_info(),
-        },
+    // 4) Transfer the curve cost to `escrow_token_account` and the fees to their vaults;
+    //    whatever a partial fill did not use stays with the buyer.
+    ctx.accounts.transfer_payment_to_escrow(fill.base_in)?;
+    let referrer = ctx.accounts.record_referrer()?;
+    let fees = ctx
+        .accounts
+        .collect_fees(fill.fee, fill.base_in, referrer)?;
+
+    // 5) Update the reserve accounting and the buyer's volume, and graduate once the
+    //    threshold is reached.
+    ctx.accounts.record_buy(new_reserve, actual_tokens_out)?;
+    ctx.accounts.record_volume(fill.base_in)?;
+    ctx.accounts.graduate_if_threshold_reached()?;
+
+    // 6) Transfer `actual_tokens_out` (raw units) from the vault to the buyer.
+    ctx.accounts
+        .transfer_tokens_to_buyer(actual_tokens_out, ctx.bumps.xyber_token)?;
+
+    let curve = &ctx.accounts.xyber_token.bonding_curve;
+    emit!(XyberSwapEvent {
+        ix_type: XyberInstructionType::BuyExactIn,
+        token_seed: ctx.accounts.token_seed.key(),
+        user: ctx.accounts.buyer.key(),
+        base_amount: fill.base_in,
+        token_amount: actual_tokens_out,
+        vault_token_amount: real_base_reserve,
+        spot_price: curve.spot_price(new_reserve)?,
+        market_cap: curve.market_cap(new_reserve)?,
+        tokens_remaining: curve.tokens_remaining(new_reserve)?,
+        partial_fill: fill.partial,
+        base_amount_unfilled: fill.base_unfilled,
+        protocol_fee: fees.protocol,
+        creator_fee: fees.creator,
+        referrer,
+        referral_fee: fees.referral,
+        sniper_fee: fees.sniper,
+        fee_bps,
+    });
+
+    Ok(())
+}
+
+/// Buys an *exact output* of project tokens, paying whatever base amount the curve requires.
+/// Enforces a “maximum” base tokens in to guard against slippage.
+pub fn buy_exact_output_instruction(
+    ctx: Context<BuyToken>,
+    tokens_out: u64,
+    max_payment_amount: u64, // slippage guard
+) -> Result<()> {
+    // 0) Reject if graduated.
+    require!(
+        !ctx.accounts.xyber_token.is_graduated,
+        CustomError::TokenIsGraduated
     );
 
-    token::transfer(transfer_payment_ctx, payment_amount)?;
-    let updated_escrow_balance = escrow_balance
-        .checked_add(payment_amount)
-        .ok_or(CustomError::MathOverflow)?;
+    require_keys_eq!(
+        ctx.accounts.payment_mint.key(),
+        ctx.accounts.xyber_core.accepted_base_mint,
+        CustomError::WrongPaymentMint
+    );
 
-    let real_escrow_tokens =
-        updated_escrow_balance / 10_u64.pow(ctx.accounts.payment_mint.decimals as u32);
+    let real_base_reserve = ctx.accounts.xyber_token.real_base_reserve;
 
-    let grad_threshold = effective_threshold_for_chains(
-        ctx.accounts.xyber_core.grad_threshold,
-        ctx.accounts.xyber_token.total_chains,
+    // 1) Determine the payment required for `tokens_out`. A buy that would cross the
+    //    graduation threshold only fills what the base up to the threshold buys.
+    let max_base_in = ctx
+        .accounts
+        .raw_grad_threshold()?
+        .saturating_sub(real_base_reserve);
+    let fee_bps = ctx.accounts.fee_bps()?;
+    let fill = fill_buy_exact_output(
+        &ctx.accounts.xyber_token.bonding_curve,
+        real_base_reserve,
+        tokens_out,
+        max_base_in,
+        fee_bps,
     )?;
-
-    if real_escrow_tokens >= grad_threshold {
-        ctx.accounts.xyber_token.is_graduated = true;
-        emit!(GraduationTriggered {
-            buyer: ctx.accounts.buyer.key(),
-            escrow_balance: ctx.accounts.escrow_token_account.amount,
-            vault: ctx.accounts.vault_token_account.key(),
-            creator: ctx.accounts.xyber_token.creator.key(),
-            escrow: ctx.accounts.escrow_token_account.key(),
-            token_seed: ctx.accounts.token_seed.key(),
-        });
+    let (payment_amount, new_reserve) = (fill.total_paid()?, fill.new_reserve);
+    msg!("buy_exact_output payment_amount = {}", payment_amount);
+    if fill.partial {
+        msg!("Partial fill: {} of {} tokens", fill.tokens_out, tokens_out);
     }
 
-    // 6) Transfer `actual_tokens_out` from the vault to the buyer, accounting for decimals.
-    let token_amount_with_decimals = actual_tokens_out
-        .checked_mul(10_u64.pow(ctx.accounts.mint.decimals as u32))
-        .ok_or(CustomError::MathOverflow)?;
-
-    let token_seed_key = ctx.accounts.token_seed.key();
-    let xyber_token_bump = ctx.bumps.xyber_token;
+    // 2) Enforce `payment_amount <= max_payment_amount`, scaled down to the tokens actually
+    //    filled when the buy is only partially filled.
+    require!(
+        (payment_amount as u128) * (tokens_out as u128)
+            <= (max_payment_amount as u128) * (fill.tokens_out as u128),
+        CustomError::SlippageExceeded
+    );
 
-    let seeds: [&[u8]; 3] = [b"xyber_token", token_seed_key.as_ref(), &[xyber_token_bump]];
-    let signer_seeds = &[&seeds[..]];
+    // 2b) Enforce the core's price-impact limit.
+    check_price_impact(
+        &ctx.accounts.xyber_token.bonding_curve,
+        real_base_reserve,
+        new_reserve,
+        ctx.accounts.xyber_core.max_price_impact_bps,
+    )?;
 
-    let vault_transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.vault_token_account.to_account_info(),
-            to: ctx.accounts.buyer_token_account.to_account_info(),
-            authority: ctx.accounts.xyber_token.to_account_info(),
-        },
-        signer_seeds,
+    // 3) Check vault balance.
+    require!(
+        fill.tokens_out <= ctx.accounts.vault_token_account.amount,
+        CustomError::InsufficientTokenVaultBalance
     );
-    token::transfer(vault_transfer_ctx, token_amount_with_decimals)?;
 
+    // 4) Transfer the curve cost to `escrow_token_account` and the fees to their vaults.
+    ctx.accounts.transfer_payment_to_escrow(fill.base_in)?;
+    let referrer = ctx.accounts.record_referrer()?;
+    let fees = ctx
+        .accounts
+        .collect_fees(fill.fee, fill.base_in, referrer)?;
+
+    // 5) Update the reserve accounting and the buyer's volume, and graduate once the
+    //    threshold is reached.
+    ctx.accounts.record_buy(new_reserve, fill.tokens_out)?;
+    ctx.accounts.record_volume(fill.base_in)?;
+    ctx.accounts.graduate_if_threshold_reached()?;
+
+    // 6) Transfer the filled tokens (raw units) from the vault to the buyer.
+    ctx.accounts
+        .transfer_tokens_to_buyer(fill.tokens_out, ctx.bumps.xyber_token)?;
+
+    let curve = &ctx.accounts.xyber_token.bonding_curve;
     emit!(XyberSwapEvent {
-        ix_type: XyberInstructionType::BuyExactIn,
+        ix_type: XyberInstructionType::BuyExactOut,
         token_seed: ctx.accounts.token_seed.key(),
         user: ctx.accounts.buyer.key(),
-        base_amount: payment_amount,
-        token_amount: actual_tokens_out,
-        vault_token_amount: escrow_balance,
+        base_amount: fill.base_in,
+        token_amount: fill.tokens_out,
+        vault_token_amount: real_base_reserve,
+        spot_price: curve.spot_price(new_reserve)?,
+        market_cap: curve.market_cap(new_reserve)?,
+        tokens_remaining: curve.tokens_remaining(new_reserve)?,
+        partial_fill: fill.partial,
+        base_amount_unfilled: fill.base_unfilled,
+        protocol_fee: fees.protocol,
+        creator_fee: fees.creator,
+        referrer,
+        referral_fee: fees.referral,
+        sniper_fee: fees.sniper,
+        fee_bps,
     });
 
     Ok(())
 }
 
+/// The part of a buy that actually goes through.
+pub struct BuyFill {
+    pub tokens_out: u64,
+    /// Base charged by the curve for `tokens_out`
+    pub base_in: u64,
+    /// Trading fee on `base_in`, paid on top of it
+    pub fee: u64,
+    pub new_reserve: u64,
+    /// Whether the buy was capped at the tokens left for sale or at the graduation threshold
+    pub partial: bool,
+    /// Base (fees included) the unfilled part of the buy would have cost; it stays with the buyer
+    pub base_unfilled: u64,
+}
+
+impl BuyFill {
+    /// Everything the buyer pays: the curve cost plus the fee.
+    pub fn total_paid(&self) -> std::result::Result<u64, CustomError> {
+        self.base_in
+            .checked_add(self.fee)
+            .ok_or(CustomError::MathOverflow)
+    }
+}
+
+/// Prices a buy paying at most `payment` at `reserve`, fees included, putting at most
+/// `max_base_in` into the curve and filling at most `max_tokens_out` tokens.
+///
+/// When `payment` buys more tokens than that, or more than the curve has left, exactly
+/// `max_tokens_out` tokens are filled and charged through the exact-output math. Either
+/// way the rest of `payment` never leaves the buyer.
+pub fn fill_buy_exact_input(
+    curve: &impl BondingCurveTrait,
+    reserve: u64,
+    payment: u64,
+    max_base_in: u64,
+    max_tokens_out: u64,
+    fee_bps: u64,
+) -> std::result::Result<BuyFill, CustomError> {
+    let base_available = amount_before_fee(payment, fee_bps)?;
+    let base_offered = base_available.min(max_base_in);
+
+    let (tokens_out, base_charged, new_reserve, tokens_capped) = match curve
+        .buy_exact_input(reserve, base_offered)
+    {
+        Ok((tokens_out, new_reserve)) if tokens_out <= max_tokens_out => {
+            (tokens_out, base_offered, new_reserve, false)
+        }
+        Ok(_) | Err(CustomError::InsufficientTokenSupply) => {
+            if max_tokens_out == 0 {
+                return Err(CustomError::InsufficientTokenSupply);
+            }
+            let (base_charged, new_reserve) = curve.buy_exact_output(reserve, max_tokens_out)?;
+            // Fewer tokens than `base_offered` buys never cost more than `base_offered`.
+            if base_charged > base_offered {
+                return Err(CustomError::SlippageExceeded);
+            }
+            (max_tokens_out, base_charged, new_reserve, true)
+        }
+        Err(err) => return Err(err),
+    };
+
+    let fee = fee_on(base_charged, fee_bps)?;
+    let total_paid = base_charged
+        .checked_add(fee)
+        .ok_or(CustomError::MathOverflow)?;
+
+    Ok(BuyFill {
+        tokens_out,
+        base_in: base_charged,
+        fee,
+        new_reserve,
+        partial: tokens_capped || base_offered < base_available,
+        base_unfilled: payment
+            .checked_sub(total_paid)
+            .ok_or(CustomError::MathOverflow)?,
+    })
+}
+
+/// Prices a buy of `tokens_out` at `reserve`, putting at most `max_base_in` into the curve.
+///
+/// When the tokens cost more than that, only what `max_base_in` buys is filled.
+pub fn fill_buy_exact_output(
+    curve: &impl BondingCurveTrait,
+    reserve: u64,
+    tokens_out: u64,
+    max_base_in: u64,
+    fee_bps: u64,
+) -> std::result::Result<BuyFill, CustomError> {
+    let (base_in, new_reserve) = curve.buy_exact_output(reserve, tokens_out)?;
+    let fee = fee_on(base_in, fee_bps)?;
+    if base_in <= max_base_in {
+        return Ok(BuyFill {
+            tokens_out,
+            base_in,
+            fee,
+            new_reserve,
+            partial: false,
+            base_unfilled: 0,
+        });
+    }
+
+    let (tokens_filled, new_reserve) = curve.buy_exact_input(reserve, max_base_in)?;
+    let fee_filled = fee_on(max_base_in, fee_bps)?;
+    let base_unfilled = (base_in - max_base_in)
+        .checked_add(fee - fee_filled)
+        .ok_or(CustomError::MathOverflow)?;
+
+    Ok(BuyFill {
+        tokens_out: tokens_filled,
+        base_in: max_base_in,
+        fee: fee_filled,
+        new_reserve,
+        partial: true,
+        base_unfilled,
+    })
+}
+
+/// The reserve (raw base units) at which a token graduates: the chain-adjusted threshold
+/// in whole base tokens, scaled by the base mint's decimals.
+///
+/// Saturates, since a threshold beyond `u64::MAX` raw units can never be reached anyway.
+pub fn raw_grad_threshold(
+    grad_threshold: u64,
+    total_chains: u8,
+    base_decimals: u8,
+) -> std::result::Result<u64, CustomError> {
+    let grad_threshold = effective_threshold_for_chains(grad_threshold, total_chains)?;
+    Ok(10_u64
+        .checked_pow(base_decimals as u32)
+        .map_or(u64::MAX, |scale| grad_threshold.saturating_mul(scale)))
+}
+
+/// Rejects a trade moving the pool from `old_reserve` to `new_reserve` when it moves the
+/// spot price by more than `max_price_impact_bps` (see `XyberCore`; 0 disables the limit).
+pub fn check_price_impact(
+    curve: &impl BondingCurveTrait,
+    old_reserve: u64,
+    new_reserve: u64,
+    max_price_impact_bps: u64,
+) -> std::result::Result<(), CustomError> {
+    if max_price_impact_bps == 0 {
+        return Ok(());
+    }
+    if curve.price_impact_bps(old_reserve, new_reserve)? > max_price_impact_bps {
+        return Err(CustomError::PriceImpactExceeded);
+    }
+    Ok(())
+}
+
 pub fn effective_threshold_for_chains(
     base_threshold: u64,
     chain_count: u8,
@@ -215,3 +747,179 @@ pub fn effective_threshold_for_chains(
 
     Ok(new_threshold)
 }
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::testing::{constant_product_curve, smooth_curve, TOKEN};
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    #[test]
+    fn test_fill_buy_exact_input() {
+        let curve = constant_product_curve();
+        let max_tokens_out = curve.max_tokens_out(0).unwrap();
+        assert_eq!(max_tokens_out, 793_100_000 * TOKEN);
+
+        // Within the supply the whole payment is used.
+        let fill =
+            fill_buy_exact_input(&curve, 0, LAMPORTS_PER_SOL, u64::MAX, max_tokens_out, 0).unwrap();
+        assert!(!fill.partial);
+        assert_eq!(fill.base_in, LAMPORTS_PER_SOL);
+        assert_eq!(
+            (fill.tokens_out, fill.new_reserve),
+            curve.buy_exact_input(0, LAMPORTS_PER_SOL).unwrap()
+        );
+
+        // 100 SOL buys past the end of the curve: only the rest of the supply is filled,
+        // at the exact-output price.
+        let base_offered = 100 * LAMPORTS_PER_SOL;
+        assert!(curve.buy_exact_input(0, base_offered).is_err());
+        let fill =
+            fill_buy_exact_input(&curve, 0, base_offered, u64::MAX, max_tokens_out, 0).unwrap();
+        assert!(fill.partial);
+        assert_eq!(fill.tokens_out, max_tokens_out);
+        assert_eq!(
+            (fill.base_in, fill.new_reserve),
+            curve.buy_exact_output(0, max_tokens_out).unwrap()
+        );
+        assert!(fill.base_in < base_offered);
+        assert_eq!(curve.max_tokens_out(fill.new_reserve).unwrap(), 0);
+        assert_eq!(curve.tokens_remaining(fill.new_reserve).unwrap(), 0);
+
+        // A cap below what the payment buys, e.g. the vault balance.
+        let fill =
+            fill_buy_exact_input(&curve, 0, LAMPORTS_PER_SOL, u64::MAX, 1_000 * TOKEN, 0).unwrap();
+        assert!(fill.partial);
+        assert_eq!(fill.tokens_out, 1_000 * TOKEN);
+        assert!(fill.base_in < LAMPORTS_PER_SOL);
+
+        // Nothing left to fill.
+        assert!(matches!(
+            fill_buy_exact_input(&curve, 0, LAMPORTS_PER_SOL, u64::MAX, 0, 0),
+            Err(CustomError::InsufficientTokenSupply)
+        ));
+    }
+
+    #[test]
+    fn test_buy_stops_at_grad_threshold() {
+        let curve = smooth_curve();
+        let threshold = raw_grad_threshold(85, 1, 9).unwrap();
+        assert_eq!(threshold, 85 * LAMPORTS_PER_SOL);
+        let reserve = 80 * LAMPORTS_PER_SOL;
+        let max_base_in = threshold - reserve;
+        let max_tokens_out = curve.max_tokens_out(reserve).unwrap();
+
+        // Exact input: 10 SOL offered, only the 5 SOL up to the threshold are charged.
+        let fill = fill_buy_exact_input(
+            &curve,
+            reserve,
+            10 * LAMPORTS_PER_SOL,
+            max_base_in,
+            max_tokens_out,
+            0,
+        )
+        .unwrap();
+        assert!(fill.partial);
+        assert_eq!(fill.base_in, max_base_in);
+        assert_eq!(fill.base_unfilled, 5 * LAMPORTS_PER_SOL);
+        assert_eq!(fill.new_reserve, threshold);
+        assert_eq!(
+            fill.tokens_out,
+            curve.buy_exact_input(reserve, max_base_in).unwrap().0
+        );
+
+        // Below the threshold nothing changes.
+        let fill = fill_buy_exact_input(
+            &curve,
+            reserve,
+            LAMPORTS_PER_SOL,
+            max_base_in,
+            max_tokens_out,
+            0,
+        )
+        .unwrap();
+        assert!(!fill.partial);
+        assert_eq!(fill.base_unfilled, 0);
+
+        // Exact output: the tokens 10 SOL would buy, filled only up to the threshold.
+        let (tokens_out, _) = curve
+            .buy_exact_input(reserve, 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let (full_cost, _) = curve.buy_exact_output(reserve, tokens_out).unwrap();
+        let fill = fill_buy_exact_output(&curve, reserve, tokens_out, max_base_in, 0).unwrap();
+        assert!(fill.partial);
+        assert_eq!(fill.base_in, max_base_in);
+        assert_eq!(fill.base_unfilled, full_cost - max_base_in);
+        assert_eq!(fill.new_reserve, threshold);
+        assert!(fill.tokens_out < tokens_out);
+
+        let fill = fill_buy_exact_output(&curve, reserve, tokens_out / 4, max_base_in, 0).unwrap();
+        assert!(!fill.partial);
+        assert_eq!(fill.tokens_out, tokens_out / 4);
+    }
+
+    #[test]
+    fn test_buy_fee() {
+        let curve = smooth_curve();
+        let max_tokens_out = curve.max_tokens_out(0).unwrap();
+
+        // 1% on top of the curve cost: 1.01 SOL puts exactly 1 SOL into the curve.
+        let payment = LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 100;
+        let fill = fill_buy_exact_input(&curve, 0, payment, u64::MAX, max_tokens_out, 100).unwrap();
+        assert!(!fill.partial);
+        assert_eq!(fill.base_in, LAMPORTS_PER_SOL);
+        assert_eq!(fill.fee, LAMPORTS_PER_SOL / 100);
+        assert_eq!(fill.total_paid().unwrap(), payment);
+        assert_eq!(fill.base_unfilled, 0);
+        assert_eq!(
+            (fill.tokens_out, fill.new_reserve),
+            curve.buy_exact_input(0, LAMPORTS_PER_SOL).unwrap()
+        );
+
+        // Exact output: the fee is charged on top of the curve cost.
+        let (tokens_out, _) = curve.buy_exact_input(0, LAMPORTS_PER_SOL).unwrap();
+        let (cost, _) = curve.buy_exact_output(0, tokens_out).unwrap();
+        let fill = fill_buy_exact_output(&curve, 0, tokens_out, u64::MAX, 100).unwrap();
+        assert_eq!(fill.base_in, cost);
+        assert_eq!(fill.fee, fee_on(cost, 100).unwrap());
+
+        // Stopping at the threshold leaves both the unused base and its fee with the buyer.
+        let fill = fill_buy_exact_output(&curve, 0, tokens_out, cost / 2, 100).unwrap();
+        assert!(fill.partial);
+        assert_eq!(
+            fill.total_paid().unwrap() + fill.base_unfilled,
+            cost + fee_on(cost, 100).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_raw_grad_threshold() {
+        // Two chains add 25% to the threshold.
+        assert_eq!(raw_grad_threshold(100, 2, 6).unwrap(), 125 * 1_000_000);
+        // Out of u64 range: the threshold can never be reached.
+        assert_eq!(raw_grad_threshold(u64::MAX / 2, 1, 9).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_price_impact_limit() {
+        let curve = smooth_curve();
+        let c = 30 * LAMPORTS_PER_SOL;
+
+        // Buying C from 0 quadruples the price (~30_000 bps); selling it back drops it by 75%.
+        assert!(check_price_impact(&curve, 0, c, 40_000).is_ok());
+        assert!(matches!(
+            check_price_impact(&curve, 0, c, 20_000),
+            Err(CustomError::PriceImpactExceeded)
+        ));
+        assert!(check_price_impact(&curve, c, 0, 7_600).is_ok());
+        assert!(matches!(
+            check_price_impact(&curve, c, 0, 7_000),
+            Err(CustomError::PriceImpactExceeded)
+        ));
+
+        // 0 disables the limit.
+        assert!(check_price_impact(&curve, 0, c, 0).is_ok());
+    }
+}
@@ -5,8 +5,16 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 use crate::curves::BondingCurveTrait;
 use crate::errors::CustomError;
+use crate::events::FeeCollected;
+use crate::events::ReferralRecorded;
 use crate::events::XyberInstructionType;
 use crate::events::XyberSwapEvent;
+use crate::fees::{
+    fee_on, gross_up_for_fee, resolve_referrer, split_fee, trader_fee_bps, FeeSplit,
+};
+use crate::instructions::check_price_impact;
+use crate::Referral;
+use crate::TraderStats;
 use crate::XyberCore;
 use crate::XyberToken;
 
@@ -72,6 +80,61 @@ pub struct SellToken<'info> {
     )]
     pub user_payment_account: Box<Account<'info, TokenAccount>>,
 
+    /// Collects the protocol's trading fees in the base mint.
+    #[account(
+        mut,
+        seeds = [b"fee_vault", payment_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Collects the creator's slice of this token's trades.
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", token_seed.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Records the user's referrer; the first one sticks.
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"referral", user.key().as_ref()],
+        bump,
+        space = Referral::LEN
+    )]
+    pub referral: Box<Account<'info, Referral>>,
+
+    /// CHECK: Referrer of the user, required once one is recorded; only its key is used.
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    /// The referrer's share of the trading fees in the base mint, claimable by the referrer.
+    /// Only used together with `referrer`.
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [
+            b"referral_vault",
+            referrer.as_ref().map(|r| r.key()).unwrap_or_default().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump,
+        token::mint = payment_mint,
+        token::authority = xyber_core
+    )]
+    pub referral_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The user's rolling volume for the fee tiers.
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"trader_stats", user.key().as_ref()],
+        bump,
+        space = TraderStats::LEN
+    )]
+    pub trader_stats: Box<Account<'info, TraderStats>>,
+
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
 
@@ -80,6 +143,165 @@ pub struct SellToken<'info> {
     pub system_program: UncheckedAccount<'info>,
 }
 
+impl<'info> SellToken<'info> {
+    /// Transfers `token_amount` (raw units) from the user to the vault.
+    fn transfer_tokens_to_vault(&self, token_amount: u64) -> Result<()> {
+        let user_to_vault_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.user_token_account.to_account_info(),
+                to: self.vault_token_account.to_account_info(),
+                authority: self.user.to_account_info(),
+            },
+        );
+        token::transfer(user_to_vault_ctx, token_amount)
+    }
+
+    /// Transfers base tokens from escrow to the user using the PDA signature.
+    fn transfer_base_to_user(&self, base_amount: u64, xyber_token_bump: u8) -> Result<()> {
+        self.transfer_from_escrow(
+            self.user_payment_account.to_account_info(),
+            base_amount,
+            xyber_token_bump,
+        )
+    }
+
+    /// Resolves who referred the user, recording the first referrer for good. Only a
+    /// `referrer` passed along with its `referral_vault` counts, and once one is recorded,
+    /// every trade has to pass it.
+    fn record_referrer(&mut self) -> Result<Option<Pubkey>> {
+        let offered = match (&self.referrer, &self.referral_vault) {
+            (Some(referrer), Some(_)) => Some(referrer.key()),
+            _ => None,
+        };
+        let referrer = resolve_referrer(self.referral.referrer, self.user.key(), offered)?;
+        if let Some(referrer) = referrer {
+            if self.referral.referrer == Pubkey::default() {
+                self.referral.referrer = referrer;
+                emit!(ReferralRecorded {
+                    user: self.user.key(),
+                    referrer,
+                });
+            }
+        }
+        Ok(referrer)
+    }
+
+    /// Splits the trading fee and transfers the protocol's share from escrow to `fee_vault`,
+    /// the creator's to `creator_fee_vault` and the referrer's, if any, to `referral_vault`,
+    /// using the PDA signature.
+    fn collect_fees(
+        &mut self,
+        fee: u64,
+        referrer: Option<Pubkey>,
+        xyber_token_bump: u8,
+    ) -> Result<FeeSplit> {
+        let xyber_core = &self.xyber_core;
+        let mut fees = split_fee(fee, xyber_core.protocol_fee_bps, xyber_core.creator_fee_bps)?;
+        if referrer.is_some() {
+            fees = fees.with_referral(xyber_core.referral_share_bps)?;
+        }
+        if let Some(referral_vault) = &self.referral_vault {
+            self.transfer_from_escrow(
+                referral_vault.to_account_info(),
+                fees.referral,
+                xyber_token_bump,
+            )?;
+        }
+        self.transfer_from_escrow(
+            self.fee_vault.to_account_info(),
+            fees.protocol,
+            xyber_token_bump,
+        )?;
+        self.transfer_from_escrow(
+            self.creator_fee_vault.to_account_info(),
+            fees.creator,
+            xyber_token_bump,
+        )?;
+
+        self.record_fees(fees, referrer)?;
+        Ok(fees)
+    }
+
+    /// Adds the fees paid into each vault to the token's and the core's totals and emits
+    /// `FeeCollected`.
+    fn record_fees(&mut self, fees: FeeSplit, referrer: Option<Pubkey>) -> Result<()> {
+        self.xyber_core
+            .record_fees(fees.protocol, fees.creator, fees.referral)?;
+        let xyber_token = &mut self.xyber_token;
+        xyber_token.record_fees(fees.protocol, fees.creator, fees.referral)?;
+
+        emit!(FeeCollected {
+            token_seed: self.token_seed.key(),
+            user: self.user.key(),
+            base_mint: self.payment_mint.key(),
+            protocol_fee: fees.protocol,
+            creator_fee: fees.creator,
+            referrer,
+            referral_fee: fees.referral,
+            sniper_fee: 0,
+            sniper_fee_to_creator: xyber_token.sniper_fee_to_creator,
+            total_protocol_fees: xyber_token.total_protocol_fees,
+            total_creator_fees: xyber_token.total_creator_fees,
+            total_referral_fees: xyber_token.total_referral_fees,
+        });
+        Ok(())
+    }
+
+    fn transfer_from_escrow(
+        &self,
+        to: AccountInfo<'info>,
+        amount: u64,
+        xyber_token_bump: u8,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let token_seed_key = self.token_seed.key();
+        let seeds: [&[u8]; 3] = [b"xyber_token", token_seed_key.as_ref(), &[xyber_token_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let escrow_transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.escrow_token_account.to_account_info(),
+                to,
+                authority: self.xyber_token.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(escrow_transfer_ctx, amount)
+    }
+
+    /// The user's regular fee rate, discounted by their volume tier.
+    fn regular_fee_bps(&self) -> Result<u64> {
+        let volume = self
+            .trader_stats
+            .rolling_volume(self.xyber_core.volume_window_slots, Clock::get()?.slot);
+        Ok(trader_fee_bps(&self.xyber_core, volume)?)
+    }
+
+    /// Adds the sell's base (before fees) to the user's rolling volume.
+    fn record_volume(&mut self, base_amount: u64) -> Result<()> {
+        let window_slots = self.xyber_core.volume_window_slots;
+        self.trader_stats
+            .record_volume(window_slots, Clock::get()?.slot, base_amount);
+        Ok(())
+    }
+
+    /// Records a sell in the token's internal accounting: the curve moves to `new_reserve`
+    /// and `token_amount` tokens are returned to the vault.
+    fn record_sell(&mut self, new_reserve: u64, token_amount: u64) -> Result<()> {
+        let xyber_token = &mut self.xyber_token;
+        xyber_token.real_base_reserve = new_reserve;
+        xyber_token.tokens_sold = xyber_token
+            .tokens_sold
+            .checked_sub(token_amount)
+            .ok_or(CustomError::InsufficientTokenSupply)?;
+        Ok(())
+    }
+}
+
 /// Sells an *exact input* of project tokens in exchange for base (payment) tokens.
 /// Enforces a “minimum” base tokens out to guard against slippage.
 pub fn sell_exact_input_instruction(
@@ -99,70 +321,258 @@ pub fn sell_exact_input_instruction(
         CustomError::WrongPaymentMint
     );
 
-    // 1) Scale the user token amount by the mint decimals.
-    let decimal_factor = ctx.accounts.mint.decimals as u32;
-    let tokens_to_transfer = user_token_amount
-        .checked_mul(10_u64.pow(decimal_factor))
-        .ok_or(CustomError::MathOverflow)?;
-
-    // 2) Transfer tokens from the user to the vault.
-    let user_to_vault_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.vault_token_account.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        },
-    );
-    token::transfer(user_to_vault_ctx, tokens_to_transfer)?;
+    // 1) Transfer tokens (raw units) from the user to the vault.
+    ctx.accounts.transfer_tokens_to_vault(user_token_amount)?;
 
-    // 3) Calculate how many base (payment) tokens the user should receive.
-    let escrow_balance = ctx.accounts.escrow_token_account.amount;
-    let (base_token_amount, _new_x) = ctx
-        .accounts
-        .xyber_core
-        .bonding_curve
-        .sell_exact_input(escrow_balance, user_token_amount)?;
+    // 2) Calculate how many base (payment) tokens the user should receive after the fee.
+    let real_base_reserve = ctx.accounts.xyber_token.real_base_reserve;
+    let fee_bps = ctx.accounts.regular_fee_bps()?;
+    let fill = fill_sell_exact_input(
+        &ctx.accounts.xyber_token.bonding_curve,
+        real_base_reserve,
+        user_token_amount,
+        fee_bps,
+    )?;
+    let (base_token_amount, new_reserve) = (fill.net_base_out()?, fill.new_reserve);
     msg!("sell_exact_input actual_tokens_out = {}", base_token_amount);
 
-    // 4) Enforce slippage check: base_token_amount >= min_base_amount_out
+    // 3) Enforce slippage check: base_token_amount >= min_base_amount_out
     require!(
         base_token_amount >= min_base_amount_out,
         CustomError::SlippageExceeded
     );
 
-    // 5) Ensure the escrow holds enough base tokens.
+    // 3b) Enforce the core's price-impact limit.
+    check_price_impact(
+        &ctx.accounts.xyber_token.bonding_curve,
+        real_base_reserve,
+        new_reserve,
+        ctx.accounts.xyber_core.max_price_impact_bps,
+    )?;
+
+    // 4) Ensure the escrow holds enough base tokens.
     require!(
-        base_token_amount <= ctx.accounts.escrow_token_account.amount,
+        fill.base_out <= ctx.accounts.escrow_token_account.amount,
         CustomError::InsufficientEscrowBalance
     );
 
-    // 6) Transfer base tokens from escrow to the user using the PDA signature.
-    let token_seed_key = ctx.accounts.token_seed.key();
-    let xyber_token_bump = ctx.bumps.xyber_token;
-
-    let seeds: [&[u8]; 3] = [b"xyber_token", token_seed_key.as_ref(), &[xyber_token_bump]];
-    let signer_seeds = &[&seeds[..]];
-
-    let escrow_to_user_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.escrow_token_account.to_account_info(),
-            to: ctx.accounts.user_payment_account.to_account_info(),
-            authority: ctx.accounts.xyber_token.to_account_info(),
-        },
-        signer_seeds,
-    );
-    token::transfer(escrow_to_user_ctx, base_token_amount)?;
+    // 5) Update the reserve accounting and the user's volume.
+    ctx.accounts.record_sell(new_reserve, user_token_amount)?;
+    ctx.accounts.record_volume(fill.base_out)?;
+
+    // 6) Transfer base tokens from escrow to the user and the fees to their vaults
+    //    using the PDA signature.
+    ctx.accounts
+        .transfer_base_to_user(base_token_amount, ctx.bumps.xyber_token)?;
+    let referrer = ctx.accounts.record_referrer()?;
+    let fees = ctx
+        .accounts
+        .collect_fees(fill.fee, referrer, ctx.bumps.xyber_token)?;
 
+    let curve = &ctx.accounts.xyber_token.bonding_curve;
     emit!(XyberSwapEvent {
         ix_type: XyberInstructionType::SellExactIn,
         token_seed: ctx.accounts.token_seed.key(),
         user: ctx.accounts.user.key(),
-        base_amount: base_token_amount,
-        token_amount: tokens_to_transfer,
-        vault_token_amount: escrow_balance,
+        base_amount: fill.base_out,
+        token_amount: user_token_amount,
+        vault_token_amount: real_base_reserve,
+        spot_price: curve.spot_price(new_reserve)?,
+        market_cap: curve.market_cap(new_reserve)?,
+        tokens_remaining: curve.tokens_remaining(new_reserve)?,
+        partial_fill: false,
+        base_amount_unfilled: 0,
+        protocol_fee: fees.protocol,
+        creator_fee: fees.creator,
+        referrer,
+        referral_fee: fees.referral,
+        sniper_fee: 0,
+        fee_bps,
     });
 
     Ok(())
 }
+
+/// Sells just enough project tokens to receive an *exact output* of base (payment) tokens.
+/// Enforces a “maximum” project tokens in to guard against slippage.
+pub fn sell_exact_output_instruction(
+    ctx: Context<SellToken>,
+    base_amount_out: u64,
+    max_user_token_amount: u64, // slippage guard
+) -> Result<()> {
+    // 0) Prevent sells if the token is already graduated (assets locked).
+    require!(
+        !ctx.accounts.xyber_token.is_graduated,
+        CustomError::TokenIsGraduated
+    );
+
+    require_keys_eq!(
+        ctx.accounts.payment_mint.key(),
+        ctx.accounts.xyber_core.accepted_base_mint,
+        CustomError::WrongPaymentMint
+    );
+
+    // 1) Calculate how many project tokens the user has to sell for `base_amount_out`
+    //    to remain after the fee; the reserve must hold the base before the fee.
+    let real_base_reserve = ctx.accounts.xyber_token.real_base_reserve;
+    let fee_bps = ctx.accounts.regular_fee_bps()?;
+    let fill = fill_sell_exact_output(
+        &ctx.accounts.xyber_token.bonding_curve,
+        real_base_reserve,
+        base_amount_out,
+        fee_bps,
+    )?;
+    let (user_token_amount, new_reserve) = (fill.tokens_in, fill.new_reserve);
+    msg!(
+        "sell_exact_output user_token_amount = {}",
+        user_token_amount
+    );
+
+    // 3) Enforce slippage check: user_token_amount <= max_user_token_amount
+    require!(
+        user_token_amount <= max_user_token_amount,
+        CustomError::SlippageExceeded
+    );
+
+    // 3b) Enforce the core's price-impact limit.
+    check_price_impact(
+        &ctx.accounts.xyber_token.bonding_curve,
+        real_base_reserve,
+        new_reserve,
+        ctx.accounts.xyber_core.max_price_impact_bps,
+    )?;
+
+    // 4) Transfer tokens (raw units) from the user to the vault.
+    ctx.accounts.transfer_tokens_to_vault(user_token_amount)?;
+
+    // 5) Update the reserve accounting and the user's volume.
+    ctx.accounts.record_sell(new_reserve, user_token_amount)?;
+    ctx.accounts.record_volume(fill.base_out)?;
+
+    // 6) Transfer base tokens from escrow to the user and the fees to their vaults
+    //    using the PDA signature.
+    ctx.accounts
+        .transfer_base_to_user(base_amount_out, ctx.bumps.xyber_token)?;
+    let referrer = ctx.accounts.record_referrer()?;
+    let fees = ctx
+        .accounts
+        .collect_fees(fill.fee, referrer, ctx.bumps.xyber_token)?;
+
+    let curve = &ctx.accounts.xyber_token.bonding_curve;
+    emit!(XyberSwapEvent {
+        ix_type: XyberInstructionType::SellExactOut,
+        token_seed: ctx.accounts.token_seed.key(),
+        user: ctx.accounts.user.key(),
+        base_amount: fill.base_out,
+        token_amount: user_token_amount,
+        vault_token_amount: real_base_reserve,
+        spot_price: curve.spot_price(new_reserve)?,
+        market_cap: curve.market_cap(new_reserve)?,
+        tokens_remaining: curve.tokens_remaining(new_reserve)?,
+        partial_fill: false,
+        base_amount_unfilled: 0,
+        protocol_fee: fees.protocol,
+        creator_fee: fees.creator,
+        referrer,
+        referral_fee: fees.referral,
+        sniper_fee: 0,
+        fee_bps,
+    });
+
+    Ok(())
+}
+
+/// The curve side of a sell and the fee taken out of its proceeds.
+pub struct SellFill {
+    pub tokens_in: u64,
+    /// Base the curve pays out for `tokens_in`
+    pub base_out: u64,
+    /// Trading fee taken out of `base_out`
+    pub fee: u64,
+    pub new_reserve: u64,
+}
+
+impl SellFill {
+    /// What the seller receives: the curve proceeds minus the fee.
+    pub fn net_base_out(&self) -> std::result::Result<u64, CustomError> {
+        self.base_out
+            .checked_sub(self.fee)
+            .ok_or(CustomError::MathOverflow)
+    }
+}
+
+/// Prices a sell of `tokens_in` at `reserve`, taking the fee out of the proceeds.
+pub fn fill_sell_exact_input(
+    curve: &impl BondingCurveTrait,
+    reserve: u64,
+    tokens_in: u64,
+    fee_bps: u64,
+) -> std::result::Result<SellFill, CustomError> {
+    let (base_out, new_reserve) = curve.sell_exact_input(reserve, tokens_in)?;
+    Ok(SellFill {
+        tokens_in,
+        base_out,
+        fee: fee_on(base_out, fee_bps)?,
+        new_reserve,
+    })
+}
+
+/// Prices a sell at `reserve` that leaves the seller exactly `net_base_out` after the fee.
+pub fn fill_sell_exact_output(
+    curve: &impl BondingCurveTrait,
+    reserve: u64,
+    net_base_out: u64,
+    fee_bps: u64,
+) -> std::result::Result<SellFill, CustomError> {
+    let base_out = gross_up_for_fee(net_base_out, fee_bps)?;
+    if base_out > reserve {
+        return Err(CustomError::InsufficientEscrowBalance);
+    }
+
+    let (tokens_in, new_reserve) = curve.sell_exact_output(reserve, base_out)?;
+    Ok(SellFill {
+        tokens_in,
+        base_out,
+        fee: base_out - net_base_out,
+        new_reserve,
+    })
+}
+
+//==============================================================================
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::testing::smooth_curve;
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    #[test]
+    fn test_sell_fee() {
+        let curve = smooth_curve();
+        let reserve = 10 * LAMPORTS_PER_SOL;
+        let (tokens_in, _) = curve.buy_exact_input(0, reserve).unwrap();
+
+        // Exact input: the fee comes out of the curve proceeds.
+        let fill = fill_sell_exact_input(&curve, reserve, tokens_in / 2, 100).unwrap();
+        let (base_out, new_reserve) = curve.sell_exact_input(reserve, tokens_in / 2).unwrap();
+        assert_eq!((fill.base_out, fill.new_reserve), (base_out, new_reserve));
+        assert_eq!(fill.fee, fee_on(base_out, 100).unwrap());
+        assert_eq!(fill.net_base_out().unwrap(), base_out - fill.fee);
+
+        // Exact output: the seller receives exactly the requested amount after the fee.
+        let net = 99 * LAMPORTS_PER_SOL / 100;
+        let fill = fill_sell_exact_output(&curve, reserve, net, 100).unwrap();
+        assert_eq!(fill.base_out, LAMPORTS_PER_SOL);
+        assert_eq!(fill.net_base_out().unwrap(), net);
+        assert_eq!(
+            (fill.tokens_in, fill.new_reserve),
+            curve.sell_exact_output(reserve, LAMPORTS_PER_SOL).unwrap()
+        );
+
+        // The gross amount has to fit into the reserve.
+        assert!(matches!(
+            fill_sell_exact_output(&curve, reserve, reserve, 100),
+            Err(CustomError::InsufficientEscrowBalance)
+        ));
+    }
+}
@@ -1,4 +1,6 @@
 use crate::errors::CustomError;
+use crate::events::LiquidityWithdrawn;
+use crate::fees::split_graduation_fee;
 use crate::{XyberCore, XyberToken};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
@@ -41,7 +43,8 @@ pub struct WithdrawLiquidity<'info> {
     /// CHECK: Used only for PDA seed derivation
     pub token_seed: UncheckedAccount<'info>,
 
-    /// CHECK: Used only for PDA seed derivation
+    /// CHECK: Creator of the token, receives the creator's graduation fee
+    #[account(address = xyber_token.creator)]
     pub creator: UncheckedAccount<'info>,
 
     /// Escrow token account holding the payment tokens (e.g. USDC)
@@ -69,39 +72,107 @@ pub struct WithdrawLiquidity<'info> {
     )]
     pub admin_vault_account: Account<'info, TokenAccount>,
 
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Receives the protocol's graduation fee
+    #[account(
+        mut,
+        seeds = [b"fee_vault", base_token_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
     pub rent: Sysvar<'info, Rent>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
+
+impl<'info> WithdrawLiquidity<'info> {
+    fn transfer_from_escrow(
+        &self,
+        to: AccountInfo<'info>,
+        amount: u64,
+        xyber_token_bump: u8,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let token_seed_key = self.token_seed.key();
+        let seeds: [&[u8]; 3] = [b"xyber_token", token_seed_key.as_ref(), &[xyber_token_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let escrow_transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.escrow_token_account.to_account_info(),
+                to,
+                authority: self.xyber_token.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(escrow_transfer_ctx, amount)
+    }
+}
+
+/// Pays the graduation fee out of the escrow to the protocol's `fee_vault` and the creator,
+/// then hands the remaining base tokens and the unsold project tokens to the admin as liquidity.
+/// Liquidity leaves the curve once: afterwards both this and `migrate` fail.
 pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>) -> Result<()> {
     require!(
         ctx.accounts.xyber_token.is_graduated,
         CustomError::BondingCurveNotGraduated
     );
+    require!(
+        !ctx.accounts.xyber_token.migrated,
+        CustomError::AlreadyMigrated
+    );
+    require!(
+        !ctx.accounts.xyber_token.withdrawn,
+        CustomError::AlreadyWithdrawn
+    );
 
     let bump = ctx.bumps.xyber_token;
+
+    // 1) Split the base tokens in escrow: graduation fees first, the rest is liquidity
+    let escrow_balance = ctx.accounts.escrow_token_account.amount;
+    let xyber_core = &ctx.accounts.xyber_core;
+    let fees = split_graduation_fee(
+        escrow_balance,
+        xyber_core.graduation_protocol_fee_bps,
+        xyber_core.graduation_creator_fee_bps,
+    )?;
+    let base_liquidity = escrow_balance - fees.protocol - fees.creator;
+
+    let accounts = &ctx.accounts;
+    accounts.transfer_from_escrow(accounts.fee_vault.to_account_info(), fees.protocol, bump)?;
+    accounts.transfer_from_escrow(
+        accounts.creator_token_account.to_account_info(),
+        fees.creator,
+        bump,
+    )?;
+    accounts.transfer_from_escrow(
+        accounts.admin_token_account.to_account_info(), // Admin’s base token ATA
+        base_liquidity,
+        bump,
+    )?;
+    let xyber_token = &mut ctx.accounts.xyber_token;
+    xyber_token.real_base_reserve = 0;
+    xyber_token.withdrawn = true;
+
+    // 2) Transfer the project tokens from vault to the admin’s project ATA
     let seeds = &[
         b"xyber_token".as_ref(),
         ctx.accounts.token_seed.key.as_ref(),
         &[bump],
     ];
     let signer = &[&seeds[..]];
-
-    // 1) Transfer the base tokens from escrow to the admin’s base ATA
-    let escrow_balance = ctx.accounts.escrow_token_account.amount;
-    let cpi_ctx_escrow = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.escrow_token_account.to_account_info(),
-            to: ctx.accounts.admin_token_account.to_account_info(), // Admin’s base token ATA
-            authority: ctx.accounts.xyber_token.to_account_info(),
-        },
-        signer,
-    );
-    token::transfer(cpi_ctx_escrow, escrow_balance)?;
-
-    // 2) Transfer the project tokens from vault to the admin’s project ATA
     let vault_balance = ctx.accounts.vault_token_account.amount;
     let cpi_ctx_vault = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
@@ -114,5 +185,16 @@ pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>) -> Result<()> {
     );
     token::transfer(cpi_ctx_vault, vault_balance)?;
 
+    emit!(LiquidityWithdrawn {
+        token_seed: ctx.accounts.token_seed.key(),
+        admin: ctx.accounts.admin.key(),
+        creator: ctx.accounts.creator.key(),
+        escrow_balance,
+        protocol_fee: fees.protocol,
+        creator_fee: fees.creator,
+        base_liquidity,
+        token_liquidity: vault_balance,
+    });
+
     Ok(())
 }
@@ -1,14 +1,16 @@
 use anchor_lang::prelude::*;
 
-mod curves;
-mod errors;
+pub mod curves;
+pub mod errors;
 mod events;
+mod fees;
 mod xyber_params;
 
 mod instructions;
 
 use crate::xyber_params::{InitCoreParams, TokenParams};
-use curves::SmoothBondingCurve;
+use curves::BondingCurveType;
+use fees::{FeeTier, MAX_FEE_TIERS};
 use instructions::*;
 
 declare_id!("8FydojysL5DJ8M3s15JLFEbsKzyQ1BcFgSMVDvJetEEq");
@@ -20,8 +22,42 @@ pub struct XyberCore {
     pub grad_threshold: u64,
     pub total_supply: u64,
     // The bonding curve shared by all tokens
-    pub bonding_curve: SmoothBondingCurve,
+    pub bonding_curve: BondingCurveType,
     pub accepted_base_mint: Pubkey,
+    // Largest spot price move a single trade may cause, in bps of the pre-trade price (0 = no limit)
+    pub max_price_impact_bps: u64,
+    // Trading fee on the base side of every trade, in bps; collected in the
+    // per-base-mint `fee_vault` and claimed by the admin
+    pub protocol_fee_bps: u64,
+    // Creator's slice of every trade, in bps, charged on top of the protocol fee;
+    // accrues in each token's `creator_fee_vault`
+    pub creator_fee_bps: u64,
+    // Referrer's share of the protocol fee, in bps of it; accrues in the referrer's
+    // `referral_vault`
+    pub referral_share_bps: u64,
+    // Anti-sniper fee for new launches: buys pay `sniper_fee_bps` in the launch slot, decaying
+    // linearly to the regular fee over `sniper_fee_decay_slots`; the surcharge goes to the
+    // creator's vault if `sniper_fee_to_creator`, else to the protocol's
+    pub sniper_fee_bps: u64,
+    pub sniper_fee_decay_slots: u64,
+    pub sniper_fee_to_creator: bool,
+    // Volume tiers discounting the regular trading fee, and the length (in slots) of the
+    // window over which a trader's volume counts (0 = volume never expires)
+    pub fee_tiers: [FeeTier; 4], // MAX_FEE_TIERS
+    pub volume_window_slots: u64,
+    // Flat fee for launching a token, paid by the creator to the protocol: in lamports, or in
+    // raw `accepted_base_mint` units (into its `fee_vault`) if `creation_fee_in_base_mint`
+    pub creation_fee: u64,
+    pub creation_fee_in_base_mint: bool,
+    // Shares of the escrow paid at withdrawal of a graduated token, in bps: the protocol's
+    // goes to the base mint's `fee_vault`, the creator's to the creator; the rest is liquidity
+    pub graduation_protocol_fee_bps: u64,
+    pub graduation_creator_fee_bps: u64,
+    // Trading fees paid into the protocol, creator and referral vaults across all tokens,
+    // in raw base units
+    pub total_protocol_fees: u64,
+    pub total_creator_fees: u64,
+    pub total_referral_fees: u64,
 }
 
 impl XyberCore {
@@ -29,13 +65,25 @@ impl XyberCore {
         + (1 + 32) // admin (Pubkey)
         + (1 + 8)  // grad_threshold (u16)
         + (1 + 8)  // total_supply (u64)
-        // SmoothBondingCurve has 3 fields:
-        // a_total_tokens: u64 -> 8 bytes
-        // k_virtual_pool_offset: u128 -> 16 bytes
-        // c_bonding_scale_factor: u128 -> 16 bytes
-        // In total: 8 + 16 + 16 = 40
-        + (1 + 40)  // bonding_curve
-        + (1 + 32); // accepted_base_mint (Pubkey)
+        // BondingCurveType: 1 byte variant tag + the largest curve variant
+        + (1 + BondingCurveType::LEN)  // bonding_curve
+        + (1 + 32) // accepted_base_mint (Pubkey)
+        + (1 + 8) // max_price_impact_bps (u64)
+        + (1 + 8) // protocol_fee_bps (u64)
+        + (1 + 8) // creator_fee_bps (u64)
+        + (1 + 8) // referral_share_bps (u64)
+        + (1 + 8) // sniper_fee_bps (u64)
+        + (1 + 8) // sniper_fee_decay_slots (u64)
+        + (1 + 1) // sniper_fee_to_creator (bool)
+        + (1 + FeeTier::LEN * MAX_FEE_TIERS) // fee_tiers
+        + (1 + 8) // volume_window_slots (u64)
+        + (1 + 8) // creation_fee (u64)
+        + (1 + 1) // creation_fee_in_base_mint (bool)
+        + (1 + 8) // graduation_protocol_fee_bps (u64)
+        + (1 + 8) // graduation_creator_fee_bps (u64)
+        + (1 + 8) // total_protocol_fees (u64)
+        + (1 + 8) // total_creator_fees (u64)
+        + (1 + 8); // total_referral_fees (u64)
 }
 
 /// One account per unique token. It holds only “token-specific” info.
@@ -55,6 +103,40 @@ pub struct XyberToken {
 
     // used for managing grad_threshold from XyberCore
     pub total_chains: u8,
+
+    // Base tokens deposited through trades (donations excluded). This, never the escrow
+    // balance, drives the bonding curve
+    pub real_base_reserve: u64,
+
+    // Project tokens (raw units) currently sold out of the vault
+    pub tokens_sold: u64,
+
+    // Launch terms copied from XyberCore at mint time, so later core updates
+    // only apply to new launches
+    pub bonding_curve: BondingCurveType,
+    pub grad_threshold: u64,
+    pub total_supply: u64,
+
+    // Trading fees of this token paid into the protocol's `fee_vault`, its `creator_fee_vault`
+    // and referrers' `referral_vault`s (anti-sniper surcharges included), in base units
+    pub total_protocol_fees: u64,
+    pub total_creator_fees: u64,
+    pub total_referral_fees: u64,
+
+    // Creator fees claimed from the `creator_fee_vault`, in base units
+    pub creator_fees_claimed: u64,
+
+    // Anti-sniper fee schedule copied from XyberCore, counted from the launch slot
+    pub launch_slot: u64,
+    pub sniper_fee_bps: u64,
+    pub sniper_fee_decay_slots: u64,
+    pub sniper_fee_to_creator: bool,
+
+    // Set by `migrate` once the token's liquidity has moved into its AMM pool
+    pub migrated: bool,
+
+    // Set by `withdraw_liquidity` once the token's liquidity has gone to the admin
+    pub withdrawn: bool,
 }
 
 impl XyberToken {
@@ -63,7 +145,53 @@ impl XyberToken {
         + 32  // mint
         + 32  // vault
         + 32 // creator
-        + 1; // total_chains
+        + 1 // total_chains
+        + 8 // real_base_reserve
+        + 8 // tokens_sold
+        + BondingCurveType::LEN // bonding_curve
+        + 8 // grad_threshold
+        + 8 // total_supply
+        + 8 // total_protocol_fees
+        + 8 // total_creator_fees
+        + 8 // total_referral_fees
+        + 8 // creator_fees_claimed
+        + 8 // launch_slot
+        + 8 // sniper_fee_bps
+        + 8 // sniper_fee_decay_slots
+        + 1 // sniper_fee_to_creator
+        + 1 // migrated
+        + 1; // withdrawn
+}
+
+/// One account per trader, recording who referred them.
+#[account]
+pub struct Referral {
+    // The first referrer the trader traded with (default until then); later ones are rejected
+    pub referrer: Pubkey,
+}
+
+impl Referral {
+    pub const LEN: usize = 8 // Discriminator
+        + 32; // referrer
+}
+
+/// One account per trader, tracking their base volume (raw units, before fees) across all
+/// tokens for the fee tiers.
+#[account]
+#[derive(Default)]
+pub struct TraderStats {
+    // Volume in the current and the previous window of `XyberCore::volume_window_slots`
+    pub current_volume: u64,
+    pub previous_volume: u64,
+    // Slot at which the current window started
+    pub window_start_slot: u64,
+}
+
+impl TraderStats {
+    pub const LEN: usize = 8 // Discriminator
+        + 8 // current_volume
+        + 8 // previous_volume
+        + 8; // window_start_slot
 }
 
 #[program]
@@ -78,6 +206,14 @@ pub mod bonding_curve {
         instructions::update_xyber_core_instruction(ctx, params)
     }
 
+    pub fn set_creation_fee_instruction(
+        ctx: Context<SetCreationFee>,
+        creation_fee: u64,
+        in_base_mint: bool,
+    ) -> Result<()> {
+        instructions::set_creation_fee_instruction(ctx, creation_fee, in_base_mint)
+    }
+
     // 1.1 CREATE TOKEN
     pub fn mint_full_supply_instruction(
         ctx: Context<InitAndMint>,
@@ -94,22 +230,71 @@ pub mod bonding_curve {
         instructions::buy_exact_input_instruction(ctx, base_in, min_amount_out)
     }
 
+    pub fn buy_exact_output_instruction(
+        ctx: Context<BuyToken>,
+        tokens_out: u64,
+        max_base_in: u64,
+    ) -> Result<()> {
+        instructions::buy_exact_output_instruction(ctx, tokens_out, max_base_in)
+    }
+
     pub fn sell_exact_input_instruction(
         ctx: Context<SellToken>,
-        normalized_token_amount: u64,
+        token_amount: u64,
         min_base_amount_out: u64,
     ) -> Result<()> {
-        instructions::sell_exact_input_instruction(
-            ctx,
-            normalized_token_amount,
-            min_base_amount_out,
-        )
+        instructions::sell_exact_input_instruction(ctx, token_amount, min_base_amount_out)
+    }
+
+    pub fn sell_exact_output_instruction(
+        ctx: Context<SellToken>,
+        base_amount_out: u64,
+        max_token_amount: u64,
+    ) -> Result<()> {
+        instructions::sell_exact_output_instruction(ctx, base_amount_out, max_token_amount)
+    }
+
+    // Read-only: simulate to get the quote through the transaction's return data.
+    pub fn quote_buy_instruction(
+        ctx: Context<QuoteTrade>,
+        amount: u64,
+        exact_input: bool,
+    ) -> Result<TradeQuote> {
+        instructions::quote_buy_instruction(ctx, amount, exact_input)
+    }
+
+    pub fn quote_sell_instruction(
+        ctx: Context<QuoteTrade>,
+        amount: u64,
+        exact_input: bool,
+    ) -> Result<TradeQuote> {
+        instructions::quote_sell_instruction(ctx, amount, exact_input)
     }
 
     pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>) -> Result<()> {
         instructions::withdraw_liquidity(ctx)
     }
 
+    pub fn migrate(ctx: Context<MigrateToAmm>) -> Result<()> {
+        instructions::migrate(ctx)
+    }
+
+    pub fn sweep_donations_instruction(ctx: Context<SweepDonations>) -> Result<()> {
+        instructions::sweep_donations_instruction(ctx)
+    }
+
+    pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+        instructions::claim_protocol_fees(ctx)
+    }
+
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        instructions::claim_creator_fees(ctx)
+    }
+
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        instructions::claim_referral_fees(ctx)
+    }
+
     pub fn close_xyber_core_instruction(_ctx: Context<CloseXyberCore>) -> Result<()> {
         Ok(())
     }
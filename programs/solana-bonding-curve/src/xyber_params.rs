@@ -1,16 +1,32 @@
 use anchor_lang::{prelude::Pubkey, AnchorDeserialize, AnchorSerialize};
 
-use crate::curves::SmoothBondingCurve;
+use crate::curves::BondingCurveType;
+use crate::fees::FeeTier;
 
 pub static DECIMALS: u8 = 9;
 
+/// Most chains a token can launch on; every chain past the first raises the graduation
+/// threshold by 25% (see `effective_threshold_for_chains`).
+pub const MAX_TOTAL_CHAINS: u8 = 8;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct InitCoreParams {
     pub admin: Option<Pubkey>,
     pub grad_threshold: Option<u64>,
-    pub bonding_curve: Option<SmoothBondingCurve>,
+    pub bonding_curve: Option<BondingCurveType>,
     pub accepted_base_mint: Option<Pubkey>,
     pub total_supply: Option<u64>,
+    pub max_price_impact_bps: Option<u64>,
+    pub protocol_fee_bps: Option<u64>,
+    pub creator_fee_bps: Option<u64>,
+    pub referral_share_bps: Option<u64>,
+    pub sniper_fee_bps: Option<u64>,
+    pub sniper_fee_decay_slots: Option<u64>,
+    pub sniper_fee_to_creator: Option<bool>,
+    pub fee_tiers: Option<[FeeTier; 4]>, // MAX_FEE_TIERS
+    pub volume_window_slots: Option<u64>,
+    pub graduation_protocol_fee_bps: Option<u64>,
+    pub graduation_creator_fee_bps: Option<u64>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
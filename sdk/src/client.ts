@@ -25,12 +25,14 @@ import {
     UpdateCoreParams,
     MintSupplyParams,
     XyberCoreAccount,
-    XyberTokenAccount
+    XyberTokenAccount,
+    TradeQuote
 } from './types';
 
 import {
     TOKEN_FACTORY_PROGRAM_ID,
     METAPLEX_PROGRAM_ID,
+    AMM_PROGRAM_ID,
 } from './constants';
 
 import {
@@ -41,6 +43,13 @@ import {
     findEscrowAta,
     findVaultAta,
     findCoreEscrowAta,
+    findFeeVaultPda,
+    findCreatorFeeVaultPda,
+    findReferralPda,
+    findReferralVaultPda,
+    findTraderStatsPda,
+    findAmmPoolPda,
+    findAmmPoolVaultPda,
 } from './pda';
 
 export interface XyberClientConfig {
@@ -50,6 +59,7 @@ export interface XyberClientConfig {
     programId: PublicKey;
     tokenFactoryProgramId?: PublicKey;
     metaplexProgramId?: PublicKey;
+    ammProgramId?: PublicKey;
     confirmOpts?: ConfirmOptions;
 }
 
@@ -58,6 +68,7 @@ export class XyberClient {
     readonly programId: PublicKey;
     readonly tokenFactoryProgramId: PublicKey;
     readonly metaplexProgramId: PublicKey;
+    readonly ammProgramId: PublicKey;
     readonly provider: anchor.AnchorProvider;
     readonly program: BondingCurveProgram;
     readonly payer: Keypair;
@@ -67,6 +78,7 @@ export class XyberClient {
         this.programId = config.programId;
         this.tokenFactoryProgramId = config.tokenFactoryProgramId ?? TOKEN_FACTORY_PROGRAM_ID;
         this.metaplexProgramId = config.metaplexProgramId ?? METAPLEX_PROGRAM_ID;
+        this.ammProgramId = config.ammProgramId ?? AMM_PROGRAM_ID;
         if (config.wallet && config.wallet.payer) {
             this.payer = config.wallet.payer;
         } else if (config.payer) {
@@ -119,6 +131,48 @@ export class XyberClient {
         return findCoreEscrowAta(paymentMint, xyberCorePda);
     }
 
+    getFeeVaultPda(paymentMint: PublicKey): [PublicKey, number] {
+        return findFeeVaultPda(paymentMint, this.programId);
+    }
+
+    getCreatorFeeVaultPda(tokenSeed: PublicKey): [PublicKey, number] {
+        return findCreatorFeeVaultPda(tokenSeed, this.programId);
+    }
+
+    getReferralPda(user: PublicKey): [PublicKey, number] {
+        return findReferralPda(user, this.programId);
+    }
+
+    getReferralVaultPda(referrer: PublicKey, paymentMint: PublicKey): [PublicKey, number] {
+        return findReferralVaultPda(referrer, paymentMint, this.programId);
+    }
+
+    getTraderStatsPda(user: PublicKey): [PublicKey, number] {
+        return findTraderStatsPda(user, this.programId);
+    }
+
+    getAmmPoolPda(paymentMint: PublicKey, tokenSeed: PublicKey): [PublicKey, number] {
+        return findAmmPoolPda(
+            paymentMint,
+            this.getMintPda(tokenSeed)[0],
+            this.getXyberTokenPda(tokenSeed)[0],
+            this.ammProgramId
+        );
+    }
+
+    getAmmPoolVaultPda(pool: PublicKey, mint: PublicKey): [PublicKey, number] {
+        return findAmmPoolVaultPda(pool, mint, this.ammProgramId);
+    }
+
+    /** The referrer recorded for `user`, or null if the user has none yet. */
+    async getReferrer(user: PublicKey): Promise<PublicKey | null> {
+        const referral = await this.program.account.referral.fetchNullable(this.getReferralPda(user)[0]);
+        if (!referral || referral.referrer.equals(PublicKey.default)) {
+            return null;
+        }
+        return referral.referrer;
+    }
+
     async getCoreState(xyberCorePda?: PublicKey): Promise<XyberCoreAccount | null> {
         const pda = xyberCorePda ?? this.getXyberCorePda()[0];
         try {
@@ -182,6 +236,7 @@ export class XyberClient {
                 xyberCore: xyberCorePda,
                 newAcceptedBaseMint: params.acceptedBaseMint,
                 escrowTokenAccount: coreEscrowAta,
+                feeVault: this.getFeeVaultPda(params.acceptedBaseMint)[0],
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -203,15 +258,14 @@ export class XyberClient {
         const [metadataPda] = this.getMetadataPda(mintPda);
         const vaultAta = await this.getVaultAta(tokenSeed);
 
-        let finalPaymentMint = paymentMint;
-        if (!finalPaymentMint) {
-            const coreState = await this.getCoreState(xyberCorePda);
-            if (!coreState || !coreState.acceptedBaseMint) {
-                throw new Error("Payment mint not provided and could not be fetched from core state.");
-            }
-            finalPaymentMint = coreState.acceptedBaseMint;
+        // The core names the treasury and says whether the creation fee is paid in the base mint
+        const coreState = await this.getCoreState(xyberCorePda);
+        if (!coreState) {
+            throw new Error("Core state could not be fetched.");
         }
+        const finalPaymentMint = paymentMint ?? coreState.acceptedBaseMint;
         const escrowAta = await this.getEscrowAta(finalPaymentMint, tokenSeed);
+        const feeInBaseMint = coreState.creationFee.gtn(0) && coreState.creationFeeInBaseMint;
 
         return this.program.methods
             .mintFullSupplyInstruction(params)
@@ -231,6 +285,34 @@ export class XyberClient {
                 tokenFactoryProgram: this.tokenFactoryProgramId,
                 escrowTokenAccount: escrowAta,
                 paymentMint: finalPaymentMint,
+                creatorFeeVault: this.getCreatorFeeVaultPda(tokenSeed)[0],
+                treasury: coreState.admin,
+                creatorTokenAccount: feeInBaseMint
+                    ? await getAssociatedTokenAddress(finalPaymentMint, signer.publicKey)
+                    : null,
+                feeVault: feeInBaseMint ? this.getFeeVaultPda(finalPaymentMint)[0] : null,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    /**
+     * Sets the fee charged for every new launch: `creationFee` lamports, or raw units of the
+     * accepted base mint if `inBaseMint`.
+     */
+    async setCreationFee(
+        creationFee: BN,
+        inBaseMint: boolean,
+        admin?: Signer
+    ): Promise<string> {
+        const signer = admin ?? this.payer;
+        const [xyberCorePda] = this.getXyberCorePda();
+
+        return this.program.methods
+            .setCreationFeeInstruction(creationFee, inBaseMint)
+            .accounts({
+                admin: signer.publicKey,
+                xyberCore: xyberCorePda,
             })
             .signers([signer])
             .rpc();
@@ -241,7 +323,8 @@ export class XyberClient {
         amountBaseIn: BN,
         minAmountOut: BN,
         buyer: Signer,
-        paymentMint?: PublicKey
+        paymentMint?: PublicKey,
+        referrer?: PublicKey
     ): Promise<string> {
         const [xyberCorePda] = this.getXyberCorePda();
         const [xyberTokenPda] = this.getXyberTokenPda(tokenSeed);
@@ -261,6 +344,9 @@ export class XyberClient {
         const buyerTokenAccount = await getAssociatedTokenAddress(mintPda, buyer.publicKey);
         const buyerPaymentAccount = await getAssociatedTokenAddress(finalPaymentMint, buyer.publicKey);
 
+        // Once recorded, the referrer has to be passed on every trade
+        const finalReferrer = referrer ?? (await this.getReferrer(buyer.publicKey));
+
         return this.program.methods
             .buyExactInputInstruction(amountBaseIn, minAmountOut)
             .accounts({
@@ -274,6 +360,68 @@ export class XyberClient {
                 vaultTokenAccount: vaultAta,
                 buyerTokenAccount: buyerTokenAccount,
                 buyerPaymentAccount: buyerPaymentAccount,
+                feeVault: this.getFeeVaultPda(finalPaymentMint)[0],
+                creatorFeeVault: this.getCreatorFeeVaultPda(tokenSeed)[0],
+                referral: this.getReferralPda(buyer.publicKey)[0],
+                referrer: finalReferrer,
+                referralVault: finalReferrer ? this.getReferralVaultPda(finalReferrer, finalPaymentMint)[0] : null,
+                traderStats: this.getTraderStatsPda(buyer.publicKey)[0],
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([buyer])
+            .rpc();
+    }
+
+    async buyExactOutput(
+        tokenSeed: PublicKey,
+        amountTokensOut: BN,
+        maxAmountBaseIn: BN,
+        buyer: Signer,
+        paymentMint?: PublicKey,
+        referrer?: PublicKey
+    ): Promise<string> {
+        const [xyberCorePda] = this.getXyberCorePda();
+        const [xyberTokenPda] = this.getXyberTokenPda(tokenSeed);
+        const [mintPda] = this.getMintPda(tokenSeed);
+
+        let finalPaymentMint = paymentMint;
+        if (!finalPaymentMint) {
+            const coreState = await this.getCoreState(xyberCorePda);
+            if (!coreState || !coreState.acceptedBaseMint) {
+                throw new Error("Payment mint not provided and could not be fetched from core state.");
+            }
+            finalPaymentMint = coreState.acceptedBaseMint;
+        }
+
+        const escrowAta = await this.getEscrowAta(finalPaymentMint, tokenSeed);
+        const vaultAta = await this.getVaultAta(tokenSeed);
+        const buyerTokenAccount = await getAssociatedTokenAddress(mintPda, buyer.publicKey);
+        const buyerPaymentAccount = await getAssociatedTokenAddress(finalPaymentMint, buyer.publicKey);
+
+        // Once recorded, the referrer has to be passed on every trade
+        const finalReferrer = referrer ?? (await this.getReferrer(buyer.publicKey));
+
+        return this.program.methods
+            .buyExactOutputInstruction(amountTokensOut, maxAmountBaseIn)
+            .accounts({
+                xyberCore: xyberCorePda,
+                tokenSeed: tokenSeed,
+                buyer: buyer.publicKey,
+                xyberToken: xyberTokenPda,
+                escrowTokenAccount: escrowAta,
+                paymentMint: finalPaymentMint,
+                mint: mintPda,
+                vaultTokenAccount: vaultAta,
+                buyerTokenAccount: buyerTokenAccount,
+                buyerPaymentAccount: buyerPaymentAccount,
+                feeVault: this.getFeeVaultPda(finalPaymentMint)[0],
+                creatorFeeVault: this.getCreatorFeeVaultPda(tokenSeed)[0],
+                referral: this.getReferralPda(buyer.publicKey)[0],
+                referrer: finalReferrer,
+                referralVault: finalReferrer ? this.getReferralVaultPda(finalReferrer, finalPaymentMint)[0] : null,
+                traderStats: this.getTraderStatsPda(buyer.publicKey)[0],
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -287,7 +435,8 @@ export class XyberClient {
         amountTokensIn: BN,
         minAmountOut: BN,
         user: Signer,
-        paymentMint?: PublicKey
+        paymentMint?: PublicKey,
+        referrer?: PublicKey
     ): Promise<string> {
         const [xyberCorePda] = this.getXyberCorePda();
         const [xyberTokenPda] = this.getXyberTokenPda(tokenSeed);
@@ -307,6 +456,9 @@ export class XyberClient {
         const userTokenAccount = await getAssociatedTokenAddress(mintPda, user.publicKey);
         const userPaymentAccount = await getAssociatedTokenAddress(finalPaymentMint, user.publicKey);
 
+        // Once recorded, the referrer has to be passed on every trade
+        const finalReferrer = referrer ?? (await this.getReferrer(user.publicKey));
+
         return this.program.methods
             .sellExactInputInstruction(amountTokensIn, minAmountOut)
             .accounts({
@@ -320,6 +472,68 @@ export class XyberClient {
                 vaultTokenAccount: vaultAta,
                 userTokenAccount: userTokenAccount,
                 userPaymentAccount: userPaymentAccount,
+                feeVault: this.getFeeVaultPda(finalPaymentMint)[0],
+                creatorFeeVault: this.getCreatorFeeVaultPda(tokenSeed)[0],
+                referral: this.getReferralPda(user.publicKey)[0],
+                referrer: finalReferrer,
+                referralVault: finalReferrer ? this.getReferralVaultPda(finalReferrer, finalPaymentMint)[0] : null,
+                traderStats: this.getTraderStatsPda(user.publicKey)[0],
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([user])
+            .rpc();
+    }
+
+    async sellExactOutput(
+        tokenSeed: PublicKey,
+        amountBaseOut: BN,
+        maxAmountTokensIn: BN,
+        user: Signer,
+        paymentMint?: PublicKey,
+        referrer?: PublicKey
+    ): Promise<string> {
+        const [xyberCorePda] = this.getXyberCorePda();
+        const [xyberTokenPda] = this.getXyberTokenPda(tokenSeed);
+        const [mintPda] = this.getMintPda(tokenSeed);
+
+        let finalPaymentMint = paymentMint;
+        if (!finalPaymentMint) {
+            const coreState = await this.getCoreState(xyberCorePda);
+            if (!coreState || !coreState.acceptedBaseMint) {
+                throw new Error("Payment mint not provided and could not be fetched from core state.");
+            }
+            finalPaymentMint = coreState.acceptedBaseMint;
+        }
+
+        const escrowAta = await this.getEscrowAta(finalPaymentMint, tokenSeed);
+        const vaultAta = await this.getVaultAta(tokenSeed);
+        const userTokenAccount = await getAssociatedTokenAddress(mintPda, user.publicKey);
+        const userPaymentAccount = await getAssociatedTokenAddress(finalPaymentMint, user.publicKey);
+
+        // Once recorded, the referrer has to be passed on every trade
+        const finalReferrer = referrer ?? (await this.getReferrer(user.publicKey));
+
+        return this.program.methods
+            .sellExactOutputInstruction(amountBaseOut, maxAmountTokensIn)
+            .accounts({
+                xyberCore: xyberCorePda,
+                tokenSeed: tokenSeed,
+                user: user.publicKey,
+                xyberToken: xyberTokenPda,
+                escrowTokenAccount: escrowAta,
+                paymentMint: finalPaymentMint,
+                mint: mintPda,
+                vaultTokenAccount: vaultAta,
+                userTokenAccount: userTokenAccount,
+                userPaymentAccount: userPaymentAccount,
+                feeVault: this.getFeeVaultPda(finalPaymentMint)[0],
+                creatorFeeVault: this.getCreatorFeeVaultPda(tokenSeed)[0],
+                referral: this.getReferralPda(user.publicKey)[0],
+                referrer: finalReferrer,
+                referralVault: finalReferrer ? this.getReferralVaultPda(finalReferrer, finalPaymentMint)[0] : null,
+                traderStats: this.getTraderStatsPda(user.publicKey)[0],
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -328,6 +542,94 @@ export class XyberClient {
             .rpc();
     }
 
+    /**
+     * Quotes a buy by simulating `quote_buy_instruction`: with `exactInput` the amount is
+     * the base paid in, otherwise the tokens out. Pass `trader` to price in their volume discount and the
+     * referral split of their recorded referrer, or of `referrer` if they have none yet.
+     */
+    async quoteBuy(
+        tokenSeed: PublicKey,
+        amount: BN,
+        exactInput: boolean,
+        paymentMint?: PublicKey,
+        trader?: PublicKey,
+        referrer?: PublicKey
+    ): Promise<TradeQuote> {
+        const [xyberCorePda] = this.getXyberCorePda();
+        const [xyberTokenPda] = this.getXyberTokenPda(tokenSeed);
+        const vaultAta = await this.getVaultAta(tokenSeed);
+
+        let finalPaymentMint = paymentMint;
+        if (!finalPaymentMint) {
+            const coreState = await this.getCoreState(xyberCorePda);
+            if (!coreState || !coreState.acceptedBaseMint) {
+                throw new Error("Payment mint not provided and could not be fetched from core state.");
+            }
+            finalPaymentMint = coreState.acceptedBaseMint;
+        }
+
+        const recordedReferrer = trader ? await this.getReferrer(trader) : null;
+
+        return this.program.methods
+            .quoteBuyInstruction(amount, exactInput)
+            .accounts({
+                tokenSeed: tokenSeed,
+                xyberCore: xyberCorePda,
+                xyberToken: xyberTokenPda,
+                vaultTokenAccount: vaultAta,
+                paymentMint: finalPaymentMint,
+                trader: trader ?? PublicKey.default,
+                traderStats: trader ? this.getTraderStatsPda(trader)[0] : null,
+                referral: recordedReferrer ? this.getReferralPda(trader!)[0] : null,
+                referrer: referrer ?? recordedReferrer,
+            })
+            .view();
+    }
+
+    /**
+     * Quotes a sell by simulating `quote_sell_instruction`: with `exactInput` the amount is
+     * the tokens paid in, otherwise the base out. Pass `trader` to price in their volume discount and the
+     * referral split of their recorded referrer, or of `referrer` if they have none yet.
+     */
+    async quoteSell(
+        tokenSeed: PublicKey,
+        amount: BN,
+        exactInput: boolean,
+        paymentMint?: PublicKey,
+        trader?: PublicKey,
+        referrer?: PublicKey
+    ): Promise<TradeQuote> {
+        const [xyberCorePda] = this.getXyberCorePda();
+        const [xyberTokenPda] = this.getXyberTokenPda(tokenSeed);
+        const vaultAta = await this.getVaultAta(tokenSeed);
+
+        let finalPaymentMint = paymentMint;
+        if (!finalPaymentMint) {
+            const coreState = await this.getCoreState(xyberCorePda);
+            if (!coreState || !coreState.acceptedBaseMint) {
+                throw new Error("Payment mint not provided and could not be fetched from core state.");
+            }
+            finalPaymentMint = coreState.acceptedBaseMint;
+        }
+
+        const recordedReferrer = trader ? await this.getReferrer(trader) : null;
+
+        return this.program.methods
+            .quoteSellInstruction(amount, exactInput)
+            .accounts({
+                tokenSeed: tokenSeed,
+                xyberCore: xyberCorePda,
+                xyberToken: xyberTokenPda,
+                vaultTokenAccount: vaultAta,
+                paymentMint: finalPaymentMint,
+                trader: trader ?? PublicKey.default,
+                traderStats: trader ? this.getTraderStatsPda(trader)[0] : null,
+                referral: recordedReferrer ? this.getReferralPda(trader!)[0] : null,
+                referrer: referrer ?? recordedReferrer,
+            })
+            .view();
+    }
+
     async withdrawLiquidity(
         tokenSeed: PublicKey,
         admin?: Signer,
@@ -352,6 +654,13 @@ export class XyberClient {
         const adminBaseAta = await getAssociatedTokenAddress(finalPaymentMint, signer.publicKey);
         const adminVaultAta = await getAssociatedTokenAddress(mintPda, signer.publicKey);
 
+        // The creator's graduation fee goes to their base token ATA
+        const tokenState = await this.getTokenState(tokenSeed, xyberTokenPda);
+        if (!tokenState) {
+            throw new Error("Token state could not be fetched.");
+        }
+        const creatorBaseAta = await getAssociatedTokenAddress(finalPaymentMint, tokenState.creator);
+
         return this.program.methods
             .withdrawLiquidity()
             .accounts({
@@ -359,13 +668,72 @@ export class XyberClient {
                 xyberCore: xyberCorePda,
                 xyberToken: xyberTokenPda,
                 tokenSeed: tokenSeed,
-                creator: signer.publicKey,
+                creator: tokenState.creator,
                 escrowTokenAccount: escrowAta,
                 baseTokenMint: finalPaymentMint,
                 mint: mintPda,
                 vaultTokenAccount: vaultAta,
                 adminTokenAccount: adminBaseAta,
                 adminVaultAccount: adminVaultAta,
+                creatorTokenAccount: creatorBaseAta,
+                feeVault: this.getFeeVaultPda(finalPaymentMint)[0],
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    /**
+     * Migrates a graduated token into a constant-product AMM pool seeded at the final curve
+     * price, after paying the graduation fees.
+     */
+    async migrate(
+        tokenSeed: PublicKey,
+        admin?: Signer,
+        paymentMint?: PublicKey
+    ): Promise<string> {
+        const signer = admin ?? this.payer;
+        const [xyberCorePda] = this.getXyberCorePda();
+        const [xyberTokenPda] = this.getXyberTokenPda(tokenSeed);
+        const [mintPda] = this.getMintPda(tokenSeed);
+
+        let finalPaymentMint = paymentMint;
+        if (!finalPaymentMint) {
+            const coreState = await this.getCoreState(xyberCorePda);
+            if (!coreState || !coreState.acceptedBaseMint) {
+                throw new Error("Payment mint not provided and could not be fetched from core state.");
+            }
+            finalPaymentMint = coreState.acceptedBaseMint;
+        }
+
+        const tokenState = await this.getTokenState(tokenSeed, xyberTokenPda);
+        if (!tokenState) {
+            throw new Error("Token state could not be fetched.");
+        }
+        const [poolPda] = this.getAmmPoolPda(finalPaymentMint, tokenSeed);
+
+        return this.program.methods
+            .migrate()
+            .accounts({
+                xyberCore: xyberCorePda,
+                admin: signer.publicKey,
+                xyberToken: xyberTokenPda,
+                tokenSeed: tokenSeed,
+                creator: tokenState.creator,
+                mint: mintPda,
+                vaultTokenAccount: await this.getVaultAta(tokenSeed),
+                escrowTokenAccount: await this.getEscrowAta(finalPaymentMint, tokenSeed),
+                baseTokenMint: finalPaymentMint,
+                adminTokenAccount: await getAssociatedTokenAddress(finalPaymentMint, signer.publicKey),
+                creatorTokenAccount: await getAssociatedTokenAddress(finalPaymentMint, tokenState.creator),
+                feeVault: this.getFeeVaultPda(finalPaymentMint)[0],
+                pool: poolPda,
+                poolBaseVault: this.getAmmPoolVaultPda(poolPda, finalPaymentMint)[0],
+                poolTokenVault: this.getAmmPoolVaultPda(poolPda, mintPda)[0],
+                ammProgram: this.ammProgramId,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -374,4 +742,151 @@ export class XyberClient {
             .signers([signer])
             .rpc();
     }
-}
\ No newline at end of file
+
+    async sweepDonations(
+        tokenSeed: PublicKey,
+        admin?: Signer,
+        paymentMint?: PublicKey
+    ): Promise<string> {
+        const signer = admin ?? this.payer;
+        const [xyberCorePda] = this.getXyberCorePda();
+        const [xyberTokenPda] = this.getXyberTokenPda(tokenSeed);
+
+        let finalPaymentMint = paymentMint;
+        if (!finalPaymentMint) {
+            const coreState = await this.getCoreState(xyberCorePda);
+            if (!coreState || !coreState.acceptedBaseMint) {
+                throw new Error("Payment mint not provided and could not be fetched from core state.");
+            }
+            finalPaymentMint = coreState.acceptedBaseMint;
+        }
+
+        const escrowAta = await this.getEscrowAta(finalPaymentMint, tokenSeed);
+        const adminBaseAta = await getAssociatedTokenAddress(finalPaymentMint, signer.publicKey);
+
+        return this.program.methods
+            .sweepDonationsInstruction()
+            .accounts({
+                admin: signer.publicKey,
+                xyberCore: xyberCorePda,
+                xyberToken: xyberTokenPda,
+                tokenSeed: tokenSeed,
+                escrowTokenAccount: escrowAta,
+                baseTokenMint: finalPaymentMint,
+                adminTokenAccount: adminBaseAta,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    async claimProtocolFees(
+        admin?: Signer,
+        paymentMint?: PublicKey
+    ): Promise<string> {
+        const signer = admin ?? this.payer;
+        const [xyberCorePda] = this.getXyberCorePda();
+
+        let finalPaymentMint = paymentMint;
+        if (!finalPaymentMint) {
+            const coreState = await this.getCoreState(xyberCorePda);
+            if (!coreState || !coreState.acceptedBaseMint) {
+                throw new Error("Payment mint not provided and could not be fetched from core state.");
+            }
+            finalPaymentMint = coreState.acceptedBaseMint;
+        }
+
+        const [feeVaultPda] = this.getFeeVaultPda(finalPaymentMint);
+        const adminBaseAta = await getAssociatedTokenAddress(finalPaymentMint, signer.publicKey);
+
+        return this.program.methods
+            .claimProtocolFees()
+            .accounts({
+                xyberCore: xyberCorePda,
+                admin: signer.publicKey,
+                baseTokenMint: finalPaymentMint,
+                feeVault: feeVaultPda,
+                adminTokenAccount: adminBaseAta,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    async claimCreatorFees(
+        tokenSeed: PublicKey,
+        creator?: Signer,
+        paymentMint?: PublicKey
+    ): Promise<string> {
+        const signer = creator ?? this.payer;
+        const [xyberCorePda] = this.getXyberCorePda();
+        const [xyberTokenPda] = this.getXyberTokenPda(tokenSeed);
+
+        let finalPaymentMint = paymentMint;
+        if (!finalPaymentMint) {
+            const coreState = await this.getCoreState(xyberCorePda);
+            if (!coreState || !coreState.acceptedBaseMint) {
+                throw new Error("Payment mint not provided and could not be fetched from core state.");
+            }
+            finalPaymentMint = coreState.acceptedBaseMint;
+        }
+
+        const [creatorFeeVaultPda] = this.getCreatorFeeVaultPda(tokenSeed);
+        const creatorBaseAta = await getAssociatedTokenAddress(finalPaymentMint, signer.publicKey);
+
+        return this.program.methods
+            .claimCreatorFees()
+            .accounts({
+                creator: signer.publicKey,
+                xyberToken: xyberTokenPda,
+                tokenSeed: tokenSeed,
+                baseTokenMint: finalPaymentMint,
+                creatorFeeVault: creatorFeeVaultPda,
+                creatorTokenAccount: creatorBaseAta,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    async claimReferralFees(
+        referrer?: Signer,
+        paymentMint?: PublicKey
+    ): Promise<string> {
+        const signer = referrer ?? this.payer;
+        const [xyberCorePda] = this.getXyberCorePda();
+
+        let finalPaymentMint = paymentMint;
+        if (!finalPaymentMint) {
+            const coreState = await this.getCoreState(xyberCorePda);
+            if (!coreState || !coreState.acceptedBaseMint) {
+                throw new Error("Payment mint not provided and could not be fetched from core state.");
+            }
+            finalPaymentMint = coreState.acceptedBaseMint;
+        }
+
+        const [referralVaultPda] = this.getReferralVaultPda(signer.publicKey, finalPaymentMint);
+        const referrerBaseAta = await getAssociatedTokenAddress(finalPaymentMint, signer.publicKey);
+
+        return this.program.methods
+            .claimReferralFees()
+            .accounts({
+                xyberCore: xyberCorePda,
+                referrer: signer.publicKey,
+                baseTokenMint: finalPaymentMint,
+                referralVault: referralVaultPda,
+                referrerTokenAccount: referrerBaseAta,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+    }
+}
@@ -9,6 +9,11 @@ export const METAPLEX_PROGRAM_ID = new PublicKey(
     process.env.METAPLEX_PROGRAM_ID || "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
 );
 
+// Constant-product AMM that graduated tokens migrate into (the in-workspace stand-in by default)
+export const AMM_PROGRAM_ID = new PublicKey(
+    process.env.AMM_PROGRAM_ID || "6YiGBs6Hp1c7zRF4bq8bWMKj2JLDdN3t6ihXWJ7NpG49"
+);
+
 // System program
 export { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
 export const SYSTEM_PROGRAM_ID = SystemProgram.programId;
@@ -1,6 +1,6 @@
 import { PublicKey } from '@solana/web3.js';
 import { getAssociatedTokenAddress } from '@solana/spl-token';
-import { TOKEN_FACTORY_PROGRAM_ID, METAPLEX_PROGRAM_ID } from './constants';
+import { TOKEN_FACTORY_PROGRAM_ID, METAPLEX_PROGRAM_ID, AMM_PROGRAM_ID } from './constants';
 
 export function findXyberCorePda(programId: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
@@ -56,4 +56,53 @@ export async function findCoreEscrowAta(paymentMint: PublicKey, xyberCorePda: Pu
         xyberCorePda,
         true
     );
-}
\ No newline at end of file
+}
+
+export function findFeeVaultPda(paymentMint: PublicKey, programId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), paymentMint.toBuffer()],
+        programId
+    );
+}
+
+export function findCreatorFeeVaultPda(tokenSeed: PublicKey, programId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("creator_fee_vault"), tokenSeed.toBuffer()],
+        programId
+    );
+}
+
+export function findReferralPda(user: PublicKey, programId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), user.toBuffer()],
+        programId
+    );
+}
+
+export function findReferralVaultPda(referrer: PublicKey, paymentMint: PublicKey, programId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("referral_vault"), referrer.toBuffer(), paymentMint.toBuffer()],
+        programId
+    );
+}
+
+export function findTraderStatsPda(user: PublicKey, programId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("trader_stats"), user.toBuffer()],
+        programId
+    );
+}
+
+export function findAmmPoolPda(baseMint: PublicKey, tokenMint: PublicKey, depositor: PublicKey, ammProgramId: PublicKey = AMM_PROGRAM_ID): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), baseMint.toBuffer(), tokenMint.toBuffer(), depositor.toBuffer()],
+        ammProgramId
+    );
+}
+
+export function findAmmPoolVaultPda(pool: PublicKey, mint: PublicKey, ammProgramId: PublicKey = AMM_PROGRAM_ID): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("pool_vault"), pool.toBuffer(), mint.toBuffer()],
+        ammProgramId
+    );
+}
@@ -5,18 +5,103 @@ import { BondingCurve, IDL } from '../../target/types/bonding_curve';
 export { BondingCurve, IDL };
 export type BondingCurveProgram = Program<BondingCurve>;
 
-export interface BondingCurveParams {
+export interface SmoothBondingCurveParams {
     aTotalTokens: BN;
     kVirtualPoolOffset: BN;
     cBondingScaleFactor: BN;
 }
 
+export interface LinearBondingCurveParams {
+    aTotalTokens: BN;
+    bVirtualTokenOffset: BN;
+    mPriceDivisor: BN;
+}
+
+export interface ExponentialBondingCurveParams {
+    aTotalTokens: BN;
+    vVirtualBaseReserve: BN;
+    lGrowthTokens: BN;
+}
+
+export interface ConstantProductBondingCurveParams {
+    virtualBaseReserve: BN;
+    virtualTokenReserve: BN;
+    realTokenReserve: BN;
+}
+
+export interface LogisticBondingCurveParams {
+    aTotalTokens: BN;
+    pMinPrice: BN;
+    pMaxPrice: BN;
+    mMidpointTokens: BN;
+    sScaleTokens: BN;
+}
+
+export interface FlatSegmentParams {
+    price: BN;
+}
+
+// Mirrors the on-chain `SegmentCurve` enum.
+export type SegmentCurveParams =
+    | { flat: { "0": FlatSegmentParams } }
+    | { smooth: { "0": SmoothBondingCurveParams } }
+    | { linear: { "0": LinearBondingCurveParams } }
+    | { exponential: { "0": ExponentialBondingCurveParams } }
+    | { constantProduct: { "0": ConstantProductBondingCurveParams } }
+    | { logistic: { "0": LogisticBondingCurveParams } };
+
+export interface PiecewiseSegmentParams {
+    xStart: BN;
+    curve: SegmentCurveParams;
+}
+
+export interface PiecewiseBondingCurveParams {
+    segments: PiecewiseSegmentParams[];
+}
+
+// Mirrors the on-chain `BondingCurveType` enum (tuple variants are keyed by "0").
+export type BondingCurveParams =
+    | { smooth: { "0": SmoothBondingCurveParams } }
+    | { linear: { "0": LinearBondingCurveParams } }
+    | { exponential: { "0": ExponentialBondingCurveParams } }
+    | { constantProduct: { "0": ConstantProductBondingCurveParams } }
+    | { logistic: { "0": LogisticBondingCurveParams } }
+    | { piecewise: { "0": PiecewiseBondingCurveParams } };
+
 export interface UpdateCoreParams {
     admin?: PublicKey;
     gradThreshold: BN;
     totalSupply: BN;
     bondingCurve: BondingCurveParams;
     acceptedBaseMint: PublicKey;
+    // Largest spot price move per trade in bps of the pre-trade price (0 = no limit)
+    maxPriceImpactBps?: BN;
+    // Trading fee in bps of the base side of every trade, collected in the fee vault
+    protocolFeeBps?: BN;
+    // Creator's slice of every trade in bps, charged on top of the protocol fee
+    creatorFeeBps?: BN;
+    // Referrer's share of the protocol fee, in bps of it
+    referralShareBps?: BN;
+    // Anti-sniper fee of new launches: the rate in the launch slot, the slots it takes to decay
+    // to the regular fee, and whether the surcharge goes to the creator instead of the protocol
+    sniperFeeBps?: BN;
+    sniperFeeDecaySlots?: BN;
+    sniperFeeToCreator?: boolean;
+    // Volume discount tiers (up to 4) and the rolling window, in slots, that trader volume is
+    // measured over; a window of 0 never expires volume
+    feeTiers?: FeeTier[];
+    volumeWindowSlots?: BN;
+    // Shares of the escrow paid to the protocol and the creator when a graduated token's
+    // liquidity is withdrawn
+    graduationProtocolFeeBps?: BN;
+    graduationCreatorFeeBps?: BN;
+}
+
+// Mirrors the on-chain `FeeTier`: traders whose rolling volume reaches `minVolume` get
+// `discountBps` off the regular fee.
+export interface FeeTier {
+    minVolume: BN;
+    discountBps: BN;
 }
 
 export interface MintSupplyParams {
@@ -26,5 +111,21 @@ export interface MintSupplyParams {
     totalChains: number;
 }
 
+// Mirrors the on-chain `TradeQuote` returned by the quote instructions.
+export interface TradeQuote {
+    baseAmount: BN;
+    protocolFee: BN;
+    creatorFee: BN;
+    referralFee: BN;
+    sniperFee: BN;
+    tokenAmount: BN;
+    newReserve: BN;
+    spotPriceBefore: BN;
+    spotPriceAfter: BN;
+    priceImpactBps: BN;
+    tokensRemaining: BN;
+    partialFill: boolean;
+}
+
 export type XyberCoreAccount = Awaited<ReturnType<BondingCurveProgram['account']['xyberCore']['fetch']>>;
 export type XyberTokenAccount = Awaited<ReturnType<BondingCurveProgram['account']['xyberToken']['fetch']>>;
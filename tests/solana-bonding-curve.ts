@@ -7,6 +7,7 @@ import {
   Transaction,
 } from "@solana/web3.js";
 import {
+  createMint,
   getAssociatedTokenAddress,
   getAccount,
   TOKEN_PROGRAM_ID,
@@ -14,28 +15,32 @@ import {
 } from "@solana/spl-token";
 import { assert } from "chai";
 import { BondingCurve } from "../target/types/bonding_curve";
+import { ConstantProductAmm } from "../target/types/constant_product_amm";
 import { BUYER_KEYPAIR_PATH, CREATOR_KEYPAIR_PATH, DEVNET_URL, METAPLEX_PROGRAM_ID, PAYMENT_MINT_PUBKEY, TOKEN_FACTORY_PROGRAM_ID } from "./constants";
 
 const TOTAL_SUPPLY = new BN(1073000191); // TODO: confirm the correct TOTAL_SUPPLY value
 const DECIMALS = 9;
 const LAMPORTS_PER_TOKEN = 10 ** DECIMALS;
 
-// SETUP – XyberToken – XBT
-const A_TOTAL_TOKENS = new BN("975000000000");
-const K_VIRTUAL_POOL_OFFSET = new BN("904507500000000")
-  .mul(new BN(LAMPORTS_PER_TOKEN));
-
-const C_BONDING_K_VIRTUAL = new BN("927000")
-  .mul(new BN(LAMPORTS_PER_TOKEN));
+// Curve parameters are in raw token base units (scaled by 10^DECIMALS),
+// the same units the program mints, transfers and trades in.
 
+// SETUP – XyberToken – XBT
+// (A = 975B tokens does not fit into u64 once scaled to raw units)
+// const A_TOTAL_TOKENS = new BN("975000000000").mul(new BN(LAMPORTS_PER_TOKEN));
+// const K_VIRTUAL_POOL_OFFSET = new BN("904507500000000")
+//   .mul(new BN(LAMPORTS_PER_TOKEN))
+//   .mul(new BN(LAMPORTS_PER_TOKEN));
+// const C_BONDING_K_VIRTUAL = new BN("927000").mul(new BN(LAMPORTS_PER_TOKEN));
 // const GRADUATE_THRESHOLD = new BN("2000000"); // FOR PRODUCTION
-const GRADUATE_THRESHOLD = new BN("1000"); // FOR TESTING
 
 // SETUP - ORIGINAL - PUMP.FUN - SOLANA
-// const A_TOTAL_TOKENS = new BN("1073000191");
-// const K_VIRTUAL_POOL_OFFSET = new BN("32190005730").mul(new BN(LAMPORTS_PER_TOKEN));
-// const C_BONDING_K_VIRTUAL = new BN(30 * LAMPORTS_PER_TOKEN);
-// const GRADUATE_THRESHOLD = new BN("428");
+const A_TOTAL_TOKENS = new BN("1073000191").mul(new BN(LAMPORTS_PER_TOKEN));
+const K_VIRTUAL_POOL_OFFSET = new BN("32190005730")
+  .mul(new BN(LAMPORTS_PER_TOKEN))
+  .mul(new BN(LAMPORTS_PER_TOKEN));
+const C_BONDING_K_VIRTUAL = new BN(30 * LAMPORTS_PER_TOKEN);
+const GRADUATE_THRESHOLD = new BN("1000"); // FOR TESTING
 
 // Metadata parameters for the project token
 const now = new Date();
@@ -85,17 +90,31 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.BondingCurve as Program<BondingCurve>;
+  const ammProgram = anchor.workspace.ConstantProductAmm as Program<ConstantProductAmm>;
 
   // 2) Variables / PDAs
   let tokenSeedKeypair: Keypair;
   let xyberTokenPda: PublicKey;
   let xyberCorePda: PublicKey;
+  let feeVaultPda: PublicKey;
+  let creatorFeeVaultPda: PublicKey;
+  let buyerReferralPda: PublicKey;
+  let buyerTraderStatsPda: PublicKey;
   let mintPda: PublicKey;
   let vaultTokenAccount: PublicKey;
   let creatorTokenAccount: PublicKey;
   let escrowTokenAccount: PublicKey;
   let metadataPda: PublicKey;
 
+  // Raw balance of a token account, 0 if it does not exist yet
+  async function tokenBalance(address: PublicKey): Promise<bigint> {
+    try {
+      return (await getAccount(connection, address)).amount;
+    } catch {
+      return BigInt(0);
+    }
+  }
+
   // Derive PDAs in before() hook
   before("Derive all PDAs", async () => {
     // XyberCore PDA
@@ -104,6 +123,12 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
       program.programId
     );
 
+    // Protocol fee vault for the payment mint
+    [feeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), PAYMENT_MINT_PUBKEY.toBuffer()],
+      program.programId
+    );
+
     // XyberToken PDA
     tokenSeedKeypair = Keypair.generate();
     [xyberTokenPda] = PublicKey.findProgramAddressSync(
@@ -114,6 +139,24 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
       program.programId
     );
 
+    // Buyer's referral record (the tests trade without a referrer)
+    [buyerReferralPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referral"), buyerKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // Buyer's rolling volume stats used for fee tiers
+    [buyerTraderStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("trader_stats"), buyerKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // Creator fee vault of the token
+    [creatorFeeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator_fee_vault"), tokenSeedKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+
     // Mint PDA (token_factory seeds)
     [mintPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("MINT"), tokenSeedKeypair.publicKey.toBuffer()],
@@ -173,9 +216,13 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
       gradThreshold: GRADUATE_THRESHOLD,
       totalSupply: TOTAL_SUPPLY,
       bondingCurve: {
-        aTotalTokens: A_TOTAL_TOKENS,
-        kVirtualPoolOffset: K_VIRTUAL_POOL_OFFSET,
-        cBondingScaleFactor: C_BONDING_K_VIRTUAL,
+        smooth: {
+          "0": {
+            aTotalTokens: A_TOTAL_TOKENS,
+            kVirtualPoolOffset: K_VIRTUAL_POOL_OFFSET,
+            cBondingScaleFactor: C_BONDING_K_VIRTUAL,
+          },
+        },
       },
       acceptedBaseMint: PAYMENT_MINT_PUBKEY,
     };
@@ -195,6 +242,7 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
 
         newAcceptedBaseMint: PAYMENT_MINT_PUBKEY,
         escrowTokenAccount: escrowTokenAccountPda,
+        feeVault: feeVaultPda,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 
@@ -213,14 +261,17 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
     const xyberState = await program.account.xyberCore.fetch(xyberCorePda);
     console.log("After update_core, XYBER state:", xyberState);
     if (xyberState) {
+      // Only the smooth curve variant is configured by this test
+      const smoothCurve = (xyberState.bondingCurve as any)?.smooth?.["0"];
+
       // Create the human-readable object using optional chaining for safety
       const readableState = {
         admin: xyberState.admin?.toString() ?? "N/A",
         gradThreshold: formatBnHumanReadable(xyberState.gradThreshold),
         bondingCurve: {
-          aTotalTokens: formatBnHumanReadable(xyberState.bondingCurve?.aTotalTokens),
-          kVirtualPoolOffset: formatBnHumanReadable(xyberState.bondingCurve?.kVirtualPoolOffset),
-          cBondingScaleFactor: formatBnHumanReadable(xyberState.bondingCurve?.cBondingScaleFactor),
+          aTotalTokens: formatBnHumanReadable(smoothCurve?.aTotalTokens),
+          kVirtualPoolOffset: formatBnHumanReadable(smoothCurve?.kVirtualPoolOffset),
+          cBondingScaleFactor: formatBnHumanReadable(smoothCurve?.cBondingScaleFactor),
         },
         acceptedBaseMint: xyberState.acceptedBaseMint?.toString() ?? "N/A",
       };
@@ -243,6 +294,65 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
     }
   });
 
+  it("1.1 – mint_full_supply_instruction rejects a payment mint other than the core's", async () => {
+    // A mint with fewer decimals would shrink the graduation threshold and leave the
+    // creator fee vault unable to take the core's base mint
+    const foreignMint = await createMint(connection, creatorKeypair, creatorKeypair.publicKey, null, 0);
+    const foreignSeed = Keypair.generate().publicKey;
+    const [foreignXyberToken] = PublicKey.findProgramAddressSync(
+      [Buffer.from("xyber_token"), foreignSeed.toBuffer()],
+      program.programId
+    );
+    const [foreignTokenMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("MINT"), foreignSeed.toBuffer()],
+      TOKEN_FACTORY_PROGRAM_ID
+    );
+    const [foreignMetadata] = PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), METAPLEX_PROGRAM_ID.toBuffer(), foreignTokenMint.toBuffer()],
+      METAPLEX_PROGRAM_ID
+    );
+    const [foreignCreatorFeeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator_fee_vault"), foreignSeed.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .mintFullSupplyInstruction({
+          name: tokenName,
+          symbol: tokenSymbol,
+          uri: tokenUri,
+          totalChains: 1,
+          tokenSeed: foreignSeed,
+        })
+        .accounts({
+          xyberCore: xyberCorePda,
+          xyberToken: foreignXyberToken,
+          creator: creatorKeypair.publicKey,
+          mint: foreignTokenMint,
+          vaultTokenAccount: await getAssociatedTokenAddress(foreignTokenMint, foreignXyberToken, true),
+          metadataAccount: foreignMetadata,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          tokenMetadataProgram: METAPLEX_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenFactoryProgram: TOKEN_FACTORY_PROGRAM_ID,
+          escrowTokenAccount: await getAssociatedTokenAddress(foreignMint, foreignXyberToken, true),
+          paymentMint: foreignMint,
+          creatorFeeVault: foreignCreatorFeeVault,
+          treasury: creatorKeypair.publicKey,
+          creatorTokenAccount: null,
+          feeVault: null,
+        })
+        .signers([creatorKeypair])
+        .rpc();
+      assert.fail("Minting with a foreign payment mint should fail");
+    } catch (err) {
+      assert.include(err.toString(), "WrongPaymentMint");
+    }
+  });
+
   // 3.2) init_and_mint_full_supply_instruction
   it("2 – mint_full_supply_instruction", async () => {
     console.log("----- Step 3: mint_full_supply_instruction -----");
@@ -271,6 +381,11 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
         tokenFactoryProgram: TOKEN_FACTORY_PROGRAM_ID,
         escrowTokenAccount: escrowTokenAccount,
         paymentMint: PAYMENT_MINT_PUBKEY,
+        creatorFeeVault: creatorFeeVaultPda,
+        // No creation fee is configured, so only the treasury is passed
+        treasury: creatorKeypair.publicKey,
+        creatorTokenAccount: null,
+        feeVault: null,
       })
       .instruction();
 
@@ -310,6 +425,24 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
     const baseIn = new BN(0.1 * LAMPORTS_PER_TOKEN);
     const expectedOut = new BN(1);
 
+    // 3b) Quote the same buy by simulating quote_buy_instruction (decoded from the return data)
+    const quote = await program.methods
+      .quoteBuyInstruction(baseIn, true)
+      .accounts({
+        tokenSeed: tokenSeedKeypair.publicKey,
+        xyberCore: xyberCorePda,
+        xyberToken: xyberTokenPda,
+        vaultTokenAccount: vaultTokenAccount,
+        paymentMint: PAYMENT_MINT_PUBKEY,
+        trader: buyerKeypair.publicKey,
+        traderStats: null, // the buyer has no volume yet
+        referral: null,
+        referrer: null,
+      })
+      .view();
+    console.log("Quoted tokens out =", quote.tokenAmount.toString());
+    const tokensBefore = await tokenBalance(buyerTokenAccount);
+
     // 4) Call the instruction
     await program.methods
       .buyExactInputInstruction(baseIn, expectedOut)
@@ -324,6 +457,12 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
         vaultTokenAccount: vaultTokenAccount,
         buyerTokenAccount: buyerTokenAccount,
         buyerPaymentAccount: buyerPaymentAccount,
+        feeVault: feeVaultPda,
+        creatorFeeVault: creatorFeeVaultPda,
+        referral: buyerReferralPda,
+        referrer: null,
+        referralVault: null,
+        traderStats: buyerTraderStatsPda,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -343,6 +482,13 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
       buyerAtaInfo.amount > BigInt(0),
       "Buyer should have received some project tokens."
     );
+
+    // 7) The buy filled exactly what was quoted
+    assert.equal(
+      (buyerAtaInfo.amount - tokensBefore).toString(),
+      quote.tokenAmount.toString(),
+      "Buyer should receive the quoted tokens."
+    );
   });
 
   // 3.4) Buyer sells token with exact input
@@ -357,24 +503,40 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
       buyerKeypair.publicKey
     );
 
-    // 2) Let's sell half of the tokens buyer has (in unscaled "curve units")
+    // 2) Let's sell half of the tokens buyer has (in raw token base units)
     const buyerAtaInfo = await getAccount(connection, buyerTokenAccount);
     const tokensBuyerHasRaw = buyerAtaInfo.amount;
     console.log("Buyer raw token balance =>", tokensBuyerHasRaw.toString());
 
-    // Convert raw tokens to unscaled
-    // Because on-chain "sell_exact_input" expects unscaled units
-    const tokensBuyerHasUnscaled = new BN(tokensBuyerHasRaw.toString()).div(
-      new BN(10 ** DECIMALS)
-    );
+    // On-chain "sell_exact_input" takes the same raw units as the token account
+    const halfTokensRaw = new BN(tokensBuyerHasRaw.toString()).divn(2);
+    console.log("Selling (raw) =>", halfTokensRaw.toString());
 
-    // We'll sell half of those unscaled tokens
-    const halfTokensInCurveUnits = tokensBuyerHasUnscaled.divn(2);
-    console.log("Selling (unscaled) =>", halfTokensInCurveUnits.toString());
+    // Quote the same sell by simulating quote_sell_instruction (decoded from the return data)
+    const quote = await program.methods
+      .quoteSellInstruction(halfTokensRaw, true)
+      .accounts({
+        tokenSeed: tokenSeedKeypair.publicKey,
+        xyberCore: xyberCorePda,
+        xyberToken: xyberTokenPda,
+        vaultTokenAccount: vaultTokenAccount,
+        paymentMint: PAYMENT_MINT_PUBKEY,
+        trader: buyerKeypair.publicKey,
+        traderStats: buyerTraderStatsPda,
+        referral: buyerReferralPda, // recorded without a referrer by the buy
+        referrer: null,
+      })
+      .view();
+    const quotedNet = quote.baseAmount
+      .sub(quote.protocolFee)
+      .sub(quote.creatorFee)
+      .sub(quote.referralFee);
+    console.log("Quoted base out (after fees) =>", quotedNet.toString());
+    const paymentBefore = await tokenBalance(buyerPaymentAccount);
 
     let slippage = new BN(1);
     await program.methods
-      .sellExactInputInstruction(halfTokensInCurveUnits, slippage)
+      .sellExactInputInstruction(halfTokensRaw, slippage)
       .accounts({
         xyberCore: xyberCorePda,
         tokenSeed: tokenSeedKeypair.publicKey,
@@ -386,6 +548,12 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
         vaultTokenAccount: vaultTokenAccount,
         userTokenAccount: buyerTokenAccount,
         userPaymentAccount: buyerPaymentAccount,
+        feeVault: feeVaultPda,
+        creatorFeeVault: creatorFeeVaultPda,
+        referral: buyerReferralPda,
+        referrer: null,
+        referralVault: null,
+        traderStats: buyerTraderStatsPda,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -399,6 +567,11 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
       buyerAtaInfoAfter.amount < buyerAtaInfo.amount,
       "Buyer’s token balance should decrease after selling"
     );
+    assert.equal(
+      ((await tokenBalance(buyerPaymentAccount)) - paymentBefore).toString(),
+      quotedNet.toString(),
+      "Seller should receive the quoted base after fees"
+    );
     console.log(
       "SellExactInput done. Before=",
       buyerAtaInfo.amount.toString(),
@@ -563,6 +736,12 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
         vaultTokenAccount: vaultTokenAccount,
         buyerTokenAccount: buyerTokenAccount,
         buyerPaymentAccount: buyerPaymentAccount,
+        feeVault: feeVaultPda,
+        creatorFeeVault: creatorFeeVaultPda,
+        referral: buyerReferralPda,
+        referrer: null,
+        referralVault: null,
+        traderStats: buyerTraderStatsPda,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -626,6 +805,9 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
           vaultTokenAccount: vaultTokenAccount,
           adminTokenAccount: adminBaseAta,
           adminVaultAccount: adminVaultAta,
+          // The creator is also the admin here, so their base ATA is the same
+          creatorTokenAccount: adminBaseAta,
+          feeVault: feeVaultPda,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -640,4 +822,273 @@ describe("Bonding Curve Program (Token Init + Buyer/Seller Flow)", () => {
       assert.fail(`Unexpected error: ${err.toString()}`);
     }
   });
+
+  it("2.15) A pool opened ahead of migration doesn't take the migration pool's address", async () => {
+    // The buyer opens a pool for the same mints with their own tokens before the admin migrates
+    const [buyerPool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), PAYMENT_MINT_PUBKEY.toBuffer(), mintPda.toBuffer(), buyerKeypair.publicKey.toBuffer()],
+      ammProgram.programId
+    );
+    const [migrationPool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), PAYMENT_MINT_PUBKEY.toBuffer(), mintPda.toBuffer(), xyberTokenPda.toBuffer()],
+      ammProgram.programId
+    );
+    assert.ok(!buyerPool.equals(migrationPool), "Pools of different depositors should not collide");
+
+    await ammProgram.methods
+      .createPool(new BN(1), new BN(1))
+      .accounts({
+        payer: buyerKeypair.publicKey,
+        depositor: buyerKeypair.publicKey,
+        baseMint: PAYMENT_MINT_PUBKEY,
+        tokenMint: mintPda,
+        pool: buyerPool,
+        baseVault: PublicKey.findProgramAddressSync(
+          [Buffer.from("pool_vault"), buyerPool.toBuffer(), PAYMENT_MINT_PUBKEY.toBuffer()],
+          ammProgram.programId
+        )[0],
+        tokenVault: PublicKey.findProgramAddressSync(
+          [Buffer.from("pool_vault"), buyerPool.toBuffer(), mintPda.toBuffer()],
+          ammProgram.programId
+        )[0],
+        depositorBaseAccount: await getAssociatedTokenAddress(PAYMENT_MINT_PUBKEY, buyerKeypair.publicKey),
+        depositorTokenAccount: await getAssociatedTokenAddress(mintPda, buyerKeypair.publicKey),
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([buyerKeypair])
+      .rpc();
+
+    // The migration pool is still free for 2.2
+    assert.isNull(await connection.getAccountInfo(migrationPool), "Migration pool should not exist yet");
+  });
+
+  it("2.2) Migrate the graduated token into the AMM pool", async () => {
+    const adminBaseAta = await getAssociatedTokenAddress(
+      PAYMENT_MINT_PUBKEY,
+      creatorKeypair.publicKey
+    );
+
+    // Pool and its reserves are PDAs of the AMM; the XyberToken PDA deposits the liquidity
+    const [poolPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), PAYMENT_MINT_PUBKEY.toBuffer(), mintPda.toBuffer(), xyberTokenPda.toBuffer()],
+      ammProgram.programId
+    );
+    const [poolBaseVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault"), poolPda.toBuffer(), PAYMENT_MINT_PUBKEY.toBuffer()],
+      ammProgram.programId
+    );
+    const [poolTokenVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault"), poolPda.toBuffer(), mintPda.toBuffer()],
+      ammProgram.programId
+    );
+
+    await program.methods
+      .migrate()
+      .accounts({
+        xyberCore: xyberCorePda,
+        admin: creatorKeypair.publicKey,
+        xyberToken: xyberTokenPda,
+        tokenSeed: tokenSeedKeypair.publicKey,
+        creator: creatorKeypair.publicKey,
+        mint: mintPda,
+        vaultTokenAccount: vaultTokenAccount,
+        escrowTokenAccount,
+        baseTokenMint: PAYMENT_MINT_PUBKEY,
+        adminTokenAccount: adminBaseAta,
+        // The creator is also the admin here, so their base ATA is the same
+        creatorTokenAccount: adminBaseAta,
+        feeVault: feeVaultPda,
+        pool: poolPda,
+        poolBaseVault,
+        poolTokenVault,
+        ammProgram: ammProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creatorKeypair])
+      .rpc();
+
+    // Everything left the curve: the escrow and the vault are empty, the pool holds the reserves
+    const escrowInfo = await getAccount(connection, escrowTokenAccount);
+    const vaultInfo = await getAccount(connection, vaultTokenAccount);
+    assert.equal(escrowInfo.amount.toString(), "0", "Escrow should be empty after migration");
+    assert.equal(vaultInfo.amount.toString(), "0", "Vault should be empty after migration");
+
+    const pool = await ammProgram.account.pool.fetch(poolPda);
+    assert.ok(pool.tokenMint.equals(mintPda), "Pool should trade the project token");
+    const poolBase = await getAccount(connection, poolBaseVault);
+    const poolTokens = await getAccount(connection, poolTokenVault);
+    console.log("Pool reserves (raw): base =", poolBase.amount.toString(), ", tokens =", poolTokens.amount.toString());
+    assert.ok(poolBase.amount > BigInt(0) && poolTokens.amount > BigInt(0), "Pool should hold both reserves");
+
+    const xyberState = await program.account.xyberToken.fetch(xyberTokenPda);
+    assert.ok(xyberState.migrated, "Token should be marked as migrated");
+  });
+
+  it("2.3) Withdrawn liquidity can't be withdrawn again or migrated", async () => {
+    // A second token, graduated by one large buy, whose liquidity the admin withdraws
+    const seed = Keypair.generate().publicKey;
+    const [xyberToken] = PublicKey.findProgramAddressSync(
+      [Buffer.from("xyber_token"), seed.toBuffer()],
+      program.programId
+    );
+    const [mint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("MINT"), seed.toBuffer()],
+      TOKEN_FACTORY_PROGRAM_ID
+    );
+    const [metadata] = PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), METAPLEX_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+      METAPLEX_PROGRAM_ID
+    );
+    const [creatorFeeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator_fee_vault"), seed.toBuffer()],
+      program.programId
+    );
+    const vault = await getAssociatedTokenAddress(mint, xyberToken, true);
+    const escrow = await getAssociatedTokenAddress(PAYMENT_MINT_PUBKEY, xyberToken, true);
+    const adminBaseAta = await getAssociatedTokenAddress(PAYMENT_MINT_PUBKEY, creatorKeypair.publicKey);
+
+    await program.methods
+      .mintFullSupplyInstruction({
+        name: tokenName,
+        symbol: tokenSymbol,
+        uri: tokenUri,
+        totalChains: 1,
+        tokenSeed: seed,
+      })
+      .accounts({
+        xyberCore: xyberCorePda,
+        xyberToken,
+        creator: creatorKeypair.publicKey,
+        mint,
+        vaultTokenAccount: vault,
+        metadataAccount: metadata,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenMetadataProgram: METAPLEX_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenFactoryProgram: TOKEN_FACTORY_PROGRAM_ID,
+        escrowTokenAccount: escrow,
+        paymentMint: PAYMENT_MINT_PUBKEY,
+        creatorFeeVault,
+        treasury: creatorKeypair.publicKey,
+        creatorTokenAccount: null,
+        feeVault: null,
+      })
+      .signers([creatorKeypair])
+      .rpc();
+
+    await program.methods
+      .buyExactInputInstruction(new BN(1000).mul(new BN(LAMPORTS_PER_TOKEN)), new BN(0))
+      .accounts({
+        xyberCore: xyberCorePda,
+        tokenSeed: seed,
+        buyer: buyerKeypair.publicKey,
+        xyberToken,
+        escrowTokenAccount: escrow,
+        paymentMint: PAYMENT_MINT_PUBKEY,
+        mint,
+        vaultTokenAccount: vault,
+        buyerTokenAccount: await getAssociatedTokenAddress(mint, buyerKeypair.publicKey),
+        buyerPaymentAccount: await getAssociatedTokenAddress(PAYMENT_MINT_PUBKEY, buyerKeypair.publicKey),
+        feeVault: feeVaultPda,
+        creatorFeeVault,
+        referral: buyerReferralPda,
+        referrer: null,
+        referralVault: null,
+        traderStats: buyerTraderStatsPda,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([buyerKeypair])
+      .rpc();
+    assert.ok((await program.account.xyberToken.fetch(xyberToken)).isGraduated, "Token should be graduated");
+
+    const withdraw = () =>
+      program.methods
+        .withdrawLiquidity()
+        .accounts({
+          admin: creatorKeypair.publicKey,
+          xyberCore: xyberCorePda,
+          xyberToken,
+          tokenSeed: seed,
+          creator: creatorKeypair.publicKey,
+          escrowTokenAccount: escrow,
+          baseTokenMint: PAYMENT_MINT_PUBKEY,
+          mint,
+          vaultTokenAccount: vault,
+          adminTokenAccount: adminBaseAta,
+          adminVaultAccount: await getAssociatedTokenAddress(mint, creatorKeypair.publicKey),
+          // The creator is also the admin here, so their base ATA is the same
+          creatorTokenAccount: adminBaseAta,
+          feeVault: feeVaultPda,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([creatorKeypair])
+        .rpc();
+
+    await withdraw();
+    assert.equal((await tokenBalance(escrow)).toString(), "0", "Escrow should be empty after the withdrawal");
+    assert.ok((await program.account.xyberToken.fetch(xyberToken)).withdrawn, "Token should be marked as withdrawn");
+
+    // A second withdrawal fails instead of emitting an empty LiquidityWithdrawn
+    try {
+      await withdraw();
+      assert.fail("Withdrawing twice should fail");
+    } catch (err) {
+      assert.include(err.toString(), "AlreadyWithdrawn");
+    }
+
+    // And the withdrawn liquidity can't be migrated either
+    const [pool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), PAYMENT_MINT_PUBKEY.toBuffer(), mint.toBuffer(), xyberToken.toBuffer()],
+      ammProgram.programId
+    );
+    try {
+      await program.methods
+        .migrate()
+        .accounts({
+          xyberCore: xyberCorePda,
+          admin: creatorKeypair.publicKey,
+          xyberToken,
+          tokenSeed: seed,
+          creator: creatorKeypair.publicKey,
+          mint,
+          vaultTokenAccount: vault,
+          escrowTokenAccount: escrow,
+          baseTokenMint: PAYMENT_MINT_PUBKEY,
+          adminTokenAccount: adminBaseAta,
+          creatorTokenAccount: adminBaseAta,
+          feeVault: feeVaultPda,
+          pool,
+          poolBaseVault: PublicKey.findProgramAddressSync(
+            [Buffer.from("pool_vault"), pool.toBuffer(), PAYMENT_MINT_PUBKEY.toBuffer()],
+            ammProgram.programId
+          )[0],
+          poolTokenVault: PublicKey.findProgramAddressSync(
+            [Buffer.from("pool_vault"), pool.toBuffer(), mint.toBuffer()],
+            ammProgram.programId
+          )[0],
+          ammProgram: ammProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([creatorKeypair])
+        .rpc();
+      assert.fail("Migrating withdrawn liquidity should fail");
+    } catch (err) {
+      assert.include(err.toString(), "AlreadyWithdrawn");
+    }
+  });
 });
\ No newline at end of file
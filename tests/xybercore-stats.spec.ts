@@ -41,10 +41,9 @@ describe("XyberCore Stats Only", () => {
     it("Fetches XyberCore and displays info (human-readable)", async () => {
         const xyberCoreState = await program.account.xyberCore.fetch(xyberCorePda);
 
-        // Convert each of the BN fields from hex to decimal
-        const aTotalTokensDecimal = new anchor.BN(xyberCoreState.bondingCurve.aTotalTokens, 16).toString(10);
-        const kVirtualPoolOffsetDecimal = new anchor.BN(xyberCoreState.bondingCurve.kVirtualPoolOffset, 16).toString(10);
-        const cBondingScaleFactorDecimal = new anchor.BN(xyberCoreState.bondingCurve.cBondingScaleFactor, 16).toString(10);
+        // The curve is an enum: { smooth: { 0: {...} } }, { linear: { 0: {...} } }, ...
+        const [curveKind, curveVariant] = Object.entries(xyberCoreState.bondingCurve)[0] as [string, any];
+        const curveParams = curveVariant["0"];
 
         console.log("=== XyberCore State (human-readable) ===");
         console.log("admin =", xyberCoreState.admin.toBase58 ? xyberCoreState.admin.toBase58() : xyberCoreState.admin);
@@ -52,10 +51,10 @@ describe("XyberCore Stats Only", () => {
         console.log("acceptedBaseMint =", xyberCoreState.acceptedBaseMint.toBase58 ? xyberCoreState.acceptedBaseMint.toBase58() : xyberCoreState.acceptedBaseMint);
 
         // Bonding Curve fields (raw decimal integers)
-        console.log("\n-- Bonding Curve --");
-        console.log("aTotalTokens =", aTotalTokensDecimal);
-        console.log("kVirtualPoolOffset =", kVirtualPoolOffsetDecimal);
-        console.log("cBondingScaleFactor =", cBondingScaleFactorDecimal);
+        console.log(`\n-- Bonding Curve (${curveKind}) --`);
+        for (const [field, value] of Object.entries(curveParams)) {
+            console.log(`${field} =`, new anchor.BN(value as any, 16).toString(10));
+        }
 
         assert(xyberCoreState, "Failed to fetch XyberCore state");
     });